@@ -0,0 +1,219 @@
+# Deutsche Übersetzung. Fehlende Meldungen werden auf Englisch angezeigt (siehe en.ftl).
+
+## Fehler
+
+error = Fehler: { $message }
+error-file-exists =
+    Eine Datei namens '{ $path }' existiert bereits.
+    Wähle einen anderen Projektnamen oder entferne die vorhandene Datei.
+error-directory-exists =
+    Das Verzeichnis '{ $path }' existiert bereits!
+
+    Um den Konflikt zu lösen, kannst du:
+    1. Einen anderen Projektnamen wählen
+    2. Das vorhandene Verzeichnis entfernen: rm -rf { $path }
+    3. Das Projekt an einem anderen Ort anlegen
+error-tool-missing =
+    `{ $tool }` ist nicht installiert. Installiere es mit:
+    {"  "}{ $install }
+error-insufficient-space =
+    Nicht genug Speicherplatz in '{ $path }': die Vorlage braucht etwa { $needed } MB, frei sind nur { $available } MB.
+    Schaffe Platz oder lege das Projekt woanders an.
+error-cancelled = Abgebrochen, das unfertige Projekt wurde entfernt
+
+## hexstack new
+
+creating = Erstelle Projekt `{ $name }`
+creating-services = Erstelle Projekt `{ $name }` mit { $count } Services
+creating-from-template = Erstelle Projekt `{ $name }` aus { $template }
+creating-without-frontend = Erstelle Projekt `{ $name }` ohne Frontend
+creating-with-frontend = Erstelle Projekt `{ $name }` mit Frontend
+creating-with-frontend-apps = Erstelle Projekt `{ $name }` mit { $count } Frontend-Apps
+
+prompt-template = Wähle die Vorlage (zum Filtern tippen)
+template-hexstack-stack = Hexstack-Stack (Komponenten wählen)
+prompt-app-name = Name der { $frontend }-App (apps/<name>)
+prompt-desktop = Das Frontend in eine Tauri-Desktop-App packen?
+prompt-database = Mit welcher Datenbank soll sich lume verbinden?
+database-none = Keine
+prompt-styling = Wie soll das Frontend gestaltet werden?
+styling-css = Einfaches CSS
+styling-tailwind = Tailwind CSS
+prompt-start-database = Die Datenbank jetzt in Docker starten und ihr Schema laden?
+prompt-create = Projekt erstellen?
+create-yes = Ja, erstellen
+create-go-back = Nein, zurück zu den Antworten
+create-cancel = Abbrechen
+cancelled = Abgebrochen, es wurde nichts erstellt
+
+## Der Assistent von hexstack new
+
+wizard-step-name = Name
+wizard-step-components = Komponenten
+wizard-step-frontends = Frontend
+wizard-step-package-manager = Paketmanager
+wizard-step-extras = Extras
+wizard-prompt-name = Wie soll dein Projekt heißen?
+wizard-prompt-components = Welche Komponenten möchtest du?
+wizard-prompt-frontends = Welche Frontends möchtest du? Keins für ein reines Backend-Projekt
+wizard-prompt-package-manager = Welcher Paketmanager soll das Frontend installieren?
+wizard-prompt-extras = Welche Extras möchtest du?
+wizard-detect-package-manager = Der zur Lock-Datei der Vorlage passende, sonst npm
+wizard-type-to-filter = Zum Filtern tippen
+wizard-filter = Filter:
+wizard-no-matches = Keine Treffer
+wizard-key-filter = tippen filtert
+wizard-key-move = ↑↓ bewegen
+wizard-key-select = Leertaste wählt
+wizard-key-next = Enter weiter
+wizard-key-finish = Enter fertig
+wizard-key-back = Esc zurück
+wizard-key-quit = Strg-C beenden
+wizard-needs-terminal = hexstack new braucht ein Terminal, um nach { $steps } zu fragen; gib sie stattdessen als Optionen an
+
+## Fortschritt beim Erstellen des Projekts
+
+progress-generating-main = Erzeuge main.rs aus { $template }...
+progress-placeholders = Fülle die Platzhalter der Vorlage aus...
+progress-git-init = Lege ein neues git-Repository an...
+progress-no-template = Keine passende Vorlage gefunden, die Standard-main.rs bleibt
+progress-cargo-update = Aktualisiere die Cargo-Abhängigkeiten...
+progress-node-install = Installiere die Abhängigkeiten in { $dir } mit { $manager }...
+progress-service = Erzeuge den Service { $service }...
+progress-app = Füge die App { $app } hinzu ({ $frontend })...
+progress-github-repo = Lege das GitHub-Repository an...
+progress-initial-commit = Erstelle den ersten Commit...
+progress-complete = Projekt fertig eingerichtet!
+progress-cancelled = Abgebrochen
+database-starting = Starte { $database } mit docker compose...
+database-waiting = Warte, bis die Datenbank Verbindungen annimmt...
+database-up = Die Datenbank läuft
+database-running = Führe `{ $command }` aus...
+database-ready = Die Datenbank läuft und ist bereit
+
+warn-unverified = Die Vorlage '{ $template }' passt nicht zu ihrer veröffentlichten Prüfsumme, wegen --allow-unverified geht es trotzdem weiter
+warn-no-toolchain = { $template } gibt in ihrem Manifest keine [toolchain] an, daher wurden keine Versionen festgelegt
+warn-kept-file = Die Datei { $path } der Vorlage bleibt, statt eine neue zu erzeugen
+warn-archive-fallback = { $reason }, lade stattdessen { $url } herunter
+archive-clone-failing = git clone schlägt wiederholt fehl
+archive-git-missing = git ist nicht installiert
+warn-download-failed = Der Download ist auch fehlgeschlagen: { $error }
+warn-no-repository = Das Projekt hat kein eigenes git-Repository, daher wurde kein GitHub-Repository angelegt
+warn-github-failed = Das GitHub-Repository konnte nicht angelegt werden, das Projekt ist lokal committet: { $error }
+warn-commit-failed = Der erste Commit konnte nicht erstellt werden, die Änderungen sind nicht committet: { $error }
+warn-not-versioned = Das Projekt liegt in keinem git-Repository und --no-reinit hat keines angelegt, es steht also nicht unter Versionskontrolle
+warn-nested-repository = Lege ein Repository innerhalb dessen in { $path } an; mit --no-reinit bleibt das Projekt in diesem
+database-failed = Die Datenbank konnte nicht gestartet werden: { $error }
+database-start-yourself = Starte sie selbst mit `docker compose up -d db`
+database-compose-failed = `docker compose up -d db` ist mit { $status } fehlgeschlagen
+database-not-ready = Die Datenbank war nach { $seconds } Sekunden nicht bereit
+database-command-failed = `{ $command }` ist mit { $status } fehlgeschlagen
+cargo-update-not-run = cargo update konnte nicht ausgeführt werden
+cargo-update-failed = 'cargo update' in '{ $dir }' ist fehlgeschlagen: { $error }
+pushed = Hochgeladen nach { $url }
+warn-no-git = git ist nicht installiert, daher ist das Projekt noch kein git-Repository
+partial-kept = Das unfertige Projekt in '{ $path }' wurde behalten; entferne es, bevor du hexstack erneut ausführst
+partial-removed = Das unfertige Projekt in '{ $path }' wurde entfernt
+partial-not-removed = Das unfertige Projekt in '{ $path }' konnte nicht entfernt werden: { $error }
+
+## Auswahlen, die nicht zusammenpassen
+
+or = oder
+invalid-lume-alone =
+    Lume kann mit einem Frontend nicht allein verwendet werden!
+
+    Lume ist ein Query Builder und braucht mit einem Frontend entweder Ripress (HTTP-Framework) oder Wynd (WebSocket-Bibliothek) als Backend.
+
+    Wähle zusammen mit Lume mindestens eine dieser Komponenten:
+    • Ripress - Ein HTTP-Framework mit erstklassiger Developer Experience
+    • Wynd - Eine ereignisgesteuerte WebSocket-Bibliothek
+
+    Ohne Frontend kannst du Lume allein verwenden, oder es für Full-Stack-Anwendungen mit Ripress/Wynd kombinieren.
+invalid-addon = { $addon } wird zu einem Ripress-, Wynd- oder Lume-Backend hinzugefügt; wähle eines davon mit aus
+invalid-graphql = GraphQL wird von Ripress ausgeliefert; wähle ripress mit aus
+invalid-extra-requirement = Das Extra { $extra } baut auf { $name } auf; wähle { $component } mit aus
+invalid-k8s = Das Extra k8s deployt die Images aus den Dockerfiles des Extras docker; füge docker hinzu
+invalid-frontend-host = { $frontend } braucht { $hosts } im Projekt, um ausgeliefert zu werden
+invalid-desktop-frontend = Eine Tauri-Desktop-App braucht ein Frontend, das sie einbettet
+invalid-desktop-server-rendered = { $frontend } wird auf dem Server gerendert und kann nicht in Tauri eingebettet werden
+invalid-tailwind = Tailwind kann zu React- und Svelte-Frontends hinzugefügt werden
+invalid-apps-desktop = Tauri-Desktop-Apps unterstützen nur ein Frontend
+invalid-app-name = Ungültiger App-Name '{ $name }': erlaubt sind Buchstaben, Ziffern, '-' und '_'
+invalid-app-duplicate = Zwei Frontend-Apps heißen '{ $name }'
+invalid-app-frontend = Unbekanntes Frontend '{ $frontend }'
+invalid-app-server-rendered = { $frontend } wird vom Backend gerendert und kann nicht eine von mehreren Frontend-Apps sein
+invalid-services = Mindestens ein Service braucht Ripress, Wynd oder Lume
+
+## Die Zusammenfassung, die vor dem Erstellen bestätigt wird
+
+summary = Zusammenfassung
+summary-name = Name:
+summary-template = Vorlage:
+summary-no-template = keine passt, die Standard-main.rs bleibt
+summary-services = Services:
+summary-components = Komponenten:
+summary-frontend = Frontend:
+summary-desktop = { $frontend } in einer Tauri-Desktop-App
+summary-database = Datenbank:
+summary-styling = Styling:
+summary-extras = Extras:
+summary-path = Pfad:
+summary-commands = Befehle:
+summary-none = keine
+
+## Nächste Schritte, nachdem das Projekt erstellt wurde
+
+created = Projekt '{ $name }' erfolgreich erstellt!
+next-steps = Nächste Schritte:
+components-added = Hinzugefügte Komponenten:
+example = Beispiel: { $description }
+example-open = Öffne http://localhost:3000, sobald es läuft; die README erklärt den Code
+database = Datenbank: { $database }
+database-env = Kopiere .env.example nach .env für DATABASE_URL
+database-seed = lädt die Testdaten aus seeds/
+tracing-middleware = Protokolliere jede Anfrage, indem du die Tracing-Middleware registrierst:
+graphql-routes = Stelle die GraphQL-API bereit, indem du ihre Routen registrierst:
+graphql-graphiql = GraphiQL: http://localhost:3000/graphiql
+component-ripress = Ein HTTP-Framework mit erstklassiger Developer Experience
+component-wynd = Eine ereignisgesteuerte WebSocket-Bibliothek
+component-lume = Ein einfacher, intuitiver Query Builder nach dem Vorbild von Drizzle
+component-redis = Ein Redis-Client mit Caching-Helfern
+component-jobs = Hintergrundjobs, ausgeführt von einem Worker-Binary
+component-graphql = Eine GraphQL-API mit GraphiQL und typisierten Frontend-Clients
+component-grpc = Ein tonic-gRPC-Service neben dem HTTP-Server
+component-scheduler = Wiederkehrende Aufgaben nach Cron-Zeitplänen
+openapi-routes = Stelle die dokumentierten Routen und die Spezifikation unter /openapi.json bereit:
+openapi-client = Erzeuge den Frontend-Client neu, nachdem du die API geändert hast:
+rate-limit-middleware = Begrenze Anfragen pro IP, indem du die Middleware vor deinen Routen registrierst:
+rate-limit-tune = Stelle sie mit RATE_LIMIT_PER_MINUTE und RATE_LIMIT_BURST in .env ein
+sessions-manager = Erzeuge den Session-Manager einmal und nutze ihn in Handlern und Middleware:
+otel-jaeger = Traces werden an Jaeger exportiert; sieh sie dir unter http://localhost:16686 an
+otel-shutdown = Sende die letzten Spans mit telemetry::shutdown(); bevor main endet
+admin-api = Stelle die Admin-API bereit und setze ADMIN_TOKEN in .env, um dich anzumelden:
+admin-client = Die Frontends bekommen einen Admin-Client in src/admin/api.ts
+uploads-route = Nimm Uploads an, indem du die Route mit einem Storage-Client registrierst:
+uploads-minio = Starte MinIO und lege den Uploads-Bucket an:
+shared-types = Erzeuge das TypeScript der Frontends neu, nachdem du shared/src/lib.rs geändert hast:
+grpc-server = Starte den gRPC-Server neben dem HTTP-Server (zum Bauen wird protoc gebraucht):
+scheduler-start = Starte die geplanten Aufgaben am Anfang von main (Zeitpläne stehen in der README):
+jobs-worker = Starte den Worker für Hintergrundjobs in einem anderen Terminal:
+email-mailhog = E-Mails gehen in der Entwicklung an MailHog; lies sie unter http://localhost:8025
+tests-run = Führe alle Testsuiten mit `hexstack test` aus
+e2e-run = Führe die End-to-End-Tests aus, die die Dev-Server selbst starten:
+devcontainer-open = Öffne das Projekt in VS Code und wähle "Reopen in Container";
+devcontainer-debug = "Debug backend" und "Full stack" findest du in der Ansicht Run and Debug
+nix-shell = Betritt die Nix-Entwicklungsumgebung mit der Toolchain des Projekts:
+nix-direnv = oder `direnv allow`, um sie beim cd zu laden
+git-hooks = Der pre-commit-Hook führt fmt, clippy und die Frontend-Linter aus;
+git-hooks-install = andere aktivieren ihn nach dem Klonen mit ./scripts/install-hooks.sh
+docker-stack = Starte den ganzen Stack in Containern:
+k8s-deploy = Deploye nach Kubernetes, sobald die Images in deiner Registry liegen:
+k8s-values = setze vorher backend.env in values.yaml
+desktop-dev = Starte die Desktop-App mit `hexstack dev` im Projektverzeichnis
+frontend = Frontend: { $frontend }
+frontend-dev-server = Starte den Frontend-Dev-Server in einem anderen Terminal:
+run-everything = Oder starte alles auf einmal mit `hexstack dev`
+template-used = Verwendete Vorlage: { $template }
+services = Services:
+services-run-one = Starte einen einzelnen Service mit `cargo run -p <name>`
+services-shared = Die Typen, die die Services austauschen, liegen in { $dir }/, wovon jeder Service abhängt
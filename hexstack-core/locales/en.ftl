@@ -0,0 +1,224 @@
+# English messages, and the ones every other translation falls back to.
+#
+# To translate hexstack, copy this file to <language>.ftl, e.g. fr.ftl or pt-BR.ftl, and
+# translate the text after each `=`. Keep the message ids and the { $variables } as they are.
+# Messages you leave out are shown in English. Put the file in the `locales` directory next to
+# hexstack's config.toml to try it, and send it upstream to bundle it with the next release.
+
+## Errors
+
+error = Error: { $message }
+error-file-exists =
+    A file named '{ $path }' already exists.
+    Please choose a different project name or remove the existing file.
+error-directory-exists =
+    Directory '{ $path }' already exists!
+
+    To resolve this conflict, you can:
+    1. Choose a different project name
+    2. Remove the existing directory: rm -rf { $path }
+    3. Use a different location for your project
+error-tool-missing =
+    `{ $tool }` is not installed. Install it with:
+    {"  "}{ $install }
+error-insufficient-space =
+    Not enough disk space in '{ $path }': the template needs about { $needed } MB but only { $available } MB is free.
+    Free up some space or create the project somewhere else.
+error-cancelled = Cancelled, the partial project was removed
+
+## hexstack new
+
+creating = Creating project `{ $name }`
+creating-services = Creating project `{ $name }` with { $count } services
+creating-from-template = Creating project `{ $name }` from { $template }
+creating-without-frontend = Creating project `{ $name }` without frontend
+creating-with-frontend = Creating project `{ $name }` with frontend
+creating-with-frontend-apps = Creating project `{ $name }` with { $count } frontend apps
+
+prompt-template = Select the template you want (type to filter)
+template-hexstack-stack = Hexstack stack (choose components)
+prompt-app-name = Name of the { $frontend } app (apps/<name>)
+prompt-desktop = Wrap the frontend in a Tauri desktop app?
+prompt-database = Which database should lume connect to?
+database-none = None
+prompt-styling = How should the frontend be styled?
+styling-css = Plain CSS
+styling-tailwind = Tailwind CSS
+prompt-start-database = Start the database in docker now and load its schema?
+prompt-create = Create the project?
+create-yes = Yes, create it
+create-go-back = No, go back and change the answers
+create-cancel = Cancel
+cancelled = Cancelled, nothing was created
+
+## The hexstack new wizard
+
+wizard-step-name = Name
+wizard-step-components = Components
+wizard-step-frontends = Frontend
+wizard-step-package-manager = Package manager
+wizard-step-extras = Extras
+wizard-prompt-name = What should the name of your project be?
+wizard-prompt-components = Which components do you want?
+wizard-prompt-frontends = Which frontends do you want? None for a backend-only project
+wizard-prompt-package-manager = Which package manager should install the frontend?
+wizard-prompt-extras = Which extras do you want?
+wizard-detect-package-manager = The one the template's lock file is for, npm without one
+wizard-type-to-filter = Type to filter
+wizard-filter = Filter:
+wizard-no-matches = No matches
+wizard-key-filter = type to filter
+wizard-key-move = ↑↓ move
+wizard-key-select = space select
+wizard-key-next = enter next
+wizard-key-finish = enter finish
+wizard-key-back = esc back
+wizard-key-quit = ctrl-c quit
+wizard-needs-terminal = hexstack new needs a terminal to ask for the { $steps }; pass them as flags instead
+
+## Progress while the project is created
+
+progress-generating-main = Generating main.rs from { $template }...
+progress-placeholders = Filling in template placeholders...
+progress-git-init = Initializing a fresh git repository...
+progress-no-template = No specific template found, keeping default main.rs
+progress-cargo-update = Updating Cargo dependencies...
+progress-node-install = Installing dependencies in { $dir } with { $manager }...
+progress-service = Generating the { $service } service...
+progress-app = Adding the { $app } app ({ $frontend })...
+progress-github-repo = Creating the GitHub repository...
+progress-initial-commit = Creating the initial commit...
+progress-complete = Project setup complete!
+progress-cancelled = Cancelled
+database-starting = Starting { $database } with docker compose...
+database-waiting = Waiting for the database to accept connections...
+database-up = Database is up
+database-running = Running `{ $command }`...
+database-ready = Database is up and ready
+
+warn-unverified = Template '{ $template }' does not match its published checksum, continuing because of --allow-unverified
+warn-no-toolchain = { $template } doesn't declare a [toolchain] in its manifest, so no versions were pinned
+warn-kept-file = Keeping the template's { $path } instead of generating one
+warn-archive-fallback = { $reason }, downloading { $url } instead
+archive-clone-failing = git clone keeps failing
+archive-git-missing = git isn't installed
+warn-download-failed = The download failed too: { $error }
+warn-no-repository = The project has no git repository of its own, so no GitHub repository was created
+warn-github-failed = Could not create the GitHub repository, the project is committed locally: { $error }
+warn-commit-failed = Could not create the initial commit, the changes are left uncommitted: { $error }
+warn-not-versioned = The project isn't inside a git repository and --no-reinit skipped creating one, so it isn't under version control
+warn-nested-repository = Creating a repository nested inside the one at { $path }; use --no-reinit to keep the project in it
+database-failed = Could not provision the database: { $error }
+database-start-yourself = Start it yourself with `docker compose up -d db`
+database-compose-failed = `docker compose up -d db` failed with { $status }
+database-not-ready = The database wasn't ready after { $seconds } seconds
+database-command-failed = `{ $command }` failed with { $status }
+cargo-update-not-run = Failed to execute cargo update
+cargo-update-failed = Failed to run 'cargo update' in '{ $dir }': { $error }
+pushed = Pushed to { $url }
+warn-no-git = git isn't installed, so the project isn't a git repository yet
+partial-kept = Kept the partial project at '{ $path }'; remove it before running hexstack again
+partial-removed = Removed the partial project at '{ $path }'
+partial-not-removed = Couldn't remove the partial project at '{ $path }': { $error }
+
+## Choices that don't go together
+
+or = or
+invalid-lume-alone =
+    Lume cannot be used alone with a frontend!
+
+    Lume is a query builder that requires either Ripress (HTTP framework) or Wynd (WebSocket library) to provide the backend infrastructure when using a frontend.
+
+    Please select at least one of the following components along with Lume:
+    • Ripress - An HTTP Framework with best in class developer experience
+    • Wynd - An Event Driven WebSocket library
+
+    You can use Lume alone without a frontend, or combine it with Ripress/Wynd for full-stack applications.
+invalid-addon = { $addon } is added to a Ripress, Wynd or Lume backend; select one of them too
+invalid-graphql = GraphQL is served by Ripress; select ripress too
+invalid-extra-requirement = The { $extra } extra is built on { $name }; select { $component } too
+invalid-k8s = The k8s extra deploys the images built from the docker extra's Dockerfiles; add docker too
+invalid-frontend-host = { $frontend } needs { $hosts } in the project to serve it
+invalid-desktop-frontend = A Tauri desktop app needs a frontend to wrap
+invalid-desktop-server-rendered = { $frontend } is rendered by the server and can't be wrapped in Tauri
+invalid-tailwind = Tailwind can be added to React and Svelte frontends
+invalid-apps-desktop = Tauri desktop apps support a single frontend
+invalid-app-name = Invalid app name '{ $name }': use letters, numbers, '-' and '_'
+invalid-app-duplicate = Two frontend apps are named '{ $name }'
+invalid-app-frontend = Unknown frontend '{ $frontend }'
+invalid-app-server-rendered = { $frontend } is rendered by the backend and can't be one of several frontend apps
+invalid-services = At least one service needs Ripress, Wynd or Lume
+
+## The summary confirmed before the project is created
+
+summary = Summary
+summary-name = Name:
+summary-template = Template:
+summary-no-template = none matches, the default main.rs is kept
+summary-services = Services:
+summary-components = Components:
+summary-frontend = Frontend:
+summary-desktop = { $frontend } in a Tauri desktop app
+summary-database = Database:
+summary-styling = Styling:
+summary-extras = Extras:
+summary-path = Path:
+summary-commands = Commands:
+summary-none = none
+
+## Next steps, printed once the project is created
+
+created = Project '{ $name }' created successfully!
+next-steps = Next steps:
+components-added = Components added:
+example = Example: { $description }
+example-open = Open http://localhost:3000 once it's running; the README explains the code
+database = Database: { $database }
+database-env = Copy .env.example to .env for DATABASE_URL
+database-seed = load the fixtures in seeds/
+tracing-middleware = Log every request by registering the tracing middleware:
+graphql-routes = Serve the GraphQL API by registering its routes:
+graphql-graphiql = GraphiQL: http://localhost:3000/graphiql
+component-ripress = An HTTP Framework with best in class developer experience
+component-wynd = An Event Driven WebSocket library
+component-lume = A simple and intuitive Query Builder inspired by Drizzle
+component-redis = A Redis client with caching helpers
+component-jobs = Background jobs run by a worker binary
+component-graphql = A GraphQL API with GraphiQL and typed frontend clients
+component-grpc = A tonic gRPC service running beside the HTTP server
+component-scheduler = Recurring tasks on cron schedules
+openapi-routes = Serve the documented routes and the spec at /openapi.json:
+openapi-client = Regenerate the frontend client after changing the API:
+rate-limit-middleware = Limit requests per IP by registering the middleware before your routes:
+rate-limit-tune = Tune it with RATE_LIMIT_PER_MINUTE and RATE_LIMIT_BURST in .env
+sessions-manager = Create the session manager once and use it in handlers and middleware:
+otel-jaeger = Traces are exported to Jaeger; browse them at http://localhost:16686
+otel-shutdown = Flush the last spans before main returns with telemetry::shutdown();
+admin-api = Serve the admin API and set ADMIN_TOKEN in .env to sign in to it:
+admin-client = The frontends get an admin client in src/admin/api.ts
+uploads-route = Accept uploads by registering the route with a storage client:
+uploads-minio = Start MinIO and create the uploads bucket:
+shared-types = Regenerate the frontends' TypeScript after changing shared/src/lib.rs:
+grpc-server = Start the gRPC server beside the HTTP server (needs protoc to build):
+scheduler-start = Start the scheduled tasks at the top of main (see the README for schedules):
+jobs-worker = Start the background job worker in another terminal:
+email-mailhog = Emails go to MailHog in development; read them at http://localhost:8025
+tests-run = Run every test suite with `hexstack test`
+e2e-run = Run the end-to-end tests, which start the dev servers themselves:
+devcontainer-open = Open the project in VS Code and pick "Reopen in Container";
+devcontainer-debug = "Debug backend" and "Full stack" are in the Run and Debug view
+nix-shell = Enter the Nix dev shell with the project's toolchain:
+nix-direnv = or `direnv allow` to load it on cd
+git-hooks = The pre-commit hook runs fmt, clippy and the frontend linters;
+git-hooks-install = teammates enable it after cloning with ./scripts/install-hooks.sh
+docker-stack = Run the whole stack in containers:
+k8s-deploy = Deploy to Kubernetes once the images are pushed to your registry:
+k8s-values = set backend.env in values.yaml first
+desktop-dev = Start the desktop app with `hexstack dev` from the project directory
+frontend = Frontend: { $frontend }
+frontend-dev-server = In another terminal, start the frontend dev server:
+run-everything = Or run everything at once with `hexstack dev`
+template-used = Template used: { $template }
+services = Services:
+services-run-one = Run a single service with `cargo run -p <name>`
+services-shared = Types the services exchange live in { $dir }/, which every service depends on
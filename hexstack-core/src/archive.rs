@@ -0,0 +1,123 @@
+//! Template downloads as `.tar.gz` archives from the forge, for machines without git or when
+//! cloning keeps failing. See [`crate::hosts::Host::archive_url`] for the URLs.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use tracing::debug;
+
+use crate::error::HexstackError;
+use crate::proxy;
+use crate::retry::{RetryPolicy, should_retry_response};
+
+/// Downloads `url`, retrying under `policy` on network errors
+pub async fn download(url: &str, policy: RetryPolicy) -> Result<Vec<u8>> {
+    let client = proxy::client_builder()
+        .timeout(Duration::from_secs(120))
+        .user_agent(format!(
+            "hexstack/{} (+https://github.com/guru901/hexstack)",
+            env!("CARGO_PKG_VERSION")
+        ))
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build HTTP client: {}", e))?;
+    let response = policy
+        .run(
+            "The archive download",
+            || client.get(url).send(),
+            should_retry_response,
+        )
+        .await
+        .map_err(|e| HexstackError::NetworkError(format!("Failed to download {}: {}", url, e)))?;
+    if !response.status().is_success() {
+        return Err(HexstackError::NetworkError(format!(
+            "Failed to download {}: {}",
+            url,
+            response.status()
+        ))
+        .into());
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| HexstackError::NetworkError(format!("Failed to download {}: {}", url, e)))?;
+    Ok(bytes.to_vec())
+}
+
+/// Unpacks a forge archive into `dest`, dropping the `repo-rev/` directory every entry sits in.
+/// Returns the commit the archive was made from, which `git archive` records in the pax header.
+/// Links are skipped and paths leading out of `dest` refused.
+pub fn extract_tarball(archive: &[u8], dest: &Path) -> Result<Option<String>> {
+    let mut tarball = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+    let mut commit = None;
+    fs::create_dir_all(dest).with_context(|| format!("Failed to create '{}'", dest.display()))?;
+
+    for entry in tarball.entries().context("Failed to read the archive")? {
+        let mut entry = entry.context("Failed to read the archive")?;
+        let kind = entry.header().entry_type();
+        if kind.is_pax_global_extensions() {
+            commit = pax_commit(&mut entry)?.or(commit);
+            continue;
+        }
+
+        let path = entry.path().context("Archive entry has an invalid path")?;
+        let Some(relative) = strip_top_dir(&path) else {
+            continue;
+        };
+        let target = dest.join(&relative);
+        if kind.is_dir() {
+            fs::create_dir_all(&target)
+                .with_context(|| format!("Failed to create '{}'", target.display()))?;
+        } else if kind.is_file() {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create '{}'", parent.display()))?;
+            }
+            entry
+                .unpack(&target)
+                .with_context(|| format!("Failed to write '{}'", target.display()))?;
+        } else {
+            debug!(
+                "Skipping {} in the archive ({:?})",
+                relative.display(),
+                kind
+            );
+        }
+    }
+
+    Ok(commit)
+}
+
+/// `path` without its first component, or `None` for the top directory itself and for paths
+/// that aren't plain relative paths
+fn strip_top_dir(path: &Path) -> Option<PathBuf> {
+    let mut components = path.components();
+    components.next();
+    let relative: PathBuf = components
+        .map(|component| match component {
+            Component::Normal(part) => Some(part),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    (!relative.as_os_str().is_empty()).then_some(relative)
+}
+
+/// The `comment` a pax global header carries, which holds the commit for `git archive` output
+fn pax_commit(entry: &mut tar::Entry<'_, impl Read>) -> Result<Option<String>> {
+    let Some(extensions) = entry.pax_extensions()? else {
+        return Ok(None);
+    };
+    for extension in extensions {
+        let extension = extension?;
+        if extension.key() == Ok("comment")
+            && let Ok(value) = extension.value()
+        {
+            let value = value.trim();
+            if value.len() == 40 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Ok(Some(value.to_string()));
+            }
+        }
+    }
+    Ok(None)
+}
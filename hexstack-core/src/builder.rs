@@ -0,0 +1,358 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::GlobalConfig;
+use crate::error::HexstackError;
+use crate::examples::Example;
+use crate::extras::{Database, Extras, OPTIONAL_EXTRAS, Styling};
+use crate::package_manager::PackageManager;
+use crate::progress::{EventHooks, ProgressReporter, ScaffoldEvent, SilentReporter};
+use crate::registry::{self, RegistryEntry};
+use crate::remote::Visibility;
+use crate::report::{CreateReport, Preview};
+use crate::retry::RetryPolicy;
+use crate::runner::CommandRunner;
+use crate::services::Service;
+use crate::setup::{FrontendApp, ProjectSetup};
+
+/// Scaffolds a project without any prompts, for tools embedding hexstack as a library.
+/// `hexstack new` collects its answers interactively and then goes through this too.
+///
+/// ```no_run
+/// # async fn scaffold() -> anyhow::Result<()> {
+/// let report = hexstack_core::ProjectSetupBuilder::new("my-app")
+///     .components(["ripress", "lume"])
+///     .frontend("react")
+///     .database(Some(hexstack_core::Database::Postgres))
+///     .extra("docker")
+///     .target_dir("/tmp/projects")
+///     .create()
+///     .await?;
+/// println!("Cloned {:?} into {}", report.template, report.project_path.display());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProjectSetupBuilder {
+    name: String,
+    components: Vec<String>,
+    frontend: Option<String>,
+    apps: Vec<FrontendApp>,
+    target_dir: PathBuf,
+    keep_partial: bool,
+    template: Option<RegistryEntry>,
+    pinned_commit: Option<String>,
+    allow_unverified: bool,
+    desktop: bool,
+    extras: Extras,
+    extra_keys: Vec<String>,
+    example: Option<Example>,
+    services: Vec<Service>,
+    start_database: bool,
+    remote_registry: bool,
+    cancel: CancellationToken,
+    runner: Option<Arc<dyn CommandRunner>>,
+    retry: RetryPolicy,
+    archive_fallback: bool,
+    install_frontend: bool,
+    package_manager: Option<PackageManager>,
+    initial_commit: Option<bool>,
+    default_branch: Option<String>,
+    reinit_git: bool,
+    create_remote: Option<Visibility>,
+    hooks: EventHooks,
+}
+
+impl ProjectSetupBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            remote_registry: true,
+            archive_fallback: true,
+            install_frontend: true,
+            reinit_git: true,
+            ..Self::default()
+        }
+    }
+
+    /// Backend components such as `ripress`, `wynd`, `lume` or `redis`
+    pub fn components<I, S>(mut self, components: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.components = components.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn frontend(mut self, frontend: impl Into<String>) -> Self {
+        self.frontend = Some(frontend.into());
+        self
+    }
+
+    /// Several frontends laid out under `apps/`; the first one is the project template's
+    pub fn frontend_apps(mut self, apps: Vec<FrontendApp>) -> Self {
+        self.frontend = apps.first().map(|app| app.frontend.clone());
+        self.apps = if apps.len() > 1 { apps } else { Vec::new() };
+        self
+    }
+
+    /// Directory the project directory is created in, instead of the current one
+    pub fn target_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.target_dir = dir.into();
+        self
+    }
+
+    /// Uses a template repository or registry entry instead of matching the components
+    pub fn template(mut self, template: RegistryEntry) -> Self {
+        self.components = template.components.clone();
+        self.frontend = template.frontend.clone();
+        self.desktop = template.desktop;
+        self.template = Some(template);
+        self
+    }
+
+    /// Checks the template out at `commit` and keeps its Cargo.lock, as `--locked` does
+    pub fn pinned_commit(mut self, commit: impl Into<String>) -> Self {
+        self.pinned_commit = Some(commit.into());
+        self
+    }
+
+    pub fn allow_unverified(mut self, allow: bool) -> Self {
+        self.allow_unverified = allow;
+        self
+    }
+
+    pub fn desktop(mut self, desktop: bool) -> Self {
+        self.desktop = desktop;
+        self
+    }
+
+    pub fn database(mut self, database: Option<Database>) -> Self {
+        self.extras.database = database;
+        self
+    }
+
+    pub fn styling(mut self, styling: Styling) -> Self {
+        self.extras.styling = styling;
+        self
+    }
+
+    /// Adds an extra by its `--extras` key, e.g. `docker` or `ci`
+    pub fn extra(mut self, key: impl Into<String>) -> Self {
+        self.extra_keys.push(key.into());
+        self
+    }
+
+    /// Replaces the extras altogether, e.g. with the ones recorded in a lock file
+    pub fn extras(mut self, extras: Extras) -> Self {
+        self.extras = extras;
+        self
+    }
+
+    pub fn example(mut self, example: Example) -> Self {
+        self.example = Some(example);
+        self
+    }
+
+    /// Generates these services under `services/` instead of a single backend
+    pub fn services(mut self, services: Vec<Service>) -> Self {
+        self.services = services;
+        self
+    }
+
+    /// Starts the database in docker and loads its schema once the project exists
+    pub fn start_database(mut self, start: bool) -> Self {
+        self.start_database = start;
+        self
+    }
+
+    /// Whether to fetch the remote template registry; only the built-in catalog and the
+    /// templates from the global config are used when off
+    pub fn remote_registry(mut self, enabled: bool) -> Self {
+        self.remote_registry = enabled;
+        self
+    }
+
+    /// Leaves the partial project on disk when a step fails, e.g. to inspect what went wrong;
+    /// by default it is removed
+    pub fn keep_partial(mut self, keep: bool) -> Self {
+        self.keep_partial = keep;
+        self
+    }
+
+    /// Stops the scaffold when `token` is cancelled, removing the partial project; `create`
+    /// then returns [`HexstackError::Cancelled`]
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+
+    /// Runs git, cargo and docker through `runner` instead of spawning them directly
+    pub fn command_runner(mut self, runner: Arc<dyn CommandRunner>) -> Self {
+        self.runner = Some(runner);
+        self
+    }
+
+    /// How often a template clone that failed on a network error is attempted; three times,
+    /// backing off from 500ms, by default
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Whether templates are downloaded as archives when git isn't installed or cloning keeps
+    /// failing on network errors; on by default
+    pub fn archive_fallback(mut self, fallback: bool) -> Self {
+        self.archive_fallback = fallback;
+        self
+    }
+
+    /// Whether dependencies are installed in Node frontends once the project exists; on by
+    /// default
+    pub fn install_frontend(mut self, install: bool) -> Self {
+        self.install_frontend = install;
+        self
+    }
+
+    /// Package manager for Node frontends. Without one, the `package_manager` of the global
+    /// config is used, then the one each frontend's lock file belongs to, then npm.
+    pub fn package_manager(mut self, manager: Option<PackageManager>) -> Self {
+        self.package_manager = manager;
+        self
+    }
+
+    /// Whether the finished project is committed as "Initial commit from hexstack (...)".
+    /// Without a choice here the `initial_commit` setting of the global config decides, and
+    /// the project is left uncommitted when that isn't set either.
+    pub fn initial_commit(mut self, commit: bool) -> Self {
+        self.initial_commit = Some(commit);
+        self
+    }
+
+    /// Branch the project's repository starts on. Without one, the `default_branch` of the
+    /// global config is used, then git's `init.defaultBranch`, then `main`.
+    pub fn default_branch(mut self, branch: impl Into<String>) -> Self {
+        self.default_branch = Some(branch.into());
+        self
+    }
+
+    /// Whether the project gets a git repository of its own; on by default. Turn it off to
+    /// create the project inside an existing repository, such as a monorepo, without nesting
+    /// another one. The template's history is removed either way.
+    pub fn reinit_git(mut self, reinit: bool) -> Self {
+        self.reinit_git = reinit;
+        self
+    }
+
+    /// Creates a GitHub repository named after the project with the `gh` CLI, adds it as
+    /// `origin` and pushes the initial commit, which this implies
+    pub fn create_remote(mut self, visibility: Option<Visibility>) -> Self {
+        self.create_remote = visibility;
+        self
+    }
+
+    /// Calls `hook` with every [`ScaffoldEvent`], e.g. to log clones or drive a custom UI
+    pub fn on_event(mut self, hook: impl Fn(&ScaffoldEvent) + Send + Sync + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Generates the project without reporting progress; see [`Self::create_with`]
+    pub async fn create(self) -> Result<CreateReport, HexstackError> {
+        self.create_with(&SilentReporter).await
+    }
+
+    /// Generates the project, sending its progress to `reporter`
+    pub async fn create_with(
+        self,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<CreateReport, HexstackError> {
+        Ok(self.scaffold(reporter).await?)
+    }
+
+    /// What [`create`](Self::create) would do, without fetching or writing anything. The
+    /// template is picked from the built-in and custom templates; the registry is only fetched
+    /// by the scaffold itself.
+    pub async fn preview(&self) -> Result<Preview, HexstackError> {
+        let project_setup = self.clone().into_setup(false).await?;
+        Ok(project_setup.preview().await)
+    }
+
+    async fn scaffold(self, reporter: &dyn ProgressReporter) -> Result<CreateReport> {
+        // A target directory created here is removed again if the scaffold fails and leaves it empty
+        let target_dir = self.target_dir.clone();
+        let keep_partial = self.keep_partial;
+        let created_target = !target_dir.as_os_str().is_empty() && !target_dir.exists();
+        let remote_registry = self.remote_registry;
+        let project_setup = self.into_setup(remote_registry).await?;
+        if !target_dir.as_os_str().is_empty() {
+            fs::create_dir_all(&target_dir)
+                .with_context(|| format!("Failed to create '{}'", target_dir.display()))?;
+        }
+        let result = project_setup.build(reporter).await;
+        if result.is_err() && created_target && !keep_partial {
+            let _ = fs::remove_dir(&target_dir);
+        }
+        result
+    }
+
+    /// The [`ProjectSetup`] for these options, with the registry's templates when
+    /// `remote_registry` is set
+    async fn into_setup(self, remote_registry: bool) -> Result<ProjectSetup> {
+        let mut extras = self.extras;
+        for key in &self.extra_keys {
+            if !extras.enable(key) {
+                anyhow::bail!(
+                    "Invalid extra '{}'. Valid values: {}",
+                    key,
+                    OPTIONAL_EXTRAS.map(|(k, _)| k).join(", ")
+                );
+            }
+        }
+
+        let global_config = GlobalConfig::load().unwrap_or_default();
+        let mut project_setup = ProjectSetup::new(self.name, self.components, self.frontend).await;
+        if remote_registry
+            && let Some(remote_templates) = registry::load_remote_templates(&global_config).await
+        {
+            project_setup.extend_templates(remote_templates);
+        }
+        let mut custom_entries = global_config.templates.clone();
+        custom_entries.extend(self.template.clone());
+        project_setup.extend_templates(registry::custom_templates(&custom_entries));
+        project_setup.template_key = self.template.map(|t| t.key);
+        project_setup.pinned_commit = self.pinned_commit;
+        project_setup.allow_unverified = self.allow_unverified;
+        project_setup.desktop = self.desktop;
+        project_setup.apps = self.apps;
+        project_setup.extras = extras;
+        project_setup.example = self.example;
+        project_setup.start_database = self.start_database;
+        project_setup.services = self.services;
+        project_setup.target_dir = self.target_dir.clone();
+        project_setup.keep_partial = self.keep_partial;
+        project_setup.cancel = self.cancel;
+        project_setup.hooks = self.hooks;
+        project_setup.retry = self.retry;
+        project_setup.archive_fallback = self.archive_fallback;
+        project_setup.install_frontend = self.install_frontend;
+        project_setup.package_manager = self.package_manager.or(global_config.package_manager);
+        project_setup.initial_commit = self
+            .initial_commit
+            .or(global_config.initial_commit)
+            .unwrap_or(false);
+        project_setup.reinit_git = self.reinit_git;
+        project_setup.create_remote = self.create_remote;
+        project_setup.default_branch = self
+            .default_branch
+            .or_else(|| global_config.default_branch.clone());
+        if let Some(runner) = self.runner {
+            project_setup.runner = runner;
+        }
+        Ok(project_setup)
+    }
+}
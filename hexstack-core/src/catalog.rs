@@ -0,0 +1,173 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::config::GlobalConfig;
+use crate::registry::{self, RegistryEntry};
+use crate::setup::{
+    ADDON_COMPONENTS, FRONTENDS, NODE_FRONTENDS, ProjectSetup, ProjectTemplate, display_name,
+    frontend_hosts, is_server_rendered, match_template,
+};
+
+/// What hexstack can scaffold: its components, frontends and templates, for docs sites and
+/// GUIs to enumerate
+///
+/// ```
+/// let catalog = hexstack_core::Catalog::builtin();
+/// let template = catalog.find(&["ripress"], Some("react"), false).unwrap();
+/// assert_eq!(template.name, "Ripress + React");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    templates: HashMap<String, ProjectTemplate>,
+}
+
+/// A backend component, e.g. `ripress`, or an add-on such as `redis`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComponentInfo {
+    pub key: String,
+    pub name: String,
+    pub description: String,
+    /// Added on top of a template rather than picking one; needs Ripress, Wynd or Lume
+    pub addon: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FrontendInfo {
+    pub key: String,
+    pub name: String,
+    /// Built with npm
+    pub node: bool,
+    /// Rendered by the Ripress app itself rather than a separate frontend
+    pub server_rendered: bool,
+    /// Components that can serve it
+    pub hosts: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CatalogTemplate {
+    pub key: String,
+    pub name: String,
+    pub description: String,
+    /// Repository to clone
+    pub url: String,
+    /// Directory inside the repository holding the template
+    pub subdir: Option<String>,
+    pub components: Vec<String>,
+    pub frontend: Option<String>,
+    /// Wraps the frontend in a Tauri desktop app
+    pub desktop: bool,
+}
+
+impl Catalog {
+    /// The official templates built into this release
+    pub fn builtin() -> Self {
+        Self {
+            templates: ProjectSetup::template_matrix().into_iter().collect(),
+        }
+    }
+
+    /// The official templates plus the remote registries and the custom templates from the
+    /// global config, like `hexstack new` sees them
+    pub async fn load() -> Self {
+        let mut catalog = Self::builtin();
+        let config = GlobalConfig::load().unwrap_or_default();
+        if let Some(remote) = registry::load_remote_templates(&config).await {
+            catalog.templates.extend(remote);
+        }
+        catalog.extend(&config.templates);
+        catalog
+    }
+
+    /// Adds templates such as custom registry entries, replacing ones with the same key
+    pub fn extend(&mut self, entries: &[RegistryEntry]) {
+        self.templates.extend(registry::custom_templates(entries));
+    }
+
+    pub fn components() -> Vec<ComponentInfo> {
+        ProjectSetup::component_choices()
+            .into_iter()
+            .map(|(key, config)| ComponentInfo {
+                name: display_name(&key),
+                description: config.description,
+                addon: ADDON_COMPONENTS.contains(&key.as_str()),
+                key,
+            })
+            .collect()
+    }
+
+    pub fn frontends() -> Vec<FrontendInfo> {
+        FRONTENDS
+            .iter()
+            .map(|key| FrontendInfo {
+                key: key.to_string(),
+                name: display_name(key),
+                node: NODE_FRONTENDS.contains(key),
+                server_rendered: is_server_rendered(key),
+                hosts: frontend_hosts(key).iter().map(|h| h.to_string()).collect(),
+            })
+            .collect()
+    }
+
+    /// Every template, sorted by key
+    pub fn templates(&self) -> Vec<CatalogTemplate> {
+        let mut templates: Vec<CatalogTemplate> = self
+            .templates
+            .iter()
+            .map(|(key, template)| describe(key, template))
+            .collect();
+        templates.sort_by(|a, b| a.key.cmp(&b.key));
+        templates
+    }
+
+    pub fn template(&self, key: &str) -> Option<CatalogTemplate> {
+        self.templates.get(key).map(|t| describe(key, t))
+    }
+
+    /// The template `hexstack new` would pick for these components and frontend
+    pub fn find(
+        &self,
+        components: &[&str],
+        frontend: Option<&str>,
+        desktop: bool,
+    ) -> Option<CatalogTemplate> {
+        let components: Vec<String> = components.iter().map(|c| c.to_lowercase()).collect();
+        let template = match_template(&self.templates, &components, frontend, desktop)?;
+        let key = self
+            .templates
+            .iter()
+            .find(|(_, t)| std::ptr::eq(*t, template))
+            .map(|(key, _)| key.as_str())?;
+        Some(describe(key, template))
+    }
+}
+
+fn describe(key: &str, template: &ProjectTemplate) -> CatalogTemplate {
+    let (url, subdir) = template.source();
+    let names: Vec<String> = template
+        .components
+        .iter()
+        .map(|c| display_name(c))
+        .collect();
+    let mut description = if names.is_empty() {
+        format!("Template from {}", url)
+    } else {
+        format!("{} backend", names.join(" + "))
+    };
+    if let Some(frontend) = &template.frontend {
+        description.push_str(&format!(" with a {} frontend", display_name(frontend)));
+    }
+    if template.desktop {
+        description.push_str(" in a Tauri desktop app");
+    }
+
+    CatalogTemplate {
+        key: key.to_string(),
+        name: template.name.clone(),
+        description,
+        url: url.to_string(),
+        subdir: subdir.map(str::to_string),
+        components: template.components.clone(),
+        frontend: template.frontend.clone(),
+        desktop: template.desktop,
+    }
+}
@@ -0,0 +1,210 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::package_manager::PackageManager;
+use crate::registry::RegistryEntry;
+
+pub const CONFIG_DIR_ENV: &str = "HEXSTACK_CONFIG_DIR";
+
+/// Overrides the directory hexstack caches the update check and registry indexes in
+pub const CACHE_DIR_ENV: &str = "HEXSTACK_CACHE_DIR";
+
+/// How often hexstack looks for a new release unless `update_check_interval_hours` says otherwise
+pub const DEFAULT_UPDATE_CHECK_INTERVAL_HOURS: u64 = 24;
+
+/// User-wide settings stored in `<config dir>/hexstack/config.toml`
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct GlobalConfig {
+    /// Additional registry indexes, merged after the official one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub registries: Vec<String>,
+    /// Individual template repositories registered with `hexstack template add`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<RegistryEntry>,
+    /// Set to `false` to never look for a new hexstack release
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_updates: Option<bool>,
+    /// Which releases hexstack updates to: `stable` (the default) or `beta` for prereleases too
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_channel: Option<UpdateChannel>,
+    /// Only update within this major version, e.g. `1` to stay on 1.x
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pin_major_version: Option<u64>,
+    /// Hours between checks for a new hexstack release; 0 checks on every run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_check_interval_hours: Option<u64>,
+    /// Package manager for Node frontends, instead of the one the template's lock file implies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_manager: Option<PackageManager>,
+    /// Set to `true` to commit new projects, as `hexstack new --initial-commit` does
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_commit: Option<bool>,
+    /// Branch new repositories start on, instead of git's `init.defaultBranch`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+    /// Set to `false` to stop `hexstack new` from saving its answers in `last_answers`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remember_answers: Option<bool>,
+    /// What the last interactive `hexstack new` was answered with, picked again the next time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_answers: Option<LastAnswers>,
+    /// Language for prompts and messages, e.g. `de`, instead of the one `LANG` names
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// The choices of an interactive `hexstack new`, offered as the defaults of the next one
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LastAnswers {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub frontends: Vec<String>,
+    /// `None` when the package manager was left to the template's lock file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_manager: Option<PackageManager>,
+}
+
+/// The releases an update may install
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    /// Releases without a prerelease tag
+    #[default]
+    Stable,
+    /// Stable releases plus alphas, betas and release candidates
+    Beta,
+}
+
+impl UpdateChannel {
+    /// Whether `version` is published on this channel
+    pub fn includes(self, version: &semver::Version) -> bool {
+        match self {
+            UpdateChannel::Stable => version.pre.is_empty(),
+            UpdateChannel::Beta => true,
+        }
+    }
+}
+
+impl GlobalConfig {
+    /// Directory holding hexstack's global files, overridable with `HEXSTACK_CONFIG_DIR`
+    pub fn dir() -> Result<PathBuf> {
+        if let Some(dir) = std::env::var_os(CONFIG_DIR_ENV).filter(|d| !d.is_empty()) {
+            return Ok(PathBuf::from(dir));
+        }
+
+        dirs::config_dir()
+            .map(|dir| dir.join("hexstack"))
+            .ok_or_else(|| anyhow::anyhow!("Could not determine the user config directory"))
+    }
+
+    pub fn path() -> Result<PathBuf> {
+        Ok(Self::dir()?.join("config.toml"))
+    }
+
+    /// Directory for what hexstack caches between runs, `<cache dir>/hexstack` unless
+    /// `HEXSTACK_CACHE_DIR` is set
+    pub fn cache_dir() -> Result<PathBuf> {
+        if let Some(dir) = std::env::var_os(CACHE_DIR_ENV).filter(|d| !d.is_empty()) {
+            return Ok(PathBuf::from(dir));
+        }
+
+        dirs::cache_dir()
+            .map(|dir| dir.join("hexstack"))
+            .ok_or_else(|| anyhow::anyhow!("Could not determine the user cache directory"))
+    }
+
+    /// Loads the global config, returning the defaults when the file does not exist yet
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::path()?)
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file '{}'", path.display()))?;
+
+        toml::from_str(&contents)
+            .with_context(|| format!("Invalid config file '{}'", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::path()?)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create config directory '{}'", parent.display())
+            })?;
+        }
+
+        let contents = toml::to_string_pretty(self).context("Failed to serialize config")?;
+        fs::write(path, contents)
+            .with_context(|| format!("Failed to write config file '{}'", path.display()))
+    }
+
+    /// How long the result of an update check is reused before crates.io is asked again
+    pub fn update_check_interval(&self) -> Duration {
+        let hours = self
+            .update_check_interval_hours
+            .unwrap_or(DEFAULT_UPDATE_CHECK_INTERVAL_HOURS);
+        Duration::from_secs(hours.saturating_mul(60 * 60))
+    }
+
+    /// The newest of `published` that the channel and major-version pin allow, when it is newer
+    /// than `current`
+    pub fn pick_update<'a>(
+        &self,
+        current: &semver::Version,
+        published: &'a [semver::Version],
+    ) -> Option<&'a semver::Version> {
+        let channel = self.update_channel.unwrap_or_default();
+        published
+            .iter()
+            .filter(|version| channel.includes(version))
+            .filter(|version| {
+                self.pin_major_version
+                    .is_none_or(|major| version.major == major)
+            })
+            .filter(|version| *version > current)
+            .max()
+    }
+
+    pub fn find_template(&self, key: &str) -> Option<&RegistryEntry> {
+        self.templates
+            .iter()
+            .find(|t| t.key.eq_ignore_ascii_case(key))
+    }
+
+    /// Registers a template repository, replacing an existing entry with the same key
+    pub fn add_template(&mut self, entry: RegistryEntry) {
+        self.templates
+            .retain(|t| !t.key.eq_ignore_ascii_case(&entry.key));
+        self.templates.push(entry);
+    }
+
+    /// Registers a registry index URL, returning `false` if it was already present
+    pub fn add_registry(&mut self, url: String) -> bool {
+        if self.registries.contains(&url) {
+            return false;
+        }
+        self.registries.push(url);
+        true
+    }
+
+    /// Removes a template key or registry URL, returning whether anything was removed
+    pub fn remove(&mut self, key_or_url: &str) -> bool {
+        let before = self.templates.len() + self.registries.len();
+        self.templates
+            .retain(|t| !t.key.eq_ignore_ascii_case(key_or_url));
+        self.registries.retain(|r| r != key_or_url);
+        before != self.templates.len() + self.registries.len()
+    }
+}
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+const MB: u64 = 1024 * 1024;
+
+/// Failures returned by the library, so tools embedding hexstack can match on them. Failures
+/// without a variant of their own are reported as [`HexstackError::Other`].
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum HexstackError {
+    /// The project or service name can't be used
+    #[error("{0}")]
+    InvalidName(String),
+    /// The project directory, or a file with its name, already exists
+    #[error("{}", directory_exists_message(path, *file))]
+    DirectoryExists { path: PathBuf, file: bool },
+    /// No template provides the requested components and frontend
+    #[error("{0}")]
+    TemplateNotFound(String),
+    /// A git command exited unsuccessfully
+    #[error("`git {command}` failed: {stderr}")]
+    GitFailed { command: String, stderr: String },
+    /// A registry, crates.io or another remote couldn't be reached or answered with an error
+    #[error("{0}")]
+    NetworkError(String),
+    /// A tool the command needs isn't on the PATH
+    #[error("{}", crate::t!("error-tool-missing", tool = tool.as_str(), install = install.as_str()))]
+    ToolMissing { tool: String, install: String },
+    /// The filesystem the project goes on doesn't have room for the template
+    #[error(
+        "{}",
+        crate::t!(
+            "error-insufficient-space",
+            path = path.display().to_string(),
+            needed = needed.div_ceil(MB),
+            available = available / MB
+        )
+    )]
+    InsufficientSpace {
+        path: PathBuf,
+        needed: u64,
+        available: u64,
+    },
+    /// The scaffold was cancelled and the partial project removed
+    #[error("{}", crate::t!("error-cancelled"))]
+    Cancelled,
+    #[error("{0}")]
+    Other(String),
+}
+
+impl HexstackError {
+    pub fn git_failed(args: &[&str], stderr: &[u8]) -> Self {
+        HexstackError::GitFailed {
+            command: args.join(" "),
+            stderr: String::from_utf8_lossy(stderr).trim().to_string(),
+        }
+    }
+
+    pub fn tool_missing(tool: &str, install: &str) -> Self {
+        HexstackError::ToolMissing {
+            tool: tool.to_string(),
+            install: install.to_string(),
+        }
+    }
+}
+
+/// Keeps the variant of errors raised as a `HexstackError` inside the library, however much
+/// context was added on the way up
+impl From<anyhow::Error> for HexstackError {
+    fn from(err: anyhow::Error) -> Self {
+        match err.downcast::<HexstackError>() {
+            Ok(err) => err,
+            Err(err) => HexstackError::Other(format!("{:#}", err)),
+        }
+    }
+}
+
+fn directory_exists_message(path: &std::path::Path, file: bool) -> String {
+    if file {
+        crate::t!("error-file-exists", path = path.display().to_string())
+    } else {
+        crate::t!("error-directory-exists", path = path.display().to_string())
+    }
+}
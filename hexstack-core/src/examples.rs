@@ -0,0 +1,676 @@
+//! Example apps for `hexstack new --example`: a working app laid over the scaffolded backend
+//! so newcomers see the stack in use.
+
+use crate::extras::{GeneratedFile, ProjectLayout, WriteMode};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Example {
+    Chat,
+    Todo,
+    Blog,
+}
+
+impl Example {
+    pub const ALL: [Example; 3] = [Example::Chat, Example::Todo, Example::Blog];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|example| example.key().eq_ignore_ascii_case(value.trim()))
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Example::Chat => "chat",
+            Example::Todo => "todo",
+            Example::Blog => "blog",
+        }
+    }
+
+    /// Components the example needs on top of the ones asked for
+    pub fn components(self) -> &'static [&'static str] {
+        match self {
+            Example::Chat => &["ripress", "wynd"],
+            Example::Todo | Example::Blog => &["ripress", "lume"],
+        }
+    }
+
+    /// What running the example gives you, shown in the next steps
+    pub fn description(self) -> &'static str {
+        match self {
+            Example::Chat => "a chat room whose messages are broadcast over a Wynd WebSocket",
+            Example::Todo => "a todo list backed by a Ripress JSON API",
+            Example::Blog => "a blog with posts written and read through a Ripress JSON API",
+        }
+    }
+
+    /// The example's backend sources, its page and a README section. main.rs is replaced, so
+    /// these are written before the extras wire their modules into it.
+    pub fn files(self, layout: &ProjectLayout) -> Vec<GeneratedFile> {
+        let (module, main, source, page, readme) = match self {
+            Example::Chat => ("chat", CHAT_MAIN, CHAT_MODULE, CHAT_PAGE, CHAT_README),
+            Example::Todo => ("todos", TODO_MAIN, TODO_MODULE, TODO_PAGE, TODO_README),
+            Example::Blog => ("blog", BLOG_MAIN, BLOG_MODULE, BLOG_PAGE, BLOG_README),
+        };
+        let src = layout.backend_dir.join("src");
+
+        vec![
+            GeneratedFile {
+                path: layout.backend_dir.join("Cargo.toml"),
+                contents: "serde = { version = \"1\", features = [\"derive\"] }\ntokio = { version = \"1\", features = [\"full\"] }\n".to_string(),
+                mode: WriteMode::Dependencies {
+                    table: "dependencies",
+                },
+            },
+            GeneratedFile {
+                path: src.join("main.rs"),
+                contents: main.to_string(),
+                mode: WriteMode::Overwrite,
+            },
+            GeneratedFile {
+                path: src.join(format!("{}.rs", module)),
+                contents: source.to_string(),
+                mode: WriteMode::Create,
+            },
+            GeneratedFile {
+                path: layout.backend_dir.join("public/index.html"),
+                contents: page.to_string(),
+                mode: WriteMode::Create,
+            },
+            GeneratedFile {
+                path: PathBuf::from("README.md"),
+                contents: readme.to_string(),
+                mode: WriteMode::AppendSection,
+            },
+        ]
+    }
+}
+
+const CHAT_MAIN: &str = r#"mod chat;
+
+use ripress::app::App;
+use wynd::wynd::{WithRipress, Wynd};
+
+#[tokio::main]
+async fn main() {
+    let mut app = App::new();
+    let mut wynd: Wynd<WithRipress> = Wynd::new();
+
+    chat::register(&mut app, &mut wynd);
+    app.use_wynd("/ws", wynd.handler());
+
+    app.listen(3000, || {
+        println!("💬 Chat running on http://localhost:3000");
+    })
+    .await;
+}
+"#;
+
+const CHAT_MODULE: &str = r#"//! Chat room: every text message a client sends over `/ws` is broadcast to all connected
+//! clients, and `/` serves the page.
+
+use ripress::app::App;
+use ripress::types::RouterFns;
+use tokio::sync::broadcast;
+use wynd::wynd::{WithRipress, Wynd};
+
+const INDEX: &str = include_str!("../public/index.html");
+
+/// Messages waiting for slow clients before they start missing some
+const BACKLOG: usize = 100;
+
+pub fn register(app: &mut App, wynd: &mut Wynd<WithRipress>) {
+    app.get("/", |_req, res| async move { res.ok().html(INDEX) });
+
+    let (sender, _) = broadcast::channel::<String>(BACKLOG);
+    wynd.on_connection(move |conn| {
+        let sender = sender.clone();
+        async move {
+            // Forward everything said in the room to this client
+            let room = sender.clone();
+            conn.on_open(move |handle| {
+                let mut messages = room.subscribe();
+                async move {
+                    tokio::spawn(async move {
+                        while let Ok(message) = messages.recv().await {
+                            if handle.send_text(&message).await.is_err() {
+                                break;
+                            }
+                        }
+                    });
+                }
+            })
+            .await;
+
+            conn.on_text(move |event, _handle| {
+                let sender = sender.clone();
+                async move {
+                    let message = event.data.trim().to_string();
+                    if !message.is_empty() {
+                        let _ = sender.send(message);
+                    }
+                }
+            });
+        }
+    });
+}
+"#;
+
+const CHAT_PAGE: &str = r#"<!doctype html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+    <title>Chat</title>
+    <style>
+      body { font-family: system-ui, sans-serif; max-width: 40rem; margin: 2rem auto; padding: 0 1rem; }
+      #messages { list-style: none; padding: 0; min-height: 10rem; }
+      #messages li { padding: 0.25rem 0; border-bottom: 1px solid #eee; }
+      form { display: flex; gap: 0.5rem; }
+      #text { flex: 1; }
+    </style>
+  </head>
+  <body>
+    <h1>Chat</h1>
+    <ul id="messages"></ul>
+    <form id="form">
+      <input id="name" placeholder="Name" required />
+      <input id="text" placeholder="Say something" autocomplete="off" required />
+      <button>Send</button>
+    </form>
+    <script>
+      const messages = document.getElementById("messages");
+      const socket = new WebSocket(`${location.protocol === "https:" ? "wss" : "ws"}://${location.host}/ws`);
+
+      socket.addEventListener("message", (event) => {
+        const item = document.createElement("li");
+        item.textContent = event.data;
+        messages.append(item);
+      });
+
+      document.getElementById("form").addEventListener("submit", (event) => {
+        event.preventDefault();
+        const name = document.getElementById("name").value.trim();
+        const text = document.getElementById("text");
+        socket.send(`${name}: ${text.value}`);
+        text.value = "";
+      });
+    </script>
+  </body>
+</html>
+"#;
+
+const CHAT_README: &str = r#"## Example: chat
+
+`cargo run` and open http://localhost:3000 in two browser windows. The page connects to the
+Wynd WebSocket at `/ws`; `src/chat.rs` broadcasts every message to all connected clients.
+"#;
+
+const TODO_MAIN: &str = r#"mod todos;
+
+use ripress::app::App;
+
+#[tokio::main]
+async fn main() {
+    let mut app = App::new();
+    todos::register(&mut app, todos::Todos::default());
+
+    app.listen(3000, || {
+        println!("✅ Todo app running on http://localhost:3000");
+    })
+    .await;
+}
+"#;
+
+const TODO_MODULE: &str = r#"//! Todo list: a JSON API under `/api/todos` and the page using it at `/`. Todos are kept in
+//! memory; back `Todos` with a lume table to keep them across restarts.
+
+use ripress::app::App;
+use ripress::context::HttpRequest;
+use ripress::types::RouterFns;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+const INDEX: &str = include_str!("../public/index.html");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Todo {
+    pub id: u64,
+    pub title: String,
+    pub done: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewTodo {
+    pub title: String,
+}
+
+/// Body of `PUT /api/todos/:id`; fields left out keep their value
+#[derive(Debug, Deserialize)]
+pub struct TodoUpdate {
+    pub title: Option<String>,
+    pub done: Option<bool>,
+}
+
+/// Cloning is cheap; clones share the same list
+#[derive(Clone, Default)]
+pub struct Todos {
+    inner: Arc<Mutex<(u64, Vec<Todo>)>>,
+}
+
+impl Todos {
+    pub fn list(&self) -> Vec<Todo> {
+        self.inner.lock().unwrap().1.clone()
+    }
+
+    pub fn add(&self, title: String) -> Todo {
+        let mut inner = self.inner.lock().unwrap();
+        inner.0 += 1;
+        let todo = Todo {
+            id: inner.0,
+            title,
+            done: false,
+        };
+        inner.1.push(todo.clone());
+        todo
+    }
+
+    pub fn update(&self, id: u64, update: TodoUpdate) -> Option<Todo> {
+        let mut inner = self.inner.lock().unwrap();
+        let todo = inner.1.iter_mut().find(|todo| todo.id == id)?;
+        if let Some(title) = update.title {
+            todo.title = title;
+        }
+        if let Some(done) = update.done {
+            todo.done = done;
+        }
+        Some(todo.clone())
+    }
+
+    pub fn remove(&self, id: u64) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let before = inner.1.len();
+        inner.1.retain(|todo| todo.id != id);
+        inner.1.len() != before
+    }
+}
+
+fn todo_id(req: &HttpRequest) -> Option<u64> {
+    req.params.get("id").and_then(|id| id.parse().ok())
+}
+
+pub fn register(app: &mut App, todos: Todos) {
+    app.get("/", |_req, res| async move { res.ok().html(INDEX) });
+
+    let list = todos.clone();
+    app.get("/api/todos", move |_req, res| {
+        let todos = list.clone();
+        async move { res.ok().json(todos.list()) }
+    });
+
+    let add = todos.clone();
+    app.post("/api/todos", move |req, res| {
+        let todos = add.clone();
+        async move {
+            match req.json::<NewTodo>() {
+                Ok(new) if !new.title.trim().is_empty() => {
+                    res.ok().json(todos.add(new.title.trim().to_string()))
+                }
+                _ => res.bad_request().text("Expected a non-empty title"),
+            }
+        }
+    });
+
+    let update = todos.clone();
+    app.put("/api/todos/:id", move |req, res| {
+        let todos = update.clone();
+        async move {
+            let (Some(id), Ok(changes)) = (todo_id(&req), req.json::<TodoUpdate>()) else {
+                return res.bad_request().text("Expected a todo id and changes");
+            };
+            match todos.update(id, changes) {
+                Some(todo) => res.ok().json(todo),
+                None => res.status(404).text("No such todo"),
+            }
+        }
+    });
+
+    app.delete("/api/todos/:id", move |req, res| {
+        let todos = todos.clone();
+        async move {
+            match todo_id(&req) {
+                Some(id) if todos.remove(id) => res.ok().text("Deleted"),
+                _ => res.status(404).text("No such todo"),
+            }
+        }
+    });
+}
+"#;
+
+const TODO_PAGE: &str = r#"<!doctype html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+    <title>Todos</title>
+    <style>
+      body { font-family: system-ui, sans-serif; max-width: 32rem; margin: 2rem auto; padding: 0 1rem; }
+      ul { list-style: none; padding: 0; }
+      li { display: flex; gap: 0.5rem; align-items: center; padding: 0.25rem 0; }
+      li.done span { text-decoration: line-through; color: #888; }
+      li span { flex: 1; }
+    </style>
+  </head>
+  <body>
+    <h1>Todos</h1>
+    <form id="form">
+      <input id="title" placeholder="What needs doing?" autocomplete="off" required />
+      <button>Add</button>
+    </form>
+    <ul id="todos"></ul>
+    <script>
+      const list = document.getElementById("todos");
+
+      async function api(path, options = {}) {
+        const response = await fetch(`/api/todos${path}`, {
+          ...options,
+          headers: { "Content-Type": "application/json" },
+        });
+        if (!response.ok) throw new Error(await response.text());
+        return response;
+      }
+
+      async function render() {
+        const todos = await (await api("")).json();
+        list.replaceChildren(
+          ...todos.map((todo) => {
+            const item = document.createElement("li");
+            item.className = todo.done ? "done" : "";
+
+            const done = document.createElement("input");
+            done.type = "checkbox";
+            done.checked = todo.done;
+            done.addEventListener("change", async () => {
+              await api(`/${todo.id}`, { method: "PUT", body: JSON.stringify({ done: done.checked }) });
+              render();
+            });
+
+            const title = document.createElement("span");
+            title.textContent = todo.title;
+
+            const remove = document.createElement("button");
+            remove.textContent = "Delete";
+            remove.addEventListener("click", async () => {
+              await api(`/${todo.id}`, { method: "DELETE" });
+              render();
+            });
+
+            item.append(done, title, remove);
+            return item;
+          }),
+        );
+      }
+
+      document.getElementById("form").addEventListener("submit", async (event) => {
+        event.preventDefault();
+        const title = document.getElementById("title");
+        await api("", { method: "POST", body: JSON.stringify({ title: title.value }) });
+        title.value = "";
+        render();
+      });
+
+      render();
+    </script>
+  </body>
+</html>
+"#;
+
+const TODO_README: &str = r#"## Example: todo
+
+`cargo run` and open http://localhost:3000. `src/todos.rs` serves the page and a JSON API:
+
+| Method   | Path             | Body                   |
+| -------- | ---------------- | ---------------------- |
+| `GET`    | `/api/todos`     |                        |
+| `POST`   | `/api/todos`     | `{ "title": "..." }`   |
+| `PUT`    | `/api/todos/:id` | `{ "done": true }`     |
+| `DELETE` | `/api/todos/:id` |                        |
+
+Todos are kept in memory; back `Todos` with a lume table to keep them across restarts.
+"#;
+
+const BLOG_MAIN: &str = r#"mod blog;
+
+use ripress::app::App;
+
+#[tokio::main]
+async fn main() {
+    let mut app = App::new();
+    blog::register(&mut app, blog::Posts::default());
+
+    app.listen(3000, || {
+        println!("📝 Blog running on http://localhost:3000");
+    })
+    .await;
+}
+"#;
+
+const BLOG_MODULE: &str = r#"//! Blog: a JSON API for posts under `/api/posts` and the page using it at `/`. Posts are kept
+//! in memory; back `Posts` with a lume table to keep them across restarts.
+
+use ripress::app::App;
+use ripress::types::RouterFns;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const INDEX: &str = include_str!("../public/index.html");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Post {
+    /// URL-friendly title, unique across posts
+    pub slug: String,
+    pub title: String,
+    pub body: String,
+    /// Seconds since the Unix epoch
+    pub published_at: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewPost {
+    pub title: String,
+    pub body: String,
+}
+
+/// Cloning is cheap; clones share the same posts
+#[derive(Clone, Default)]
+pub struct Posts {
+    inner: Arc<Mutex<Vec<Post>>>,
+}
+
+impl Posts {
+    /// Every post, newest first
+    pub fn list(&self) -> Vec<Post> {
+        let mut posts = self.inner.lock().unwrap().clone();
+        posts.reverse();
+        posts
+    }
+
+    pub fn get(&self, slug: &str) -> Option<Post> {
+        let posts = self.inner.lock().unwrap();
+        posts.iter().find(|post| post.slug == slug).cloned()
+    }
+
+    pub fn publish(&self, new: NewPost) -> Post {
+        let mut posts = self.inner.lock().unwrap();
+        let base = slugify(&new.title);
+        let mut slug = base.clone();
+        let mut n = 1;
+        while posts.iter().any(|post| post.slug == slug) {
+            n += 1;
+            slug = format!("{}-{}", base, n);
+        }
+
+        let post = Post {
+            slug,
+            title: new.title,
+            body: new.body,
+            published_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+        };
+        posts.push(post.clone());
+        post
+    }
+
+    pub fn delete(&self, slug: &str) -> bool {
+        let mut posts = self.inner.lock().unwrap();
+        let before = posts.len();
+        posts.retain(|post| post.slug != slug);
+        posts.len() != before
+    }
+}
+
+/// Lowercase letters and digits of `title`, words joined by `-`
+fn slugify(title: &str) -> String {
+    let slug = title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "post".to_string()
+    } else {
+        slug
+    }
+}
+
+pub fn register(app: &mut App, posts: Posts) {
+    app.get("/", |_req, res| async move { res.ok().html(INDEX) });
+
+    let list = posts.clone();
+    app.get("/api/posts", move |_req, res| {
+        let posts = list.clone();
+        async move { res.ok().json(posts.list()) }
+    });
+
+    let get = posts.clone();
+    app.get("/api/posts/:slug", move |req, res| {
+        let posts = get.clone();
+        async move {
+            match req.params.get("slug").and_then(|slug| posts.get(slug)) {
+                Some(post) => res.ok().json(post),
+                None => res.status(404).text("No such post"),
+            }
+        }
+    });
+
+    let publish = posts.clone();
+    app.post("/api/posts", move |req, res| {
+        let posts = publish.clone();
+        async move {
+            match req.json::<NewPost>() {
+                Ok(new) if !new.title.trim().is_empty() => res.ok().json(posts.publish(new)),
+                _ => res.bad_request().text("Expected a title and body"),
+            }
+        }
+    });
+
+    app.delete("/api/posts/:slug", move |req, res| {
+        let posts = posts.clone();
+        async move {
+            match req.params.get("slug") {
+                Some(slug) if posts.delete(slug) => res.ok().text("Deleted"),
+                _ => res.status(404).text("No such post"),
+            }
+        }
+    });
+}
+"#;
+
+const BLOG_PAGE: &str = r#"<!doctype html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+    <title>Blog</title>
+    <style>
+      body { font-family: system-ui, sans-serif; max-width: 40rem; margin: 2rem auto; padding: 0 1rem; }
+      article { border-bottom: 1px solid #eee; padding: 1rem 0; }
+      article p { white-space: pre-wrap; }
+      time { color: #888; font-size: 0.875rem; }
+      form { display: grid; gap: 0.5rem; margin-top: 2rem; }
+      textarea { min-height: 8rem; }
+    </style>
+  </head>
+  <body>
+    <h1>Blog</h1>
+    <div id="posts"></div>
+    <form id="form">
+      <h2>Write a post</h2>
+      <input id="title" placeholder="Title" required />
+      <textarea id="body" placeholder="What's on your mind?" required></textarea>
+      <button>Publish</button>
+    </form>
+    <script>
+      const container = document.getElementById("posts");
+
+      async function render() {
+        const posts = await (await fetch("/api/posts")).json();
+        container.replaceChildren(
+          ...posts.map((post) => {
+            const article = document.createElement("article");
+            const title = document.createElement("h2");
+            title.textContent = post.title;
+            const date = document.createElement("time");
+            date.textContent = new Date(post.published_at * 1000).toLocaleString();
+            const body = document.createElement("p");
+            body.textContent = post.body;
+            const remove = document.createElement("button");
+            remove.textContent = "Delete";
+            remove.addEventListener("click", async () => {
+              await fetch(`/api/posts/${post.slug}`, { method: "DELETE" });
+              render();
+            });
+            article.append(title, date, body, remove);
+            return article;
+          }),
+        );
+        if (!posts.length) container.textContent = "No posts yet.";
+      }
+
+      document.getElementById("form").addEventListener("submit", async (event) => {
+        event.preventDefault();
+        const title = document.getElementById("title");
+        const body = document.getElementById("body");
+        await fetch("/api/posts", {
+          method: "POST",
+          headers: { "Content-Type": "application/json" },
+          body: JSON.stringify({ title: title.value, body: body.value }),
+        });
+        title.value = "";
+        body.value = "";
+        render();
+      });
+
+      render();
+    </script>
+  </body>
+</html>
+"#;
+
+const BLOG_README: &str = r#"## Example: blog
+
+`cargo run` and open http://localhost:3000. `src/blog.rs` serves the page and a JSON API:
+
+| Method   | Path               | Body                               |
+| -------- | ------------------ | ---------------------------------- |
+| `GET`    | `/api/posts`       |                                    |
+| `GET`    | `/api/posts/:slug` |                                    |
+| `POST`   | `/api/posts`       | `{ "title": "...", "body": "..." }` |
+| `DELETE` | `/api/posts/:slug` |                                    |
+
+Posts are kept in memory; back `Posts` with a lume table to keep them across restarts.
+"#;
@@ -0,0 +1,5298 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::setup::{NODE_FRONTENDS, frontend_dev_steps, frontend_tool};
+use crate::workspace::Workspace;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory of a frontend the shared types are written to
+pub const TYPES_DIR: &str = "src/types";
+
+/// Optional pieces hexstack adds on top of the template, recorded in `.hexstack.lock`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Extras {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub database: Option<Database>,
+    /// Dockerfiles for the backend and frontends plus a docker-compose.yml
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub docker: bool,
+    /// GitHub Actions workflow building, linting and testing the project
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ci: bool,
+    /// `tracing` logging wired into the backend
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub tracing: bool,
+    /// `.env` files and a typed config module loading them at startup
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub config: bool,
+    #[serde(default, skip_serializing_if = "Styling::is_css")]
+    pub styling: Styling,
+    /// Backend integration tests and Vitest for the frontends
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub tests: bool,
+    /// Playwright end-to-end tests in `e2e/`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub e2e: bool,
+    /// SMTP email module with a welcome template and MailHog for development
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub email: bool,
+    /// OpenAPI spec for the ripress routes and a typed TypeScript client in the frontends
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub openapi: bool,
+    /// A `shared` crate whose types are exported to the frontends as TypeScript
+    #[serde(
+        default,
+        rename = "shared-types",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub shared_types: bool,
+    /// Multipart upload route, S3-compatible storage with MinIO, and a frontend upload component
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub uploads: bool,
+    /// Per-IP token bucket rate limiting for the ripress routes
+    #[serde(
+        default,
+        rename = "rate-limit",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub rate_limit: bool,
+    /// Cookie-based sessions with a memory, Redis or database store
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sessions: bool,
+    /// OTLP trace export and propagation on top of the tracing setup, with Jaeger in
+    /// docker-compose
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub otel: bool,
+    /// Token-protected admin API over users and an admin page in the frontends
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub admin: bool,
+    /// Dev container, recommended VS Code extensions and debug launch configurations
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub devcontainer: bool,
+    /// `flake.nix` dev shell with the project's toolchain
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub nix: bool,
+    /// Committed pre-commit hook running fmt, clippy and the frontend linters
+    #[serde(
+        default,
+        rename = "git-hooks",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub git_hooks: bool,
+    /// `rust-toolchain.toml` and `.nvmrc` with the versions declared by the template manifest
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub toolchain: bool,
+    /// Helm chart deploying the docker images to Kubernetes
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub k8s: bool,
+    /// A Cargo workspace at the project root over the backend, shared crate and Rust frontends
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub workspace: bool,
+}
+
+/// Opt-in extras offered in the extras step and accepted by `--extras`
+pub const OPTIONAL_EXTRAS: [(&str, &str); 20] = [
+    ("docker", "Docker & docker-compose"),
+    ("ci", "GitHub Actions CI"),
+    ("tracing", "Logging with tracing"),
+    ("config", "Typed config loaded from .env"),
+    ("tests", "Test harness (integration tests, Vitest)"),
+    ("e2e", "End-to-end tests (Playwright)"),
+    ("email", "Email sending (SMTP)"),
+    ("openapi", "OpenAPI spec & typed TypeScript client"),
+    (
+        "shared-types",
+        "Types shared by the backend and frontends (ts-rs)",
+    ),
+    ("uploads", "File uploads to S3-compatible storage (MinIO)"),
+    ("rate-limit", "Per-IP rate limiting middleware"),
+    ("sessions", "Cookie-based sessions"),
+    ("otel", "OpenTelemetry traces (OTLP, Jaeger)"),
+    ("admin", "Admin area for managing users"),
+    ("devcontainer", "Dev container & VS Code debug setup"),
+    ("nix", "Nix flake dev shell"),
+    ("git-hooks", "Pre-commit hooks (fmt, clippy, lint)"),
+    (
+        "toolchain",
+        "Pin the Rust & Node versions the template is tested with",
+    ),
+    ("k8s", "Kubernetes Helm chart"),
+    ("workspace", "One Cargo workspace for every Rust crate"),
+];
+
+/// Extras that add code to a component, and that component; they need it in the project
+pub const EXTRA_REQUIREMENTS: [(&str, &str); 5] = [
+    ("openapi", "ripress"),
+    ("uploads", "ripress"),
+    ("rate-limit", "ripress"),
+    ("sessions", "ripress"),
+    ("admin", "ripress"),
+];
+
+impl Extras {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Turns on the extra named `key` from [`OPTIONAL_EXTRAS`], returning false for unknown keys
+    pub fn enable(&mut self, key: &str) -> bool {
+        match key {
+            "docker" => self.docker = true,
+            "ci" => self.ci = true,
+            "tracing" => self.tracing = true,
+            "config" => self.config = true,
+            "tests" => self.tests = true,
+            "e2e" => self.e2e = true,
+            "email" => self.email = true,
+            "openapi" => self.openapi = true,
+            "shared-types" => self.shared_types = true,
+            "uploads" => self.uploads = true,
+            "rate-limit" => self.rate_limit = true,
+            "sessions" => self.sessions = true,
+            "otel" => self.otel = true,
+            "admin" => self.admin = true,
+            "devcontainer" => self.devcontainer = true,
+            "nix" => self.nix = true,
+            "git-hooks" => self.git_hooks = true,
+            "toolchain" => self.toolchain = true,
+            "k8s" => self.k8s = true,
+            "workspace" => self.workspace = true,
+            _ => return false,
+        }
+        true
+    }
+
+    /// The keys of the [`OPTIONAL_EXTRAS`] that are turned on, in the order they are offered
+    pub fn enabled(&self) -> Vec<&'static str> {
+        let flags = [
+            self.docker,
+            self.ci,
+            self.tracing,
+            self.config,
+            self.tests,
+            self.e2e,
+            self.email,
+            self.openapi,
+            self.shared_types,
+            self.uploads,
+            self.rate_limit,
+            self.sessions,
+            self.otel,
+            self.admin,
+            self.devcontainer,
+            self.nix,
+            self.git_hooks,
+            self.toolchain,
+            self.k8s,
+            self.workspace,
+        ];
+        OPTIONAL_EXTRAS
+            .iter()
+            .zip(flags)
+            .filter(|(_, enabled)| *enabled)
+            .map(|((key, _), _)| *key)
+            .collect()
+    }
+
+    /// Values for the extras' template placeholders; `{{database}}` is `none` without a database
+    pub fn placeholder_values(&self, crate_name: &str) -> [(&'static str, String); 2] {
+        [
+            (
+                "database",
+                self.database.map_or("none", Database::key).to_string(),
+            ),
+            (
+                "database_url",
+                self.database
+                    .map(|database| database.url(crate_name))
+                    .unwrap_or_default(),
+            ),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Database {
+    Postgres,
+    Sqlite,
+    Mysql,
+}
+
+impl Database {
+    pub const ALL: [Database; 3] = [Database::Postgres, Database::Sqlite, Database::Mysql];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "postgres" | "postgresql" | "pg" => Some(Database::Postgres),
+            "sqlite" => Some(Database::Sqlite),
+            "mysql" => Some(Database::Mysql),
+            _ => None,
+        }
+    }
+
+    /// Engine name used for lume features and the `{{database}}` placeholder
+    pub fn key(self) -> &'static str {
+        match self {
+            Database::Postgres => "postgres",
+            Database::Sqlite => "sqlite",
+            Database::Mysql => "mysql",
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Database::Postgres => "PostgreSQL",
+            Database::Sqlite => "SQLite",
+            Database::Mysql => "MySQL",
+        }
+    }
+
+    /// Connection string for local development, matching the docker-compose service
+    pub fn url(self, db_name: &str) -> String {
+        self.url_on_host(db_name, "localhost")
+    }
+
+    /// Connection string for a server reachable as `host`, e.g. `db` inside docker-compose
+    pub fn url_on_host(self, db_name: &str, host: &str) -> String {
+        match self {
+            Database::Postgres => {
+                format!("postgres://postgres:postgres@{}:5432/{}", host, db_name)
+            }
+            Database::Sqlite => format!("sqlite://{}.db", db_name),
+            Database::Mysql => format!("mysql://root:mysql@{}:3306/{}", host, db_name),
+        }
+    }
+
+    /// Command run inside the docker-compose service that succeeds once it accepts connections
+    pub fn ready_check(self, db_name: &str) -> Option<Vec<String>> {
+        let check: &[&str] = match self {
+            Database::Sqlite => return None,
+            Database::Postgres => &["pg_isready", "-U", "postgres", "-d", db_name],
+            // Over TCP, so the temporary server MySQL runs while initializing doesn't count
+            Database::Mysql => &["mysqladmin", "ping", "-h", "127.0.0.1", "-uroot", "-pmysql"],
+        };
+        Some(check.iter().map(|arg| arg.to_string()).collect())
+    }
+
+    /// docker-compose service running the engine; SQLite needs none
+    pub fn compose_service(self, db_name: &str) -> Option<ComposeService> {
+        let (image, port, environment, data) = match self {
+            Database::Sqlite => return None,
+            Database::Postgres => (
+                "postgres:17",
+                "5432:5432",
+                vec![
+                    ("POSTGRES_USER", "postgres".to_string()),
+                    ("POSTGRES_PASSWORD", "postgres".to_string()),
+                    ("POSTGRES_DB", db_name.to_string()),
+                ],
+                "/var/lib/postgresql/data",
+            ),
+            Database::Mysql => (
+                "mysql:8",
+                "3306:3306",
+                vec![
+                    ("MYSQL_ROOT_PASSWORD", "mysql".to_string()),
+                    ("MYSQL_DATABASE", db_name.to_string()),
+                ],
+                "/var/lib/mysql",
+            ),
+        };
+
+        Some(ComposeService {
+            name: "db".to_string(),
+            image: Some(image.to_string()),
+            build: None,
+            dockerfile: None,
+            environment: environment
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+            ports: vec![port.to_string()],
+            volumes: vec![format!("db-data:{}", data)],
+            depends_on: Vec::new(),
+            command: None,
+            entrypoint: Vec::new(),
+        })
+    }
+}
+
+/// How generated frontends are styled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Styling {
+    /// Whatever CSS the template ships
+    #[default]
+    Css,
+    Tailwind,
+}
+
+impl Styling {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "css" | "plain" => Some(Styling::Css),
+            "tailwind" | "tailwindcss" => Some(Styling::Tailwind),
+            _ => None,
+        }
+    }
+
+    fn is_css(&self) -> bool {
+        *self == Styling::Css
+    }
+}
+
+/// Stylesheet that gets the Tailwind directives and the files Tailwind scans for class names,
+/// for frontends Tailwind can be added to
+pub fn tailwind_target(frontend: &str) -> Option<(&'static str, &'static str)> {
+    match frontend {
+        "react" => Some((
+            "src/index.css",
+            "\"./index.html\", \"./src/**/*.{js,ts,jsx,tsx}\"",
+        )),
+        "svelte" => Some((
+            "src/app.css",
+            "\"./index.html\", \"./src/**/*.{svelte,js,ts}\"",
+        )),
+        _ => None,
+    }
+}
+
+/// A service in the generated `docker-compose.yml`
+#[derive(Debug, Clone)]
+pub struct ComposeService {
+    pub name: String,
+    pub image: Option<String>,
+    /// Build context, relative to the project root
+    pub build: Option<String>,
+    /// Dockerfile relative to the root, when it isn't the one in the build context
+    pub dockerfile: Option<String>,
+    pub environment: Vec<(String, String)>,
+    pub ports: Vec<String>,
+    /// `volume:path` mounts; named volumes are declared at the top level
+    pub volumes: Vec<String>,
+    pub depends_on: Vec<String>,
+    /// Overrides the image's command
+    pub command: Option<String>,
+    /// Overrides the image's entrypoint, as an exec-form argument list
+    pub entrypoint: Vec<String>,
+}
+
+/// Renders a docker-compose file for the given services
+pub fn render_compose(services: &[ComposeService]) -> String {
+    let mut out = String::from("services:\n");
+    let mut named_volumes = Vec::new();
+
+    for service in services {
+        out.push_str(&format!("  {}:\n", service.name));
+        if let Some(image) = &service.image {
+            out.push_str(&format!("    image: {}\n", image));
+        }
+        match (&service.build, &service.dockerfile) {
+            (Some(build), Some(dockerfile)) => out.push_str(&format!(
+                "    build:\n      context: {}\n      dockerfile: {}\n",
+                build, dockerfile
+            )),
+            (Some(build), None) => out.push_str(&format!("    build: {}\n", build)),
+            (None, _) => {}
+        }
+        if !service.entrypoint.is_empty() {
+            out.push_str(&format!(
+                "    entrypoint: {}\n",
+                serde_json::to_string(&service.entrypoint).unwrap_or_default()
+            ));
+        }
+        if let Some(command) = &service.command {
+            out.push_str(&format!("    command: {}\n", command));
+        }
+        if !service.environment.is_empty() {
+            out.push_str("    environment:\n");
+            for (key, value) in &service.environment {
+                out.push_str(&format!("      {}: \"{}\"\n", key, value));
+            }
+        }
+        for (key, values) in [
+            ("ports", &service.ports),
+            ("volumes", &service.volumes),
+            ("depends_on", &service.depends_on),
+        ] {
+            if values.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("    {}:\n", key));
+            for value in values {
+                out.push_str(&format!("      - \"{}\"\n", value));
+            }
+        }
+
+        for volume in &service.volumes {
+            if let Some((name, _)) = volume.split_once(':')
+                && !name.starts_with(['.', '/'])
+                && !named_volumes.contains(&name)
+            {
+                named_volumes.push(name);
+            }
+        }
+    }
+
+    if !named_volumes.is_empty() {
+        out.push_str("\nvolumes:\n");
+        for name in named_volumes {
+            out.push_str(&format!("  {}:\n", name));
+        }
+    }
+
+    out
+}
+
+/// How a generated file is combined with a file of the same name from the template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteMode {
+    /// Leave an existing file alone
+    Create,
+    /// Replace an existing file, e.g. the template's main.rs with an example app's
+    Overwrite,
+    /// Like `Create`, for scripts that should be executable
+    Executable,
+    /// Append `KEY=value` lines whose key the file doesn't define yet
+    MergeEnv,
+    /// Append lines the file doesn't contain yet, e.g. `.gitignore` entries
+    AppendLines,
+    /// Put the contents at the top of an existing file that doesn't start with them
+    Prepend,
+    /// Append the contents, a Markdown section, to an existing file that doesn't have its
+    /// heading (first line) yet
+    AppendSection,
+    /// Add the entries of the contents' objects (`devDependencies`, `scripts`, ...) to an
+    /// existing package.json, keeping the ones it already has
+    MergePackageJson,
+    /// Add `name = ...` lines to a table like `[dependencies]` of an existing Cargo.toml
+    Dependencies { table: &'static str },
+    /// Point `default-run` of an existing Cargo.toml at its package's own binary
+    DefaultRun,
+    /// Make the Cargo.toml a workspace over the member paths listed one per line, adding the
+    /// table to a root package or creating a virtual manifest
+    Workspace,
+    /// Declare the module named by the contents in an existing main.rs, calling `init` first
+    /// thing in `main` when given
+    WireModule { init: Option<&'static str> },
+}
+
+#[derive(Debug, Clone)]
+pub struct GeneratedFile {
+    /// Path relative to the project root
+    pub path: PathBuf,
+    pub contents: String,
+    pub mode: WriteMode,
+}
+
+/// Where the parts of a generated project live, relative to its root
+#[derive(Debug, Clone)]
+pub struct ProjectLayout<'a> {
+    pub name: &'a str,
+    /// Directory holding the backend crate, empty when it's the project root
+    pub backend_dir: PathBuf,
+    /// Frontend directories and the frontend each one contains
+    pub frontends: Vec<(PathBuf, &'a str)>,
+    /// Whether the backend renders the pages itself (HTMX)
+    pub server_rendered: bool,
+    pub components: &'a [String],
+}
+
+/// Port the backend listens on inside its container
+const BACKEND_PORT: u16 = 3000;
+
+/// Port the grpc component's server listens on
+const GRPC_PORT: u16 = 50051;
+
+/// Everything the selected extras add to the project
+pub fn generated_files(layout: &ProjectLayout, extras: &Extras) -> Vec<GeneratedFile> {
+    let db_name = layout.name.replace(['-', '.'], "_");
+    let mut files = Vec::new();
+    let mut services = Vec::new();
+
+    if let Some(database) = extras.database {
+        files.push(GeneratedFile {
+            path: layout.backend_dir.join(".env.example"),
+            contents: format!("DATABASE_URL={}\n", database.url(&db_name)),
+            mode: WriteMode::MergeEnv,
+        });
+        services.extend(database.compose_service(&db_name));
+        if layout.components.iter().any(|c| c == "lume") {
+            files.extend(seed_files(layout, database));
+        }
+    }
+
+    let redis = layout.components.iter().any(|c| c == "redis");
+    if redis {
+        files.extend(redis_files(layout));
+        services.push(ComposeService {
+            name: "redis".to_string(),
+            image: Some("redis:7-alpine".to_string()),
+            build: None,
+            dockerfile: None,
+            environment: Vec::new(),
+            ports: vec!["6379:6379".to_string()],
+            volumes: Vec::new(),
+            depends_on: Vec::new(),
+            command: None,
+            entrypoint: Vec::new(),
+        });
+    }
+
+    if layout.components.iter().any(|c| c == "graphql") {
+        files.extend(graphql_files(layout));
+    }
+
+    let uploads = extras.uploads && layout.components.iter().any(|c| c == "ripress");
+    if uploads {
+        files.extend(uploads_files(layout));
+        services.push(ComposeService {
+            name: "minio".to_string(),
+            image: Some("minio/minio".to_string()),
+            build: None,
+            dockerfile: None,
+            environment: vec![
+                ("MINIO_ROOT_USER".to_string(), "minioadmin".to_string()),
+                ("MINIO_ROOT_PASSWORD".to_string(), "minioadmin".to_string()),
+            ],
+            ports: vec!["9000:9000".to_string(), "9001:9001".to_string()],
+            volumes: vec!["minio-data:/data".to_string()],
+            depends_on: Vec::new(),
+            command: Some("server /data --console-address :9001".to_string()),
+            entrypoint: Vec::new(),
+        });
+        // MinIO starts without buckets, so a one-off container creates the uploads bucket
+        services.push(ComposeService {
+            name: "minio-setup".to_string(),
+            image: Some("minio/mc".to_string()),
+            build: None,
+            dockerfile: None,
+            environment: Vec::new(),
+            ports: Vec::new(),
+            volumes: Vec::new(),
+            depends_on: vec!["minio".to_string()],
+            command: None,
+            entrypoint: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "until mc alias set local http://minio:9000 minioadmin minioadmin; do sleep 1; done && mc mb --ignore-existing local/uploads".to_string(),
+            ],
+        });
+    }
+
+    if extras.openapi && layout.components.iter().any(|c| c == "ripress") {
+        files.extend(openapi_files(layout));
+    }
+
+    if extras.rate_limit && layout.components.iter().any(|c| c == "ripress") {
+        files.extend(rate_limit_files(layout));
+    }
+
+    if extras.sessions && layout.components.iter().any(|c| c == "ripress") {
+        files.extend(session_files(layout, extras.database));
+    }
+
+    if extras.admin && layout.components.iter().any(|c| c == "ripress") {
+        files.extend(admin_files(layout));
+    }
+
+    if extras.shared_types {
+        files.extend(shared_types_files(layout));
+    }
+
+    let grpc = layout.components.iter().any(|c| c == "grpc");
+    if grpc {
+        files.extend(grpc_files(layout));
+    }
+
+    if layout.components.iter().any(|c| c == "scheduler") {
+        files.extend(scheduler_files(layout));
+    }
+
+    let jobs = layout.components.iter().any(|c| c == "jobs");
+    if jobs {
+        files.extend(jobs_files(layout, extras.email));
+    }
+
+    if extras.email {
+        files.extend(email_files(layout));
+        services.push(ComposeService {
+            name: "mailhog".to_string(),
+            image: Some("mailhog/mailhog".to_string()),
+            build: None,
+            dockerfile: None,
+            environment: Vec::new(),
+            ports: vec!["1025:1025".to_string(), "8025:8025".to_string()],
+            volumes: Vec::new(),
+            depends_on: Vec::new(),
+            command: None,
+            entrypoint: Vec::new(),
+        });
+    }
+
+    if extras.otel {
+        services.push(ComposeService {
+            name: "jaeger".to_string(),
+            image: Some("jaegertracing/all-in-one".to_string()),
+            build: None,
+            dockerfile: None,
+            environment: vec![("COLLECTOR_OTLP_ENABLED".to_string(), "true".to_string())],
+            ports: vec!["16686:16686".to_string(), "4318:4318".to_string()],
+            volumes: Vec::new(),
+            depends_on: Vec::new(),
+            command: None,
+            entrypoint: Vec::new(),
+        });
+    }
+
+    if extras.docker {
+        files.push(GeneratedFile {
+            path: layout.backend_dir.join("Dockerfile"),
+            contents: backend_dockerfile(layout.server_rendered, jobs, grpc),
+            mode: WriteMode::Create,
+        });
+        files.push(GeneratedFile {
+            path: layout.backend_dir.join(".dockerignore"),
+            contents: "target\n.env\nfrontend\napps\n".to_string(),
+            mode: WriteMode::Create,
+        });
+
+        let mut environment: Vec<(String, String)> = extras
+            .database
+            .map(|database| {
+                (
+                    "DATABASE_URL".to_string(),
+                    database.url_on_host(&db_name, "db"),
+                )
+            })
+            .into_iter()
+            .collect();
+        if redis {
+            environment.push(("REDIS_URL".to_string(), "redis://redis:6379".to_string()));
+        }
+        if extras.email {
+            environment.push(("SMTP_HOST".to_string(), "mailhog".to_string()));
+        }
+        if uploads {
+            environment.push(("S3_ENDPOINT".to_string(), "http://minio:9000".to_string()));
+        }
+        if extras.otel {
+            environment.push((
+                "OTEL_EXPORTER_OTLP_ENDPOINT".to_string(),
+                "http://jaeger:4318".to_string(),
+            ));
+        }
+        let mut ports = vec![format!("{0}:{0}", BACKEND_PORT)];
+        if grpc {
+            ports.push(format!("{0}:{0}", GRPC_PORT));
+        }
+        services.push(ComposeService {
+            name: "backend".to_string(),
+            image: None,
+            build: Some(compose_path(&layout.backend_dir)),
+            dockerfile: None,
+            environment,
+            ports,
+            volumes: Vec::new(),
+            depends_on: services.iter().map(|s| s.name.clone()).collect(),
+            command: None,
+            entrypoint: Vec::new(),
+        });
+
+        // The worker needs a queue outside the server process to be worth its own container
+        if jobs && redis {
+            let mut environment = vec![("REDIS_URL".to_string(), "redis://redis:6379".to_string())];
+            let mut depends_on = vec!["redis".to_string()];
+            if extras.email {
+                environment.push(("SMTP_HOST".to_string(), "mailhog".to_string()));
+                depends_on.push("mailhog".to_string());
+            }
+            services.push(ComposeService {
+                name: "worker".to_string(),
+                image: None,
+                build: Some(compose_path(&layout.backend_dir)),
+                dockerfile: None,
+                environment,
+                ports: Vec::new(),
+                volumes: Vec::new(),
+                depends_on,
+                command: Some("worker".to_string()),
+                entrypoint: Vec::new(),
+            });
+        }
+
+        let mut host_port = 8080;
+        for (dir, frontend) in &layout.frontends {
+            let Some((dockerfile, port)) = frontend_dockerfile(frontend) else {
+                continue;
+            };
+            files.push(GeneratedFile {
+                path: dir.join("Dockerfile"),
+                contents: dockerfile,
+                mode: WriteMode::Create,
+            });
+            files.push(GeneratedFile {
+                path: dir.join(".dockerignore"),
+                contents: "node_modules\ndist\ntarget\n.env\n".to_string(),
+                mode: WriteMode::Create,
+            });
+            services.push(ComposeService {
+                name: dir.file_name().map_or("frontend".into(), |name| {
+                    name.to_string_lossy().into_owned()
+                }),
+                image: None,
+                build: Some(compose_path(dir)),
+                dockerfile: None,
+                environment: Vec::new(),
+                ports: vec![format!("{}:{}", host_port, port)],
+                volumes: Vec::new(),
+                depends_on: vec!["backend".to_string()],
+                command: None,
+                entrypoint: Vec::new(),
+            });
+            host_port += 1;
+        }
+    }
+
+    // OpenTelemetry builds on the tracing setup, exporting the same spans
+    if extras.tracing || extras.otel {
+        let src = layout.backend_dir.join("src");
+        let mut dependencies = String::from(
+            "tracing = \"0.1\"\ntracing-subscriber = { version = \"0.3\", features = [\"env-filter\", \"json\"] }\n",
+        );
+        if extras.otel {
+            dependencies.push_str("opentelemetry = \"0.31\"\nopentelemetry_sdk = \"0.31\"\nopentelemetry-otlp = \"0.31\"\ntracing-opentelemetry = \"0.32\"\n");
+        }
+        files.push(GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: dependencies,
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        });
+        files.push(GeneratedFile {
+            path: src.join("telemetry.rs"),
+            contents: telemetry_module(
+                layout.components.iter().any(|c| c == "ripress"),
+                layout.components.iter().any(|c| c == "wynd"),
+                extras.otel,
+            ),
+            mode: WriteMode::Create,
+        });
+        files.push(GeneratedFile {
+            path: src.join("main.rs"),
+            contents: "telemetry".to_string(),
+            mode: WriteMode::WireModule {
+                init: Some("telemetry::init();"),
+            },
+        });
+        files.push(GeneratedFile {
+            path: layout.backend_dir.join(".env.example"),
+            contents: if extras.otel {
+                format!(
+                    "RUST_LOG=info\nLOG_FORMAT=pretty\nOTEL_SERVICE_NAME={}\nOTEL_EXPORTER_OTLP_ENDPOINT=http://localhost:4318\n",
+                    layout.name
+                )
+            } else {
+                "RUST_LOG=info\nLOG_FORMAT=pretty\n".to_string()
+            },
+            mode: WriteMode::MergeEnv,
+        });
+    }
+
+    // Wired after tracing so `.env` is loaded before the subscriber reads RUST_LOG
+    if extras.config {
+        let src = layout.backend_dir.join("src");
+        let vars = config_vars(layout, extras);
+        files.push(GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: "dotenvy = \"0.15\"\n".to_string(),
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        });
+        files.push(GeneratedFile {
+            path: src.join("config.rs"),
+            contents: config_module(&vars),
+            mode: WriteMode::Create,
+        });
+        files.push(GeneratedFile {
+            path: src.join("main.rs"),
+            contents: "config".to_string(),
+            mode: WriteMode::WireModule {
+                init: Some("config::init();"),
+            },
+        });
+
+        let env_file = |secret: &str| -> String {
+            vars.iter()
+                .map(|var| match var.name {
+                    "JWT_SECRET" => format!("{}={}\n", var.name, secret),
+                    _ => format!("{}={}\n", var.name, var.example),
+                })
+                .collect()
+        };
+        files.push(GeneratedFile {
+            path: layout.backend_dir.join(".env.example"),
+            contents: env_file("change-me"),
+            mode: WriteMode::MergeEnv,
+        });
+        files.push(GeneratedFile {
+            path: layout.backend_dir.join(".env"),
+            contents: env_file(&random_secret()),
+            mode: WriteMode::MergeEnv,
+        });
+        files.push(GeneratedFile {
+            path: PathBuf::from(".gitignore"),
+            contents: ".env\n".to_string(),
+            mode: WriteMode::AppendLines,
+        });
+    }
+
+    if extras.styling == Styling::Tailwind {
+        for (dir, frontend) in &layout.frontends {
+            let Some((stylesheet, content)) = tailwind_target(frontend) else {
+                continue;
+            };
+            files.push(GeneratedFile {
+                path: dir.join("package.json"),
+                contents: r#"{"devDependencies": {"tailwindcss": "^3.4.17", "postcss": "^8.5.6", "autoprefixer": "^10.4.21"}}"#.to_string(),
+                mode: WriteMode::MergePackageJson,
+            });
+            files.push(GeneratedFile {
+                path: dir.join("tailwind.config.js"),
+                contents: format!(
+                    "/** @type {{import('tailwindcss').Config}} */\nexport default {{\n  content: [{}],\n  theme: {{\n    extend: {{}},\n  }},\n  plugins: [],\n}};\n",
+                    content
+                ),
+                mode: WriteMode::Create,
+            });
+            files.push(GeneratedFile {
+                path: dir.join("postcss.config.js"),
+                contents: "export default {\n  plugins: {\n    tailwindcss: {},\n    autoprefixer: {},\n  },\n};\n".to_string(),
+                mode: WriteMode::Create,
+            });
+            files.push(GeneratedFile {
+                path: dir.join(stylesheet),
+                contents: "@tailwind base;\n@tailwind components;\n@tailwind utilities;\n"
+                    .to_string(),
+                mode: WriteMode::Prepend,
+            });
+        }
+    }
+
+    if extras.tests {
+        files.extend(backend_test_files(layout, extras.database));
+        for (dir, frontend) in &layout.frontends {
+            if NODE_FRONTENDS.contains(frontend) {
+                files.extend(vitest_files(dir));
+            }
+        }
+    }
+
+    if extras.e2e {
+        files.extend(e2e_files(layout));
+    }
+
+    if extras.ci {
+        files.push(GeneratedFile {
+            path: PathBuf::from(".github/workflows/ci.yml"),
+            contents: ci_workflow(layout, extras.database),
+            mode: WriteMode::Create,
+        });
+    }
+
+    if extras.devcontainer {
+        files.extend(devcontainer_files(layout, extras, !services.is_empty()));
+    }
+
+    if extras.nix {
+        files.extend(nix_files(layout, extras.database));
+    }
+
+    if extras.git_hooks {
+        files.extend(git_hook_files(layout));
+    }
+
+    if extras.k8s {
+        files.extend(helm_files(layout, extras));
+    }
+
+    if extras.workspace {
+        files.push(GeneratedFile {
+            path: PathBuf::from("Cargo.toml"),
+            contents: workspace_members(layout, extras)
+                .iter()
+                .map(|member| format!("{}\n", member.display()))
+                .collect(),
+            mode: WriteMode::Workspace,
+        });
+    }
+
+    if !services.is_empty() {
+        files.push(GeneratedFile {
+            path: PathBuf::from("docker-compose.yml"),
+            contents: render_compose(&services),
+            mode: WriteMode::Create,
+        });
+    }
+
+    files
+}
+
+/// GitHub Actions workflow checking formatting, lints and tests of the backend and building
+/// every frontend
+fn ci_workflow(layout: &ProjectLayout, database: Option<Database>) -> String {
+    let working_directory = |dir: &Path| {
+        if dir.as_os_str().is_empty() {
+            String::new()
+        } else {
+            format!(
+                "    defaults:\n      run:\n        working-directory: {}\n",
+                dir.display()
+            )
+        }
+    };
+
+    let db_name = "ci";
+    let mut services = String::new();
+    let mut env = String::new();
+    if let Some(database) = database {
+        env = format!("    env:\n      DATABASE_URL: {}\n", database.url(db_name));
+        if let Some(service) = database.compose_service(db_name) {
+            let environment: String = service
+                .environment
+                .iter()
+                .map(|(key, value)| format!("          {}: {}\n", key, value))
+                .collect();
+            services = format!(
+                "    services:\n      db:\n        image: {}\n        env:\n{}        ports:\n          - {}\n",
+                service.image.unwrap_or_default(),
+                environment,
+                service.ports.join("\n          - ")
+            );
+        }
+    }
+
+    // The grpc component's build script compiles protos with protoc
+    let protoc = if layout.components.iter().any(|c| c == "grpc") {
+        "      - run: sudo apt-get update && sudo apt-get install -y protobuf-compiler\n"
+    } else {
+        ""
+    };
+
+    let mut workflow = format!(
+        r#"name: CI
+
+on:
+  push:
+    branches: [main]
+  pull_request:
+
+env:
+  CARGO_TERM_COLOR: always
+
+jobs:
+  backend:
+    runs-on: ubuntu-latest
+{}{}{}    steps:
+      - uses: actions/checkout@v4
+      - uses: dtolnay/rust-toolchain@stable
+        with:
+          components: rustfmt, clippy
+      - uses: Swatinem/rust-cache@v2
+{}      - run: cargo fmt --all -- --check
+      - run: cargo clippy --all-targets -- -D warnings
+      - run: cargo build
+      - run: cargo test
+"#,
+        working_directory(&layout.backend_dir),
+        services,
+        env,
+        protoc
+    );
+
+    for (dir, frontend) in &layout.frontends {
+        let Some(steps) = frontend_ci_steps(frontend) else {
+            continue;
+        };
+        let job = dir.file_name().map_or("frontend".into(), |name| {
+            name.to_string_lossy().into_owned()
+        });
+        workflow.push_str(&format!(
+            "\n  {}:\n    runs-on: ubuntu-latest\n{}    steps:\n      - uses: actions/checkout@v4\n{}",
+            job,
+            working_directory(dir),
+            steps
+        ));
+    }
+
+    workflow
+}
+
+/// Workflow steps building a frontend, after the checkout
+fn frontend_ci_steps(frontend: &str) -> Option<&'static str> {
+    let steps = match frontend {
+        frontend if NODE_FRONTENDS.contains(&frontend) => {
+            "      - uses: actions/setup-node@v4\n        with:\n          node-version: 22\n      - run: npm install\n      - run: npm run build\n"
+        }
+        "leptos" => {
+            "      - uses: dtolnay/rust-toolchain@stable\n        with:\n          targets: wasm32-unknown-unknown\n      - run: cargo install cargo-leptos --locked\n      - run: cargo leptos build --release\n"
+        }
+        "yew" => {
+            "      - uses: dtolnay/rust-toolchain@stable\n        with:\n          targets: wasm32-unknown-unknown\n      - run: cargo install trunk --locked\n      - run: trunk build --release\n"
+        }
+        "dioxus" => {
+            "      - uses: dtolnay/rust-toolchain@stable\n        with:\n          targets: wasm32-unknown-unknown\n      - run: cargo install dioxus-cli --locked\n      - run: dx build --release\n"
+        }
+        _ => return None,
+    };
+    Some(steps)
+}
+
+/// `src/telemetry.rs` for the backend: an env-filtered subscriber with an optional JSON format,
+/// plus a request-span middleware when the backend uses ripress. With `otel`, spans are also
+/// exported over OTLP and linked to the callers' traces.
+fn telemetry_module(ripress: bool, wynd: bool, otel: bool) -> String {
+    let mut module = if otel {
+        String::from(
+            r#"//! Logging and tracing setup. `RUST_LOG` controls the filter (e.g. `info,my_app=debug`) and
+//! `LOG_FORMAT=json` switches to structured JSON output. Spans are exported over OTLP to
+//! `OTEL_EXPORTER_OTLP_ENDPOINT` under the service name `OTEL_SERVICE_NAME`.
+#![allow(dead_code)]
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+static PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+pub fn init() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .build()
+        .expect("failed to create the OTLP exporter");
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .build();
+    let tracer = provider.tracer(env!("CARGO_PKG_NAME"));
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    opentelemetry::global::set_tracer_provider(provider.clone());
+    let _ = PROVIDER.set(provider);
+
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_opentelemetry::layer().with_tracer(tracer));
+    if std::env::var("LOG_FORMAT").is_ok_and(|format| format.eq_ignore_ascii_case("json")) {
+        registry
+            .with(tracing_subscriber::fmt::layer().json())
+            .init();
+    } else {
+        registry.with(tracing_subscriber::fmt::layer()).init();
+    }
+}
+
+/// Exports the spans still buffered; call it before `main` returns
+pub fn shutdown() {
+    if let Some(provider) = PROVIDER.get()
+        && let Err(err) = provider.shutdown()
+    {
+        eprintln!("failed to flush traces: {}", err);
+    }
+}
+
+/// `traceparent`/`tracestate` headers for the current span; add them to outgoing requests so
+/// the services they call continue this trace
+pub fn trace_headers() -> HashMap<String, String> {
+    let context = tracing::Span::current().context();
+    let mut headers = HashMap::new();
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut headers)
+    });
+    headers
+}
+"#,
+        )
+    } else {
+        String::from(
+            r#"//! Logging setup. `RUST_LOG` controls the filter (e.g. `info,my_app=debug`) and
+//! `LOG_FORMAT=json` switches to structured JSON output.
+
+use tracing_subscriber::EnvFilter;
+
+pub fn init() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+
+    if std::env::var("LOG_FORMAT").is_ok_and(|format| format.eq_ignore_ascii_case("json")) {
+        builder.json().init();
+    } else {
+        builder.init();
+    }
+}
+"#,
+        )
+    };
+
+    if ripress && otel {
+        module.push_str(
+            r#"
+/// Reads the trace context from a ripress request's headers
+struct RequestHeaders<'a>(&'a ripress::context::HttpRequest);
+
+impl opentelemetry::propagation::Extractor for RequestHeaders<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.headers.get(key)
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        Vec::new()
+    }
+}
+
+/// Span for a request, continuing the caller's trace when it sent a `traceparent` header.
+/// Instrument handlers with it: `async move { ... }.instrument(telemetry::request_span(&req))`
+pub fn request_span(req: &ripress::context::HttpRequest) -> tracing::Span {
+    let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.extract(&RequestHeaders(req))
+    });
+    let span = tracing::info_span!("request", method = ?req.method, path = %req.path);
+    let _ = span.set_parent(parent);
+    span
+}
+
+/// Logs every request inside its span. Register it with
+/// `app.use_pre_middleware("/", telemetry::trace_requests);`
+pub async fn trace_requests(
+    req: ripress::context::HttpRequest,
+    _res: ripress::context::HttpResponse,
+) -> (
+    ripress::context::HttpRequest,
+    Option<ripress::context::HttpResponse>,
+) {
+    request_span(&req).in_scope(|| tracing::info!("request received"));
+    (req, None)
+}
+"#,
+        );
+    } else if ripress {
+        module.push_str(
+            r#"
+/// Logs every request inside a span. Register it with
+/// `app.use_pre_middleware("/", telemetry::trace_requests);`
+pub async fn trace_requests(
+    req: ripress::context::HttpRequest,
+    _res: ripress::context::HttpResponse,
+) -> (
+    ripress::context::HttpRequest,
+    Option<ripress::context::HttpResponse>,
+) {
+    let span = tracing::info_span!("request", method = ?req.method, path = %req.path);
+    span.in_scope(|| tracing::info!("request received"));
+    (req, None)
+}
+"#,
+        );
+    }
+
+    if wynd && otel {
+        module.push_str(
+            r#"
+/// Span for a wynd event such as `"connection"` or `"text"`; instrument the handler's future
+/// with it so WebSocket work shows up in traces
+pub fn websocket_span(event: &str) -> tracing::Span {
+    tracing::info_span!("websocket", otel.name = %format!("ws {}", event))
+}
+"#,
+        );
+    }
+
+    module
+}
+
+/// An environment variable read by the generated config module
+struct EnvVar {
+    name: &'static str,
+    field: &'static str,
+    /// Rust type and default for optional variables; required ones are `String`s
+    parsed: Option<(&'static str, &'static str)>,
+    /// Value written to `.env.example`
+    example: String,
+}
+
+/// Variables the backend needs for the selected components and database
+fn config_vars(layout: &ProjectLayout, extras: &Extras) -> Vec<EnvVar> {
+    let has = |component: &str| layout.components.iter().any(|c| c == component);
+    let mut vars = Vec::new();
+
+    if has("ripress") {
+        vars.push(EnvVar {
+            name: "PORT",
+            field: "port",
+            parsed: Some(("u16", "3000")),
+            example: "3000".to_string(),
+        });
+    }
+    if has("wynd") {
+        vars.push(EnvVar {
+            name: "WS_PORT",
+            field: "ws_port",
+            parsed: Some(("u16", "3001")),
+            example: "3001".to_string(),
+        });
+    }
+    if let Some(database) = extras.database {
+        vars.push(EnvVar {
+            name: "DATABASE_URL",
+            field: "database_url",
+            parsed: None,
+            example: database.url(&layout.name.replace(['-', '.'], "_")),
+        });
+    }
+    if has("redis") {
+        vars.push(EnvVar {
+            name: "REDIS_URL",
+            field: "redis_url",
+            parsed: None,
+            example: "redis://localhost:6379".to_string(),
+        });
+    }
+    if has("ripress") {
+        vars.push(EnvVar {
+            name: "JWT_SECRET",
+            field: "jwt_secret",
+            parsed: None,
+            example: "change-me".to_string(),
+        });
+    }
+    if extras.rate_limit && has("ripress") {
+        vars.push(EnvVar {
+            name: "RATE_LIMIT_PER_MINUTE",
+            field: "rate_limit_per_minute",
+            parsed: Some(("u32", "60")),
+            example: "60".to_string(),
+        });
+        vars.push(EnvVar {
+            name: "RATE_LIMIT_BURST",
+            field: "rate_limit_burst",
+            parsed: Some(("u32", "20")),
+            example: "20".to_string(),
+        });
+    }
+    if extras.admin && has("ripress") {
+        vars.push(EnvVar {
+            name: "ADMIN_TOKEN",
+            field: "admin_token",
+            parsed: None,
+            example: "change-me-admin".to_string(),
+        });
+    }
+    if extras.sessions && has("ripress") {
+        vars.push(EnvVar {
+            name: "SESSION_TTL_SECS",
+            field: "session_ttl_secs",
+            parsed: Some(("u64", "86400")),
+            example: "86400".to_string(),
+        });
+    }
+    if extras.email {
+        vars.push(EnvVar {
+            name: "SMTP_HOST",
+            field: "smtp_host",
+            parsed: None,
+            example: "localhost".to_string(),
+        });
+        vars.push(EnvVar {
+            name: "SMTP_PORT",
+            field: "smtp_port",
+            parsed: Some(("u16", "1025")),
+            example: "1025".to_string(),
+        });
+    }
+
+    vars
+}
+
+/// `src/config.rs` for the backend, loading `.env` and validating every variable at startup
+fn config_module(vars: &[EnvVar]) -> String {
+    let fields: String = vars
+        .iter()
+        .map(|var| {
+            format!(
+                "    pub {}: {},\n",
+                var.field,
+                var.parsed.map_or("String", |(ty, _)| ty)
+            )
+        })
+        .collect();
+    let loaders: String = vars
+        .iter()
+        .map(|var| match var.parsed {
+            Some((_, default)) => format!(
+                "            {}: parse_or(\"{}\", {}, &mut errors),\n",
+                var.field, var.name, default
+            ),
+            None => format!(
+                "            {}: required(\"{}\", &mut errors),\n",
+                var.field, var.name
+            ),
+        })
+        .collect();
+
+    format!(
+        r#"//! Typed configuration, loaded from the environment and `.env` at startup.
+#![allow(dead_code)]
+
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone)]
+pub struct Config {{
+{}}}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Loads `.env` and validates the configuration, exiting with every problem found
+pub fn init() {{
+    let _ = dotenvy::dotenv();
+    match Config::from_env() {{
+        Ok(config) => {{
+            let _ = CONFIG.set(config);
+        }}
+        Err(errors) => {{
+            eprintln!("Invalid configuration:");
+            for error in errors {{
+                eprintln!("  {{}}", error);
+            }}
+            std::process::exit(1);
+        }}
+    }}
+}}
+
+/// The configuration loaded by [`init`]
+pub fn get() -> &'static Config {{
+    CONFIG.get().expect("config::init() must run first")
+}}
+
+impl Config {{
+    pub fn from_env() -> Result<Self, Vec<String>> {{
+        let mut errors = Vec::new();
+        let config = Config {{
+{}        }};
+
+        if errors.is_empty() {{
+            Ok(config)
+        }} else {{
+            Err(errors)
+        }}
+    }}
+}}
+
+fn required(name: &str, errors: &mut Vec<String>) -> String {{
+    match std::env::var(name) {{
+        Ok(value) if !value.trim().is_empty() => value,
+        _ => {{
+            errors.push(format!("{{}} is required", name));
+            String::new()
+        }}
+    }}
+}}
+
+fn parse_or<T: std::str::FromStr>(name: &str, default: T, errors: &mut Vec<String>) -> T {{
+    match std::env::var(name) {{
+        Ok(value) => value.parse().unwrap_or_else(|_| {{
+            errors.push(format!("{{}} must be a valid {{}}", name, std::any::type_name::<T>()));
+            default
+        }}),
+        Err(_) => default,
+    }}
+}}
+"#,
+        fields, loaders
+    )
+}
+
+/// A random hex secret for the generated `.env`
+fn random_secret() -> String {
+    let mut bytes = [0u8; 32];
+    let from_os = fs::File::open("/dev/urandom")
+        .and_then(|mut file| std::io::Read::read_exact(&mut file, &mut bytes))
+        .is_ok();
+    if !from_os {
+        let seed = format!("{:?}{}", std::time::SystemTime::now(), std::process::id());
+        bytes.copy_from_slice(&Sha256::digest(seed.as_bytes()));
+    }
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Backend integration test scaffolding: shared helpers in `tests/common` and an example test
+fn backend_test_files(layout: &ProjectLayout, database: Option<Database>) -> Vec<GeneratedFile> {
+    let ripress = layout.components.iter().any(|c| c == "ripress");
+    let lume = layout.components.iter().any(|c| c == "lume");
+    let tests = layout.backend_dir.join("tests");
+
+    let mut helpers = String::from(
+        r#"//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use std::future::Future;
+use std::time::Duration;
+
+/// Awaits `future`, failing the test after five seconds instead of hanging the suite
+pub async fn with_timeout<T>(future: impl Future<Output = T>) -> T {
+    tokio::time::timeout(Duration::from_secs(5), future)
+        .await
+        .expect("test timed out")
+}
+"#,
+    );
+    if ripress {
+        helpers.push_str(
+            r#"
+/// A fresh app for each test; register the routes under test on it
+pub fn test_app() -> ripress::app::App {
+    ripress::app::App::new()
+}
+"#,
+        );
+    }
+    if lume || database.is_some() {
+        helpers.push_str(
+            r#"
+/// In-memory SQLite database, so tests start empty and never touch real data
+pub const TEST_DATABASE_URL: &str = "sqlite::memory:";
+"#,
+        );
+    }
+
+    let mut example = String::from(
+        r#"mod common;
+
+#[tokio::test]
+async fn example() {
+    let sum = common::with_timeout(async { 1 + 1 }).await;
+    assert_eq!(sum, 2);
+}
+"#,
+    );
+    if ripress {
+        example.push_str(
+            r#"
+#[test]
+fn app_builds() {
+    let _app = common::test_app();
+}
+"#,
+        );
+    }
+
+    vec![
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: "tokio = { version = \"1\", features = [\"macros\", \"rt-multi-thread\", \"time\"] }\n".to_string(),
+            mode: WriteMode::Dependencies {
+                table: "dev-dependencies",
+            },
+        },
+        GeneratedFile {
+            path: tests.join("common/mod.rs"),
+            contents: helpers,
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: tests.join("example.rs"),
+            contents: example,
+            mode: WriteMode::Create,
+        },
+    ]
+}
+
+/// Vitest setup with an example test for a Node-based frontend in `dir`
+fn vitest_files(dir: &Path) -> Vec<GeneratedFile> {
+    vec![
+        GeneratedFile {
+            path: dir.join("package.json"),
+            contents:
+                r#"{"scripts": {"test": "vitest run"}, "devDependencies": {"vitest": "^3.2.4"}}"#
+                    .to_string(),
+            mode: WriteMode::MergePackageJson,
+        },
+        GeneratedFile {
+            path: dir.join("tests/example.test.ts"),
+            contents: r#"import { describe, expect, it } from "vitest";
+
+describe("example", () => {
+  it("adds numbers", () => {
+    expect(1 + 1).toBe(2);
+  });
+});
+"#
+            .to_string(),
+            mode: WriteMode::Create,
+        },
+    ]
+}
+
+/// Port a frontend's dev server listens on, and the environment that puts it there when its
+/// default would clash with the backend
+fn frontend_dev_server(frontend: &str) -> Option<(u16, &'static str)> {
+    match frontend {
+        "react" | "svelte" | "solid" | "sveltekit" | "vanilla" => Some((5173, "")),
+        "astro" => Some((4321, "")),
+        "nextjs" => Some((3001, "PORT: \"3001\"")),
+        "leptos" => Some((3002, "LEPTOS_SITE_ADDR: \"127.0.0.1:3002\"")),
+        "yew" | "dioxus" => Some((8080, "")),
+        _ => None,
+    }
+}
+
+/// VS Code extensions worth having for the selected stack
+fn vscode_extensions(layout: &ProjectLayout, extras: &Extras, compose: bool) -> Vec<&'static str> {
+    let mut extensions = vec![
+        "rust-lang.rust-analyzer",
+        "vadimcn.vscode-lldb",
+        "tamasfe.even-better-toml",
+    ];
+    let has = |component: &str| layout.components.iter().any(|c| c == component);
+    if has("grpc") {
+        extensions.push("zxh404.vscode-proto3");
+    }
+    if has("graphql") {
+        extensions.push("GraphQL.vscode-graphql");
+    }
+    if compose || extras.docker {
+        extensions.push("ms-azuretools.vscode-docker");
+    }
+
+    let frontends: Vec<&str> = layout.frontends.iter().map(|(_, f)| *f).collect();
+    if frontends.iter().any(|f| NODE_FRONTENDS.contains(f)) {
+        extensions.extend(["dbaeumer.vscode-eslint", "esbenp.prettier-vscode"]);
+    }
+    if frontends
+        .iter()
+        .any(|f| matches!(*f, "svelte" | "sveltekit"))
+    {
+        extensions.push("svelte.svelte-vscode");
+    }
+    if frontends.contains(&"astro") {
+        extensions.push("astro-build.astro-vscode");
+    }
+    if extras.styling == Styling::Tailwind {
+        extensions.push("bradlc.vscode-tailwindcss");
+    }
+    if extras.tests && frontends.iter().any(|f| NODE_FRONTENDS.contains(f)) {
+        extensions.push("vitest.explorer");
+    }
+    if extras.e2e {
+        extensions.push("ms-playwright.playwright");
+    }
+    extensions
+}
+
+/// `.devcontainer/devcontainer.json` plus the VS Code extension recommendations and debug
+/// launch configurations for the backend and each frontend
+fn devcontainer_files(
+    layout: &ProjectLayout,
+    extras: &Extras,
+    compose: bool,
+) -> Vec<GeneratedFile> {
+    let workspace = |dir: &Path| {
+        if dir.as_os_str().is_empty() {
+            "${workspaceFolder}".to_string()
+        } else {
+            format!("${{workspaceFolder}}/{}", dir.display())
+        }
+    };
+    let in_dir = |dir: &Path, line: &str| {
+        if dir.as_os_str().is_empty() {
+            line.to_string()
+        } else {
+            format!("(cd {} && {})", dir.display(), line)
+        }
+    };
+    let extensions = vscode_extensions(layout, extras, compose);
+
+    let mut setup = vec![in_dir(&layout.backend_dir, "cargo fetch")];
+    if layout.components.iter().any(|c| c == "grpc") {
+        setup.insert(
+            0,
+            "sudo apt-get update && sudo apt-get install -y protobuf-compiler".to_string(),
+        );
+    }
+    let mut ports = vec![BACKEND_PORT];
+    if layout.components.iter().any(|c| c == "grpc") {
+        ports.push(GRPC_PORT);
+    }
+    let mut node = extras.e2e;
+    for (dir, frontend) in &layout.frontends {
+        if NODE_FRONTENDS.contains(frontend) {
+            node = true;
+            setup.push(in_dir(dir, "npm install"));
+        } else if let Some((_, install)) = frontend_tool(frontend) {
+            let wasm = "rustup target add wasm32-unknown-unknown".to_string();
+            if !setup.contains(&wasm) {
+                setup.push(wasm);
+            }
+            setup.push(install.to_string());
+        }
+        if let Some((port, _)) = frontend_dev_server(frontend)
+            && !ports.contains(&port)
+        {
+            ports.push(port);
+        }
+    }
+
+    let mut features = serde_json::Map::new();
+    if node {
+        features.insert(
+            "ghcr.io/devcontainers/features/node:1".to_string(),
+            serde_json::json!({ "version": "lts" }),
+        );
+    }
+    let mut devcontainer = serde_json::json!({
+        "name": layout.name,
+        "image": "mcr.microsoft.com/devcontainers/rust:1",
+        "features": features,
+        "forwardPorts": ports,
+        "postCreateCommand": setup.join(" && "),
+        "customizations": { "vscode": { "extensions": extensions } },
+    });
+    if compose {
+        // Uses the host's docker for `docker compose up`, and its network so the localhost
+        // URLs in .env reach the services
+        devcontainer["features"]["ghcr.io/devcontainers/features/docker-outside-of-docker:1"] =
+            serde_json::json!({});
+        devcontainer["runArgs"] = serde_json::json!(["--network=host"]);
+    }
+
+    let mut cargo_args = vec!["build".to_string(), format!("--bin={}", layout.name)];
+    if !layout.backend_dir.as_os_str().is_empty() {
+        cargo_args.push(format!(
+            "--manifest-path={}",
+            layout.backend_dir.join("Cargo.toml").display()
+        ));
+    }
+    let mut configurations = vec![serde_json::json!({
+        "type": "lldb",
+        "request": "launch",
+        "name": "Debug backend",
+        "cargo": {
+            "args": cargo_args,
+            "filter": { "name": layout.name, "kind": "bin" },
+        },
+        "cwd": workspace(&layout.backend_dir),
+    })];
+    let mut browsers = Vec::new();
+    if layout.server_rendered {
+        browsers.push((
+            "Open pages in Chrome".to_string(),
+            BACKEND_PORT,
+            workspace(&layout.backend_dir),
+        ));
+    }
+    for (dir, frontend) in &layout.frontends {
+        if let Some((port, _)) = frontend_dev_server(frontend) {
+            let name = dir
+                .file_name()
+                .map_or("frontend".into(), |name| name.to_string_lossy());
+            browsers.push((format!("Debug {} in Chrome", name), port, workspace(dir)));
+        }
+    }
+    for (name, port, web_root) in &browsers {
+        configurations.push(serde_json::json!({
+            "type": "chrome",
+            "request": "launch",
+            "name": name,
+            "url": format!("http://localhost:{}", port),
+            "webRoot": web_root,
+        }));
+    }
+    let mut launch = serde_json::json!({
+        "version": "0.2.0",
+        "configurations": configurations,
+    });
+    if let Some((name, _, _)) = browsers.first() {
+        launch["compounds"] = serde_json::json!([{
+            "name": "Full stack",
+            "configurations": ["Debug backend", name],
+        }]);
+    }
+
+    let pretty = |value: serde_json::Value| {
+        let mut json = serde_json::to_string_pretty(&value).unwrap_or_default();
+        json.push('\n');
+        json
+    };
+    vec![
+        GeneratedFile {
+            path: PathBuf::from(".devcontainer/devcontainer.json"),
+            contents: pretty(devcontainer),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: PathBuf::from(".vscode/extensions.json"),
+            contents: pretty(serde_json::json!({ "recommendations": extensions })),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: PathBuf::from(".vscode/launch.json"),
+            contents: pretty(launch),
+            mode: WriteMode::Create,
+        },
+    ]
+}
+
+/// `flake.nix` whose dev shell pins the Rust toolchain (via rust-overlay), Node, the database
+/// client and the tools the selected stack needs, plus an `.envrc` for direnv users
+fn nix_files(layout: &ProjectLayout, database: Option<Database>) -> Vec<GeneratedFile> {
+    let mut wasm = false;
+    let mut packages = vec!["rust", "pkgs.pkg-config", "pkgs.openssl"];
+    for (_, frontend) in &layout.frontends {
+        let tool = match *frontend {
+            frontend if NODE_FRONTENDS.contains(&frontend) => "pkgs.nodejs_22",
+            "leptos" => "pkgs.cargo-leptos",
+            "yew" => "pkgs.trunk",
+            "dioxus" => "pkgs.dioxus-cli",
+            _ => continue,
+        };
+        if !packages.contains(&tool) {
+            packages.push(tool);
+        }
+        wasm |= !NODE_FRONTENDS.contains(frontend);
+    }
+    if layout.components.iter().any(|c| c == "grpc") {
+        packages.push("pkgs.protobuf");
+    }
+    if layout.components.iter().any(|c| c == "redis") {
+        packages.push("pkgs.redis");
+    }
+    packages.extend(database.map(|database| match database {
+        Database::Postgres => "pkgs.postgresql",
+        Database::Sqlite => "pkgs.sqlite",
+        Database::Mysql => "pkgs.mariadb.client",
+    }));
+
+    let flake = format!(
+        r#"{{
+  description = "{name} development shell";
+
+  inputs = {{
+    nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+    rust-overlay = {{
+      url = "github:oxalica/rust-overlay";
+      inputs.nixpkgs.follows = "nixpkgs";
+    }};
+    flake-utils.url = "github:numtide/flake-utils";
+  }};
+
+  outputs = {{ nixpkgs, rust-overlay, flake-utils, ... }}:
+    flake-utils.lib.eachDefaultSystem (system:
+      let
+        pkgs = import nixpkgs {{
+          inherit system;
+          overlays = [ (import rust-overlay) ];
+        }};
+        rust = pkgs.rust-bin.stable.latest.default.override {{
+          extensions = [ "rust-src" "rust-analyzer" ];
+          targets = [{targets}];
+        }};
+      in
+      {{
+        devShells.default = pkgs.mkShell {{
+          packages = [
+{packages}
+          ];
+        }};
+      }});
+}}
+"#,
+        name = layout.name,
+        targets = if wasm {
+            " \"wasm32-unknown-unknown\" "
+        } else {
+            ""
+        },
+        packages = packages
+            .iter()
+            .map(|package| format!("            {}\n", package))
+            .collect::<String>()
+            .trim_end(),
+    );
+
+    vec![
+        GeneratedFile {
+            path: PathBuf::from("flake.nix"),
+            contents: flake,
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: PathBuf::from(".envrc"),
+            contents: "use flake\n".to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: PathBuf::from(".gitignore"),
+            contents: ".direnv\n".to_string(),
+            mode: WriteMode::AppendLines,
+        },
+    ]
+}
+
+/// Crates of the project besides a root package: the backend, its `shared` crate and the Rust
+/// frontends. The jobs worker is a binary of the backend crate, so it's covered by it.
+fn workspace_members(layout: &ProjectLayout, extras: &Extras) -> Vec<PathBuf> {
+    let mut members = Vec::new();
+    if !layout.backend_dir.as_os_str().is_empty() {
+        members.push(layout.backend_dir.clone());
+    }
+    if extras.shared_types {
+        members.push(layout.backend_dir.join("shared"));
+    }
+    for (dir, frontend) in &layout.frontends {
+        if frontend_tool(frontend).is_some() {
+            members.push(dir.clone());
+        }
+    }
+    members
+}
+
+/// Turns a Cargo.toml into a workspace over `members`, or returns `None` when it already is one
+pub fn workspace_manifest(existing: Option<&str>, members: &str) -> Option<String> {
+    let list = members
+        .lines()
+        .map(|member| format!("\"{}\"", member))
+        .collect::<Vec<_>>()
+        .join(", ");
+    match existing {
+        Some(existing) if existing.lines().any(|line| line.trim() == "[workspace]") => None,
+        // A root package is a member itself, and its edition picks the resolver
+        Some(existing) => Some(format!(
+            "{}\n\n[workspace]\nmembers = [{}]\n",
+            existing.trim_end(),
+            list
+        )),
+        None => Some(format!(
+            "[workspace]\nmembers = [{}]\nresolver = \"3\"\n",
+            list
+        )),
+    }
+}
+
+/// Directory of the committed git hooks, which git is pointed at with `core.hooksPath`
+pub const HOOKS_DIR: &str = ".githooks";
+
+/// A pre-commit hook checking formatting and lints of the backend and every frontend, and the
+/// script that installs it in a fresh clone
+fn git_hook_files(layout: &ProjectLayout) -> Vec<GeneratedFile> {
+    let in_dir = |dir: &Path, line: &str| {
+        if dir.as_os_str().is_empty() {
+            format!("{}\n", line)
+        } else {
+            format!("(cd {} && {})\n", dir.display(), line)
+        }
+    };
+    let label = |dir: &Path| {
+        if dir.as_os_str().is_empty() {
+            "backend".to_string()
+        } else {
+            dir.display().to_string()
+        }
+    };
+
+    let mut checks = String::new();
+    let mut crates = vec![layout.backend_dir.as_path()];
+    for (dir, frontend) in &layout.frontends {
+        if NODE_FRONTENDS.contains(frontend) {
+            checks.push_str(&format!(
+                "\necho \"🔍 Linting {}\"\nif [ -d {}/node_modules ]; then\n    {}else\n    echo \"   skipped, run npm install there first\"\nfi\n",
+                dir.display(),
+                dir.display(),
+                in_dir(dir, "npm run lint --if-present")
+            ));
+        } else if frontend_tool(frontend).is_some() {
+            crates.push(dir);
+        }
+    }
+    let rust_checks: String = crates
+        .iter()
+        .map(|dir| {
+            format!(
+                "\necho \"🔍 Checking {}\"\n{}{}",
+                label(dir),
+                in_dir(dir, "cargo fmt --all -- --check"),
+                in_dir(dir, "cargo clippy --all-targets -- -D warnings")
+            )
+        })
+        .collect();
+
+    vec![
+        GeneratedFile {
+            path: PathBuf::from(HOOKS_DIR).join("pre-commit"),
+            contents: format!(
+                "#!/bin/sh\n# Runs before every commit; skip it once with `git commit --no-verify`.\nset -eu\n\ncd \"$(git rev-parse --show-toplevel)\"\n{}{}",
+                rust_checks, checks
+            ),
+            mode: WriteMode::Executable,
+        },
+        GeneratedFile {
+            path: PathBuf::from("scripts/install-hooks.sh"),
+            contents: format!(
+                r#"#!/bin/sh
+# Points git at the hooks committed in {dir}/. Run it once after cloning.
+set -eu
+
+cd "$(dirname "$0")/.."
+git config core.hooksPath {dir}
+echo "✅ Git hooks installed from {dir}/"
+"#,
+                dir = HOOKS_DIR
+            ),
+            mode: WriteMode::Executable,
+        },
+    ]
+}
+
+/// Playwright project in `e2e/` whose config boots the backend and every frontend dev server,
+/// with a smoke test against the first frontend (or the backend when pages are server-rendered)
+fn e2e_files(layout: &ProjectLayout) -> Vec<GeneratedFile> {
+    let up = |dir: &Path| {
+        if dir.as_os_str().is_empty() {
+            "..".to_string()
+        } else {
+            format!("../{}", dir.display())
+        }
+    };
+
+    let mut servers = vec![format!(
+        "    {{\n      command: \"cargo run\",\n      cwd: \"{}\",\n      url: \"http://localhost:{}\",\n      reuseExistingServer: !process.env.CI,\n      timeout: 300_000,\n    }},\n",
+        up(&layout.backend_dir),
+        BACKEND_PORT
+    )];
+    let mut base_url = format!("http://localhost:{}", BACKEND_PORT);
+    for (index, (dir, frontend)) in layout.frontends.iter().enumerate() {
+        let (Some(steps), Some((port, env))) =
+            (frontend_dev_steps(frontend), frontend_dev_server(frontend))
+        else {
+            continue;
+        };
+        // Apps after the first would collide on the default port, so they only get booted
+        // when they listen elsewhere
+        if index > 0 && servers.iter().any(|s| s.contains(&format!(":{}\"", port))) {
+            continue;
+        }
+        if index == 0 {
+            base_url = format!("http://localhost:{}", port);
+        }
+        let env = if env.is_empty() {
+            String::new()
+        } else {
+            format!("      env: {{ {} }},\n", env)
+        };
+        servers.push(format!(
+            "    {{\n      command: \"{}\",\n      cwd: \"{}\",\n      url: \"http://localhost:{}\",\n{}      reuseExistingServer: !process.env.CI,\n      timeout: 300_000,\n    }},\n",
+            steps.join(" && "),
+            up(dir),
+            port,
+            env
+        ));
+    }
+
+    let config = format!(
+        r#"import {{ defineConfig, devices }} from "@playwright/test";
+
+export default defineConfig({{
+  testDir: "./tests",
+  use: {{
+    baseURL: "{}",
+  }},
+  projects: [{{ name: "chromium", use: {{ ...devices["Desktop Chrome"] }} }}],
+  // Boots the backend and the frontend dev servers before the suite runs
+  webServer: [
+{}  ],
+}});
+"#,
+        base_url,
+        servers.concat()
+    );
+
+    vec![
+        GeneratedFile {
+            path: PathBuf::from("e2e/package.json"),
+            contents: format!(
+                "{{\n  \"name\": \"{}-e2e\",\n  \"private\": true,\n  \"scripts\": {{\n    \"test\": \"playwright test\"\n  }},\n  \"devDependencies\": {{\n    \"@playwright/test\": \"^1.55.0\"\n  }}\n}}\n",
+                layout.name
+            ),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: PathBuf::from("e2e/playwright.config.ts"),
+            contents: config,
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: PathBuf::from("e2e/tests/home.spec.ts"),
+            contents: r#"import { expect, test } from "@playwright/test";
+
+test("home page loads", async ({ page }) => {
+  const response = await page.goto("/");
+  expect(response?.ok()).toBeTruthy();
+});
+"#
+            .to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: PathBuf::from("scripts/e2e.sh"),
+            contents: r#"#!/bin/sh
+# Installs Playwright and runs the end-to-end suite. The backend and frontend dev servers are
+# started by the webServer entries in e2e/playwright.config.ts.
+set -eu
+
+cd "$(dirname "$0")/../e2e"
+npm install
+npx playwright install --with-deps chromium
+npx playwright test "$@"
+"#
+            .to_string(),
+            mode: WriteMode::Executable,
+        },
+        GeneratedFile {
+            path: PathBuf::from(".gitignore"),
+            contents: "e2e/node_modules\ne2e/test-results\ne2e/playwright-report\n".to_string(),
+            mode: WriteMode::AppendLines,
+        },
+    ]
+}
+
+/// `src/cache.rs` for the backend: a shared Redis connection with typed caching helpers
+const CACHE_MODULE: &str = r#"//! Redis client and caching helpers. Create one `Cache` at startup and clone it where needed;
+//! clones share the same connection.
+#![allow(dead_code)]
+
+use redis::aio::ConnectionManager;
+use redis::{AsyncCommands, FromRedisValue, RedisResult, ToRedisArgs};
+use std::future::Future;
+
+#[derive(Clone)]
+pub struct Cache {
+    connection: ConnectionManager,
+}
+
+impl Cache {
+    /// Connects to `REDIS_URL`, defaulting to a local Redis
+    pub async fn connect() -> RedisResult<Self> {
+        let url =
+            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let client = redis::Client::open(url)?;
+        Ok(Self {
+            connection: ConnectionManager::new(client).await?,
+        })
+    }
+
+    pub async fn get<V: FromRedisValue>(&self, key: &str) -> RedisResult<Option<V>> {
+        self.connection.clone().get(key).await
+    }
+
+    /// Stores `value` under `key` for `ttl_secs` seconds
+    pub async fn set<V: ToRedisArgs + Send + Sync>(
+        &self,
+        key: &str,
+        value: V,
+        ttl_secs: u64,
+    ) -> RedisResult<()> {
+        self.connection.clone().set_ex(key, value, ttl_secs).await
+    }
+
+    pub async fn delete(&self, key: &str) -> RedisResult<()> {
+        self.connection.clone().del(key).await
+    }
+
+    /// Returns the value cached under `key`, computing and caching it on a miss
+    pub async fn get_or_set<V, F, Fut>(&self, key: &str, ttl_secs: u64, compute: F) -> RedisResult<V>
+    where
+        V: FromRedisValue + ToRedisArgs + Send + Sync,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        if let Some(value) = self.get(key).await? {
+            return Ok(value);
+        }
+        let value = compute().await;
+        self.set(key, &value, ttl_secs).await?;
+        Ok(value)
+    }
+}
+"#;
+
+/// Redis client, caching module and connection URL for the `redis` component
+fn redis_files(layout: &ProjectLayout) -> Vec<GeneratedFile> {
+    let src = layout.backend_dir.join("src");
+    vec![
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: "redis = { version = \"0.32\", features = [\"tokio-comp\", \"connection-manager\"] }\n".to_string(),
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        },
+        GeneratedFile {
+            path: src.join("cache.rs"),
+            contents: CACHE_MODULE.to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: src.join("main.rs"),
+            contents: "cache".to_string(),
+            mode: WriteMode::WireModule { init: None },
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join(".env.example"),
+            contents: "REDIS_URL=redis://localhost:6379\n".to_string(),
+            mode: WriteMode::MergeEnv,
+        },
+    ]
+}
+
+/// `src/jobs.rs` for the backend: the job definitions, a queue kept in process or in Redis,
+/// and a worker loop that finishes the current job before shutting down on Ctrl-C
+fn jobs_module(redis: bool, email: bool) -> String {
+    let (queue_imports, queue) = if redis {
+        (
+            "use redis::AsyncCommands;\nuse redis::aio::ConnectionManager;\nuse serde::{Deserialize, Serialize};\n",
+            r#"/// Redis list the jobs wait in
+const QUEUE_KEY: &str = "jobs";
+
+/// Jobs wait in Redis, so the server enqueues them and the worker binary runs them
+#[derive(Clone)]
+pub struct Queue {
+    connection: ConnectionManager,
+}
+
+impl Queue {
+    /// Connects to `REDIS_URL`, defaulting to a local Redis
+    pub async fn connect() -> Result<Self, Error> {
+        let url =
+            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let client = redis::Client::open(url)?;
+        Ok(Self {
+            connection: ConnectionManager::new(client).await?,
+        })
+    }
+
+    pub async fn push(&self, job: &Job) -> Result<(), Error> {
+        let payload = serde_json::to_string(job)?;
+        let _: () = self.connection.clone().lpush(QUEUE_KEY, payload).await?;
+        Ok(())
+    }
+
+    /// Waits up to a second for the next job
+    pub async fn pop(&self) -> Result<Option<Job>, Error> {
+        let popped: Option<(String, String)> =
+            self.connection.clone().brpop(QUEUE_KEY, 1.0).await?;
+        match popped {
+            Some((_, payload)) => Ok(Some(serde_json::from_str(&payload)?)),
+            None => Ok(None),
+        }
+    }
+}
+"#,
+        )
+    } else {
+        (
+            "use std::sync::Arc;\nuse tokio::sync::{Mutex, mpsc};\n",
+            r#"/// Jobs wait in memory, so they're enqueued and run in the same process. Add the redis
+/// component to run them in a separate worker.
+#[derive(Clone)]
+pub struct Queue {
+    sender: mpsc::UnboundedSender<Job>,
+    receiver: Arc<Mutex<mpsc::UnboundedReceiver<Job>>>,
+}
+
+impl Queue {
+    pub async fn connect() -> Result<Self, Error> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Ok(Self {
+            sender,
+            receiver: Arc::new(Mutex::new(receiver)),
+        })
+    }
+
+    pub async fn push(&self, job: &Job) -> Result<(), Error> {
+        self.sender.send(job.clone())?;
+        Ok(())
+    }
+
+    /// Waits up to a second for the next job
+    pub async fn pop(&self) -> Result<Option<Job>, Error> {
+        let mut receiver = self.receiver.lock().await;
+        Ok(
+            tokio::time::timeout(Duration::from_secs(1), receiver.recv())
+                .await
+                .ok()
+                .flatten(),
+        )
+    }
+}
+"#,
+        )
+    };
+    let derives = if redis {
+        "#[derive(Debug, Clone, Serialize, Deserialize)]\n#[serde(tag = \"type\", rename_all = \"snake_case\")]"
+    } else {
+        "#[derive(Debug, Clone)]"
+    };
+    // With the email extra the example job sends a real welcome email
+    let (welcome, send_welcome) = if email {
+        (
+            "SendWelcome { email: String, name: String }",
+            r#"Job::SendWelcome { email, name } => {
+                crate::email::Mailer::from_env()?
+                    .send_welcome(&email, &name)
+                    .await
+            }"#,
+        )
+    } else {
+        (
+            "SendWelcome { user_id: u64 }",
+            r#"Job::SendWelcome { user_id } => {
+                println!("Sending welcome message to user {}", user_id);
+                Ok(())
+            }"#,
+        )
+    };
+
+    format!(
+        r#"//! Background jobs: what they do, the queue they wait in and the worker loop running them.
+#![allow(dead_code)]
+
+{}use std::time::Duration;
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// Work done in the background. Add a variant per kind of job and handle it in `run`.
+{}
+pub enum Job {{
+    {},
+}}
+
+impl Job {{
+    pub async fn run(self) -> Result<(), Error> {{
+        match self {{
+            {}
+        }}
+    }}
+}}
+
+{}
+/// Runs jobs until Ctrl-C. A job that's running when the signal arrives is finished first.
+pub async fn work(queue: Queue) {{
+    let shutdown = tokio::signal::ctrl_c();
+    tokio::pin!(shutdown);
+
+    loop {{
+        tokio::select! {{
+            _ = &mut shutdown => {{
+                println!("Worker shutting down");
+                break;
+            }}
+            job = queue.pop() => match job {{
+                Ok(Some(job)) => {{
+                    if let Err(err) = job.run().await {{
+                        eprintln!("Job failed: {{}}", err);
+                    }}
+                }}
+                Ok(None) => {{}}
+                Err(err) => {{
+                    eprintln!("Failed to fetch a job: {{}}", err);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }}
+            }},
+        }}
+    }}
+}}
+"#,
+        queue_imports, derives, welcome, send_welcome, queue
+    )
+}
+
+/// `src/bin/worker.rs`: a second binary running the jobs
+fn worker_binary(redis: bool, email: bool) -> String {
+    let demo = match (redis, email) {
+        (true, _) => "",
+        (false, false) => {
+            "    // The in-process queue only holds jobs pushed from this process\n    queue\n        .push(&jobs::Job::SendWelcome { user_id: 1 })\n        .await\n        .expect(\"failed to enqueue the example job\");\n"
+        }
+        (false, true) => {
+            "    // The in-process queue only holds jobs pushed from this process\n    queue\n        .push(&jobs::Job::SendWelcome {\n            email: \"new-user@example.com\".to_string(),\n            name: \"New user\".to_string(),\n        })\n        .await\n        .expect(\"failed to enqueue the example job\");\n"
+        }
+    };
+    let email_module = if email {
+        "#[path = \"../email.rs\"]\nmod email;\n"
+    } else {
+        ""
+    };
+
+    format!(
+        r#"{}#[path = "../jobs.rs"]
+mod jobs;
+
+#[tokio::main]
+async fn main() {{
+    let queue = jobs::Queue::connect()
+        .await
+        .expect("failed to connect to the job queue");
+{}
+    println!("👷 Worker started, press Ctrl-C to stop");
+    jobs::work(queue).await;
+}}
+"#,
+        email_module, demo
+    )
+}
+
+/// Queue, example job and worker binary for the `jobs` component
+fn jobs_files(layout: &ProjectLayout, email: bool) -> Vec<GeneratedFile> {
+    let redis = layout.components.iter().any(|c| c == "redis");
+    let src = layout.backend_dir.join("src");
+    let mut dependencies = String::from("tokio = { version = \"1\", features = [\"full\"] }\n");
+    if redis {
+        dependencies
+            .push_str("serde = { version = \"1\", features = [\"derive\"] }\nserde_json = \"1\"\n");
+    }
+
+    vec![
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: dependencies,
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        },
+        // With a second binary, `cargo run` needs to know which one is the server
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: String::new(),
+            mode: WriteMode::DefaultRun,
+        },
+        GeneratedFile {
+            path: src.join("jobs.rs"),
+            contents: jobs_module(redis, email),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: src.join("main.rs"),
+            contents: "jobs".to_string(),
+            mode: WriteMode::WireModule { init: None },
+        },
+        GeneratedFile {
+            path: src.join("bin/worker.rs"),
+            contents: worker_binary(redis, email),
+            mode: WriteMode::Create,
+        },
+    ]
+}
+
+/// `src/email.rs` for the backend: an SMTP mailer built on lettre and the welcome email
+fn email_module(app_name: &str) -> String {
+    format!(
+        r#"//! Email over SMTP. In development messages go to MailHog; open http://localhost:8025 to read them.
+#![allow(dead_code)]
+
+use lettre::message::{{Mailbox, header::ContentType}};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor}};
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// Welcome email body; `{{{{name}}}}` is replaced with the recipient's name
+const WELCOME_TEMPLATE: &str = include_str!("../templates/email/welcome.html");
+
+#[derive(Clone)]
+pub struct Mailer {{
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}}
+
+impl Mailer {{
+    /// Reads `SMTP_HOST`, `SMTP_PORT` and `MAIL_FROM`. With `SMTP_USERNAME` set the connection
+    /// uses STARTTLS and logs in with `SMTP_PASSWORD`; without it, plain SMTP as MailHog expects.
+    pub fn from_env() -> Result<Self, Error> {{
+        let host = env_or("SMTP_HOST", "localhost");
+        let port: u16 = env_or("SMTP_PORT", "1025").parse()?;
+        let builder = match std::env::var("SMTP_USERNAME") {{
+            Ok(username) => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host)?
+                .credentials(Credentials::new(
+                    username,
+                    std::env::var("SMTP_PASSWORD").unwrap_or_default(),
+                )),
+            Err(_) => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host),
+        }};
+
+        Ok(Self {{
+            transport: builder.port(port).build(),
+            from: env_or("MAIL_FROM", "{app} <noreply@localhost>").parse()?,
+        }})
+    }}
+
+    pub async fn send(&self, to: &str, subject: &str, html: String) -> Result<(), Error> {{
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to.parse()?)
+            .subject(subject)
+            .header(ContentType::TEXT_HTML)
+            .body(html)?;
+        self.transport.send(message).await?;
+        Ok(())
+    }}
+
+    pub async fn send_welcome(&self, to: &str, name: &str) -> Result<(), Error> {{
+        let html = render(WELCOME_TEMPLATE, &[("name", name)]);
+        self.send(to, "Welcome to {app}!", html).await
+    }}
+}}
+
+/// Replaces each `{{{{key}}}}` in `template` with its HTML-escaped value
+pub fn render(template: &str, values: &[(&str, &str)]) -> String {{
+    values.iter().fold(template.to_string(), |html, (key, value)| {{
+        html.replace(&format!("{{{{{{{{{{}}}}}}}}}}", key), &escape(value))
+    }})
+}}
+
+fn escape(value: &str) -> String {{
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}}
+
+fn env_or(name: &str, default: &str) -> String {{
+    std::env::var(name).unwrap_or_else(|_| default.to_string())
+}}
+"#,
+        app = app_name
+    )
+}
+
+/// Mailer, welcome template, SMTP settings and lettre for the `email` extra
+fn email_files(layout: &ProjectLayout) -> Vec<GeneratedFile> {
+    let src = layout.backend_dir.join("src");
+    vec![
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: "lettre = { version = \"0.11\", features = [\"tokio1\", \"tokio1-native-tls\"] }\ntokio = { version = \"1\", features = [\"full\"] }\n".to_string(),
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        },
+        GeneratedFile {
+            path: src.join("email.rs"),
+            contents: email_module(layout.name),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: src.join("main.rs"),
+            contents: "email".to_string(),
+            mode: WriteMode::WireModule { init: None },
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("templates/email/welcome.html"),
+            contents: format!(
+                r#"<!DOCTYPE html>
+<html>
+  <body style="font-family: sans-serif; line-height: 1.5">
+    <h1>Welcome, {{{{name}}}}!</h1>
+    <p>Thanks for signing up for {}. We're glad to have you.</p>
+  </body>
+</html>
+"#,
+                layout.name
+            ),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join(".env.example"),
+            contents: "SMTP_HOST=localhost\nSMTP_PORT=1025\nMAIL_FROM=noreply@localhost\n"
+                .to_string(),
+            mode: WriteMode::MergeEnv,
+        },
+    ]
+}
+
+/// `src/graphql.rs` for the backend: an example async-graphql schema and the ripress routes
+/// serving it
+const GRAPHQL_MODULE: &str = r#"//! GraphQL API: the schema, an example query and mutation, and the routes serving them.
+#![allow(dead_code)]
+
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{Context, EmptySubscription, ID, Object, Schema, SimpleObject};
+use ripress::app::App;
+use ripress::types::RouterFns;
+use std::sync::Mutex;
+
+pub type AppSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+#[derive(Clone, SimpleObject)]
+pub struct Todo {
+    pub id: ID,
+    pub title: String,
+    pub done: bool,
+}
+
+/// Example in-memory data; swap it for your database
+#[derive(Default)]
+pub struct Store {
+    todos: Mutex<Vec<Todo>>,
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Greets `name`, or the world
+    async fn hello(&self, name: Option<String>) -> String {
+        format!("Hello, {}!", name.as_deref().unwrap_or("world"))
+    }
+
+    async fn todos(&self, ctx: &Context<'_>) -> Vec<Todo> {
+        ctx.data_unchecked::<Store>().todos.lock().unwrap().clone()
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn add_todo(&self, ctx: &Context<'_>, title: String) -> Todo {
+        let mut todos = ctx.data_unchecked::<Store>().todos.lock().unwrap();
+        let todo = Todo {
+            id: ID::from(todos.len() + 1),
+            title,
+            done: false,
+        };
+        todos.push(todo.clone());
+        todo
+    }
+}
+
+pub fn schema() -> AppSchema {
+    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(Store::default())
+        .finish()
+}
+
+/// Serves the API at `POST /graphql`. Debug builds also serve GraphiQL at `/graphiql` and
+/// write the schema to `schema.graphql`, which the frontends generate their types from.
+pub fn register(app: &mut App) {
+    let schema = schema();
+
+    if cfg!(debug_assertions) {
+        if let Err(err) = std::fs::write("schema.graphql", schema.sdl()) {
+            eprintln!("Failed to write schema.graphql: {}", err);
+        }
+        app.get("/graphiql", |_, res| async move {
+            res.ok()
+                .html(&GraphiQLSource::build().endpoint("/graphql").finish())
+        });
+    }
+
+    app.post("/graphql", move |req, res| {
+        let schema = schema.clone();
+        async move {
+            match req.json::<async_graphql::Request>() {
+                Ok(request) => res.ok().json(schema.execute(request).await),
+                Err(err) => res.bad_request().text(err.to_string()),
+            }
+        }
+    });
+}
+"#;
+
+/// What [`GRAPHQL_MODULE`]'s schema prints as SDL, so the frontends' codegen works before the
+/// backend first runs
+const GRAPHQL_SCHEMA: &str = r#"type MutationRoot {
+	addTodo(title: String!): Todo!
+}
+
+type QueryRoot {
+	"""
+	Greets `name`, or the world
+	"""
+	hello(name: String): String!
+	todos: [Todo!]!
+}
+
+type Todo {
+	id: ID!
+	title: String!
+	done: Boolean!
+}
+
+"""
+Directs the executor to include this field or fragment only when the `if` argument is true.
+"""
+directive @include(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+"""
+Directs the executor to skip this field or fragment when the `if` argument is true.
+"""
+directive @skip(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+schema {
+	query: QueryRoot
+	mutation: MutationRoot
+}
+"#;
+
+/// Typed GraphQL client for a Node frontend: graphql-codegen generates `src/gql` from the
+/// backend's `schema.graphql` before each dev and build run
+fn graphql_client_files(dir: &Path, backend_dir: &Path) -> Vec<GeneratedFile> {
+    let schema = Path::new(&"../".repeat(dir.components().count()))
+        .join(backend_dir)
+        .join("schema.graphql");
+
+    vec![
+        GeneratedFile {
+            path: dir.join("package.json"),
+            contents: r#"{
+  "scripts": {"codegen": "graphql-codegen", "predev": "graphql-codegen", "prebuild": "graphql-codegen"},
+  "dependencies": {"graphql": "^16.11.0", "@graphql-typed-document-node/core": "^3.2.0"},
+  "devDependencies": {"@graphql-codegen/cli": "^5.0.7", "@graphql-codegen/client-preset": "^4.8.3"}
+}"#
+            .to_string(),
+            mode: WriteMode::MergePackageJson,
+        },
+        GeneratedFile {
+            path: dir.join("codegen.ts"),
+            contents: format!(
+                r#"import type {{ CodegenConfig }} from "@graphql-codegen/cli";
+
+const config: CodegenConfig = {{
+  schema: "{}",
+  documents: ["src/**/*.{{ts,tsx,svelte,astro}}", "!src/gql/**"],
+  ignoreNoDocuments: true,
+  generates: {{
+    "./src/gql/": {{ preset: "client" }},
+  }},
+}};
+
+export default config;
+"#,
+                schema.display()
+            ),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: dir.join("src/graphql.ts"),
+            contents: format!(
+                r#"import type {{ TypedDocumentNode }} from "@graphql-typed-document-node/core";
+import {{ print }} from "graphql";
+import {{ graphql }} from "./gql";
+
+const endpoint = "http://localhost:{}/graphql";
+
+/** Runs `document` against the backend, typed by the generated `src/gql` */
+export async function request<TResult, TVariables>(
+  document: TypedDocumentNode<TResult, TVariables>,
+  variables?: TVariables,
+): Promise<TResult> {{
+  const response = await fetch(endpoint, {{
+    method: "POST",
+    headers: {{ "Content-Type": "application/json" }},
+    body: JSON.stringify({{ query: print(document), variables }}),
+  }});
+  const {{ data, errors }} = await response.json();
+  if (errors?.length) {{
+    throw new Error(errors.map((error: {{ message: string }}) => error.message).join("\n"));
+  }}
+  return data;
+}}
+
+/** Example query; `await request(TodosQuery)` resolves to typed todos */
+export const TodosQuery = graphql(`
+  query Todos {{
+    todos {{
+      id
+      title
+      done
+    }}
+  }}
+`);
+"#,
+                BACKEND_PORT
+            ),
+            mode: WriteMode::Create,
+        },
+    ]
+}
+
+/// Schema module, async-graphql and the printed schema for the `graphql` component
+fn graphql_files(layout: &ProjectLayout) -> Vec<GeneratedFile> {
+    let src = layout.backend_dir.join("src");
+    let mut files = vec![
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: "async-graphql = \"7\"\n".to_string(),
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        },
+        GeneratedFile {
+            path: src.join("graphql.rs"),
+            contents: GRAPHQL_MODULE.to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: src.join("main.rs"),
+            contents: "graphql".to_string(),
+            mode: WriteMode::WireModule { init: None },
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("schema.graphql"),
+            contents: GRAPHQL_SCHEMA.to_string(),
+            mode: WriteMode::Create,
+        },
+    ];
+    for (dir, frontend) in &layout.frontends {
+        if NODE_FRONTENDS.contains(frontend) {
+            files.extend(graphql_client_files(dir, &layout.backend_dir));
+        }
+    }
+    files
+}
+
+/// Sample service definition compiled by `build.rs`
+const GREETER_PROTO: &str = r#"syntax = "proto3";
+
+package greeter;
+
+// Example service; add RPCs here and implement them in src/grpc.rs
+service Greeter {
+  rpc SayHello (HelloRequest) returns (HelloReply);
+}
+
+message HelloRequest {
+  string name = 1;
+}
+
+message HelloReply {
+  string message = 1;
+}
+"#;
+
+/// `src/grpc.rs` for the backend: the tonic server for the sample proto and a client helper
+/// for calling other services
+const GRPC_MODULE: &str = r#"//! gRPC services for service-to-service calls, generated from `proto/greeter.proto`.
+#![allow(dead_code)]
+
+use std::net::SocketAddr;
+use tonic::transport::{Channel, Server};
+use tonic::{Request, Response, Status};
+
+pub mod proto {
+    tonic::include_proto!("greeter");
+}
+
+use proto::greeter_client::GreeterClient;
+use proto::greeter_server::{Greeter, GreeterServer};
+use proto::{HelloReply, HelloRequest};
+
+#[derive(Default)]
+pub struct GreeterService;
+
+#[tonic::async_trait]
+impl Greeter for GreeterService {
+    async fn say_hello(
+        &self,
+        request: Request<HelloRequest>,
+    ) -> Result<Response<HelloReply>, Status> {
+        let name = request.into_inner().name;
+        Ok(Response::new(HelloReply {
+            message: format!("Hello, {}!", name),
+        }))
+    }
+}
+
+/// Serves the gRPC services on `GRPC_PORT` (50051 by default). It runs beside the HTTP
+/// server: `tokio::spawn(grpc::serve());`
+pub async fn serve() {
+    let port = std::env::var("GRPC_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(50051);
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+
+    println!("📡 gRPC server listening on {}", addr);
+    if let Err(err) = Server::builder()
+        .add_service(GreeterServer::new(GreeterService))
+        .serve(addr)
+        .await
+    {
+        eprintln!("gRPC server stopped: {}", err);
+    }
+}
+
+/// Client for another service's Greeter, e.g. `connect("http://users:50051")`
+pub async fn connect(url: &str) -> Result<GreeterClient<Channel>, tonic::transport::Error> {
+    GreeterClient::connect(url.to_string()).await
+}
+"#;
+
+/// Sample proto, build-time codegen, tonic server and client for the `grpc` component
+fn grpc_files(layout: &ProjectLayout) -> Vec<GeneratedFile> {
+    vec![
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: "tonic = \"0.14\"\ntonic-prost = \"0.14\"\nprost = \"0.14\"\ntokio = { version = \"1\", features = [\"full\"] }\n".to_string(),
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: "tonic-prost-build = \"0.14\"\n".to_string(),
+            mode: WriteMode::Dependencies {
+                table: "build-dependencies",
+            },
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("build.rs"),
+            contents: r#"fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Needs `protoc` on the PATH
+    tonic_prost_build::compile_protos("proto/greeter.proto")?;
+    Ok(())
+}
+"#
+            .to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("proto/greeter.proto"),
+            contents: GREETER_PROTO.to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("src/grpc.rs"),
+            contents: GRPC_MODULE.to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("src/main.rs"),
+            contents: "grpc".to_string(),
+            mode: WriteMode::WireModule { init: None },
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join(".env.example"),
+            contents: format!("GRPC_PORT={}\n", GRPC_PORT),
+            mode: WriteMode::MergeEnv,
+        },
+    ]
+}
+
+/// `src/scheduler.rs` for the backend: the cron scheduler and its example task
+const SCHEDULER_MODULE: &str = r#"//! Recurring tasks run on cron schedules. Schedules have a seconds field
+//! (`sec min hour day-of-month month day-of-week`) and can be overridden from the environment.
+#![allow(dead_code)]
+
+use tokio_cron_scheduler::{Job, JobScheduler, JobSchedulerError};
+
+/// Starts every task unless `SCHEDULER_ENABLED=false`. Keep the returned scheduler alive, e.g.
+/// `let _scheduler = scheduler::start().await?;` at the top of `main`.
+pub async fn start() -> Result<JobScheduler, JobSchedulerError> {
+    let scheduler = JobScheduler::new().await?;
+    if std::env::var("SCHEDULER_ENABLED").is_ok_and(|enabled| enabled == "false") {
+        return Ok(scheduler);
+    }
+
+    // Example task; replace the body with real work such as pruning expired records
+    let cleanup = schedule("CLEANUP_SCHEDULE", "0 */5 * * * *");
+    scheduler
+        .add(Job::new_async(cleanup.as_str(), |_id, _scheduler| {
+            Box::pin(async {
+                println!("🧹 Running the cleanup task");
+            })
+        })?)
+        .await?;
+
+    scheduler.start().await?;
+    Ok(scheduler)
+}
+
+/// The cron expression in the `env` variable, or `default` when it isn't set
+fn schedule(env: &str, default: &str) -> String {
+    std::env::var(env).unwrap_or_else(|_| default.to_string())
+}
+"#;
+
+/// README section documenting the scheduled tasks and how to configure them
+const SCHEDULER_README: &str = r#"## Scheduled tasks
+
+Recurring tasks live in `src/scheduler.rs` and run once `scheduler::start()` is called from
+`main`. Schedules are cron expressions with a seconds field (`sec min hour day month weekday`).
+
+| Task    | Default schedule                   | Override           |
+| ------- | ---------------------------------- | ------------------ |
+| cleanup | `0 */5 * * * *` (every 5 minutes)  | `CLEANUP_SCHEDULE` |
+
+Set `SCHEDULER_ENABLED=false` to run the server without its tasks, e.g. on all but one
+replica. To add a task, add another `Job` in `start()` with its own schedule variable and
+list it here.
+"#;
+
+/// tokio-cron-scheduler setup, configuration and README docs for the `scheduler` component
+fn scheduler_files(layout: &ProjectLayout) -> Vec<GeneratedFile> {
+    vec![
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: "tokio-cron-scheduler = \"0.14\"\ntokio = { version = \"1\", features = [\"full\"] }\n".to_string(),
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("src/scheduler.rs"),
+            contents: SCHEDULER_MODULE.to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("src/main.rs"),
+            contents: "scheduler".to_string(),
+            mode: WriteMode::WireModule { init: None },
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join(".env.example"),
+            contents: "SCHEDULER_ENABLED=true\nCLEANUP_SCHEDULE=\"0 */5 * * * *\"\n".to_string(),
+            mode: WriteMode::MergeEnv,
+        },
+        GeneratedFile {
+            path: PathBuf::from("README.md"),
+            contents: SCHEDULER_README.to_string(),
+            mode: WriteMode::AppendSection,
+        },
+    ]
+}
+
+/// Example fixtures loaded by the seed binary; edit them to match your lume models
+const SEED_USERS: &str = "-- Fixtures run by `hexstack db seed`, in file name order
+CREATE TABLE IF NOT EXISTS users (id INTEGER PRIMARY KEY, name VARCHAR(100) NOT NULL, email VARCHAR(255) NOT NULL);
+DELETE FROM users;
+INSERT INTO users (id, name, email) VALUES (1, 'Ada Lovelace', 'ada@example.com');
+INSERT INTO users (id, name, email) VALUES (2, 'Alan Turing', 'alan@example.com');
+INSERT INTO users (id, name, email) VALUES (3, 'Grace Hopper', 'grace@example.com');
+";
+
+/// `src/bin/seed.rs` for the backend: runs every `seeds/*.sql` file against `DATABASE_URL`
+fn seed_binary(database: Database) -> String {
+    let (pool, connect) = match database {
+        Database::Postgres => ("sqlx::PgPool", "sqlx::PgPool::connect(&url).await?"),
+        Database::Sqlite => (
+            "sqlx::SqlitePool",
+            "sqlx::SqlitePool::connect_with(\n        url.parse::<sqlx::sqlite::SqliteConnectOptions>()?\n            .create_if_missing(true),\n    )\n    .await?",
+        ),
+        Database::Mysql => ("sqlx::MySqlPool", "sqlx::MySqlPool::connect(&url).await?"),
+    };
+
+    format!(
+        r#"//! Loads the fixtures in `seeds/` into the database, so the app has data to play with.
+//! Run it with `hexstack db seed` or `cargo run --bin seed`.
+
+use std::path::PathBuf;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {{
+    let _ = dotenvy::dotenv();
+    let url = std::env::var("DATABASE_URL").map_err(|_| "DATABASE_URL is not set")?;
+    let pool: {pool} = {connect};
+
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("seeds");
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "sql"))
+        .collect();
+    files.sort();
+
+    for file in files {{
+        let sql = std::fs::read_to_string(&file)?;
+        let statements = sql
+            .split(';')
+            .map(|statement| {{
+                statement
+                    .lines()
+                    .filter(|line| !line.trim_start().starts_with("--"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }})
+            .filter(|statement| !statement.trim().is_empty());
+        for statement in statements {{
+            sqlx::query(&statement).execute(&pool).await?;
+        }}
+        println!("🌱 Seeded {{}}", file.display());
+    }}
+    Ok(())
+}}
+"#
+    )
+}
+
+/// Seed binary and example fixtures for lume projects, run by `hexstack db seed`
+fn seed_files(layout: &ProjectLayout, database: Database) -> Vec<GeneratedFile> {
+    vec![
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: format!(
+                "sqlx = {{ version = \"0.8\", features = [\"runtime-tokio\", \"{}\"] }}\ndotenvy = \"0.15\"\ntokio = {{ version = \"1\", features = [\"full\"] }}\n",
+                database.key()
+            ),
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        },
+        // With a second binary, `cargo run` needs to know which one is the server
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: String::new(),
+            mode: WriteMode::DefaultRun,
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("src/bin/seed.rs"),
+            contents: seed_binary(database),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("seeds/001_users.sql"),
+            contents: SEED_USERS.to_string(),
+            mode: WriteMode::Create,
+        },
+    ]
+}
+
+/// `src/rate_limit.rs` for the backend: a per-IP token bucket kept in process, or in Redis
+/// so every server instance shares the limits
+fn rate_limit_module(redis: bool) -> String {
+    let (imports, store, connect, take) = if redis {
+        (
+            "use redis::aio::ConnectionManager;\nuse ripress::app::App;\nuse std::net::IpAddr;\nuse std::sync::Arc;\n",
+            r#"/// Buckets live in Redis as `rate-limit:<ip>` hashes, shared by every server instance
+#[derive(Clone)]
+struct Store {
+    connection: ConnectionManager,
+}
+
+/// Refills the bucket for the elapsed time and takes a token, atomically
+const TAKE_SCRIPT: &str = r"
+local capacity = tonumber(ARGV[1])
+local per_second = tonumber(ARGV[2])
+local now = tonumber(ARGV[3])
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'updated')
+local tokens = tonumber(bucket[1]) or capacity
+local updated = tonumber(bucket[2]) or now
+tokens = math.min(capacity, tokens + (now - updated) / 1000 * per_second)
+local allowed = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+end
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'updated', tostring(now))
+redis.call('PEXPIRE', KEYS[1], math.ceil(capacity / per_second * 1000))
+return allowed
+";
+"#,
+            r#"
+    /// Reads `RATE_LIMIT_PER_MINUTE` (default 60) and `RATE_LIMIT_BURST` (default 20) and
+    /// connects to `REDIS_URL`, defaulting to a local Redis
+    pub async fn connect() -> redis::RedisResult<Self> {
+        let url =
+            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let connection = ConnectionManager::new(redis::Client::open(url)?).await?;
+        Ok(Self::new(Store { connection }))
+    }
+"#,
+            r#"    /// Takes a token from `ip`'s bucket, returning false when it's empty. Requests are let
+    /// through when Redis can't be reached.
+    pub async fn allow(&self, ip: IpAddr) -> bool {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let result: redis::RedisResult<i32> = redis::Script::new(TAKE_SCRIPT)
+            .key(format!("rate-limit:{}", ip))
+            .arg(self.capacity)
+            .arg(self.per_second)
+            .arg(now)
+            .invoke_async(&mut self.store.connection.clone())
+            .await;
+        result.unwrap_or_else(|err| {
+            eprintln!("rate limiting skipped: {}", err);
+            1
+        }) == 1
+    }
+"#,
+        )
+    } else {
+        (
+            "use ripress::app::App;\nuse std::collections::HashMap;\nuse std::net::IpAddr;\nuse std::sync::{Arc, Mutex};\nuse std::time::Instant;\n",
+            r#"/// Buckets are kept per server process; use Redis to share them between instances
+#[derive(Default)]
+struct Store {
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Past this many buckets, full ones are dropped since they behave like new ones
+const MAX_IDLE_BUCKETS: usize = 10_000;
+"#,
+            "",
+            r#"    /// Takes a token from `ip`'s bucket, returning false when it's empty
+    pub async fn allow(&self, ip: IpAddr) -> bool {
+        let mut buckets = self.store.buckets.lock().unwrap();
+        let now = Instant::now();
+        if buckets.len() > MAX_IDLE_BUCKETS {
+            buckets.retain(|_, bucket| self.refill(bucket, now) < self.capacity as f64);
+        }
+
+        let bucket = buckets.entry(ip).or_insert(Bucket {
+            tokens: self.capacity as f64,
+            updated: now,
+        });
+        if self.refill(bucket, now) < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+
+    /// Adds the tokens earned since the bucket was last updated, returning the new count
+    fn refill(&self, bucket: &mut Bucket, now: Instant) -> f64 {
+        let earned = now.duration_since(bucket.updated).as_secs_f64() * self.per_second;
+        bucket.tokens = (bucket.tokens + earned).min(self.capacity as f64);
+        bucket.updated = now;
+        bucket.tokens
+    }
+"#,
+        )
+    };
+    let from_env = if redis {
+        String::new()
+    } else {
+        r#"
+    /// Reads `RATE_LIMIT_PER_MINUTE` (default 60) and `RATE_LIMIT_BURST` (default 20)
+    pub fn from_env() -> Self {
+        Self::new(Store::default())
+    }
+"#
+        .to_string()
+    };
+
+    format!(
+        r#"//! Per-IP rate limiting for the ripress routes. Each client IP gets a token bucket holding
+//! up to `RATE_LIMIT_BURST` requests that refills at `RATE_LIMIT_PER_MINUTE`; requests
+//! arriving at an empty bucket get `429 Too Many Requests`.
+#![allow(dead_code)]
+
+{imports}
+{store}
+#[derive(Clone)]
+pub struct RateLimiter {{
+    capacity: u32,
+    per_second: f64,
+    store: Arc<Store>,
+}}
+
+impl RateLimiter {{
+    fn new(store: Store) -> Self {{
+        Self {{
+            capacity: env_or("RATE_LIMIT_BURST", 20).max(1),
+            per_second: env_or("RATE_LIMIT_PER_MINUTE", 60).max(1) as f64 / 60.0,
+            store: Arc::new(store),
+        }}
+    }}
+{from_env}{connect}
+{take}}}
+
+fn env_or(name: &str, default: u32) -> u32 {{
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}}
+
+/// Limits every route of `app`; register it before the routes
+pub fn register(app: &mut App, limiter: RateLimiter) {{
+    app.use_pre_middleware("/", move |req, res| {{
+        let limiter = limiter.clone();
+        async move {{
+            if limiter.allow(req.ip).await {{
+                (req, None)
+            }} else {{
+                let res = res.status(429).text("Too many requests");
+                (req, Some(res))
+            }}
+        }}
+    }});
+}}
+"#
+    )
+}
+
+/// Rate-limiting middleware and its `.env` knobs for the `rate-limit` extra, using Redis when
+/// the redis component is selected
+fn rate_limit_files(layout: &ProjectLayout) -> Vec<GeneratedFile> {
+    let redis = layout.components.iter().any(|c| c == "redis");
+    let src = layout.backend_dir.join("src");
+    let mut files = Vec::new();
+    if redis {
+        files.push(GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: "redis = { version = \"0.32\", features = [\"tokio-comp\", \"connection-manager\"] }\n".to_string(),
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        });
+    }
+    files.extend([
+        GeneratedFile {
+            path: src.join("rate_limit.rs"),
+            contents: rate_limit_module(redis),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: src.join("main.rs"),
+            contents: "rate_limit".to_string(),
+            mode: WriteMode::WireModule { init: None },
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join(".env.example"),
+            contents: "RATE_LIMIT_PER_MINUTE=60\nRATE_LIMIT_BURST=20\n".to_string(),
+            mode: WriteMode::MergeEnv,
+        },
+    ]);
+    files
+}
+
+/// The session types, cookie handling and in-memory store of `src/session.rs`
+const SESSION_MODULE: &str = r#"
+/// Key holding the logged-in user's id
+const USER_ID: &str = "user_id";
+
+pub type SessionData = HashMap<String, String>;
+
+/// Where session data is kept; implement it to plug in another backend
+pub trait SessionStore: Send + Sync + 'static {
+    fn load(&self, id: &str) -> impl Future<Output = Option<SessionData>> + Send;
+    fn save(&self, id: &str, data: &SessionData, ttl: Duration) -> impl Future<Output = ()> + Send;
+    fn destroy(&self, id: &str) -> impl Future<Output = ()> + Send;
+}
+
+/// A request's session; changes are kept once it's passed to [`Sessions::save`]
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    /// Id from the request's cookie, `None` for a new session
+    id: Option<String>,
+    data: SessionData,
+    /// Give the session a new id when saving, e.g. after logging in
+    rotate: bool,
+    destroyed: bool,
+}
+
+impl Session {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.data.get(key).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.data.insert(key.into(), value.into());
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.data.remove(key)
+    }
+
+    /// The logged-in user, if any
+    pub fn user_id(&self) -> Option<&str> {
+        self.get(USER_ID)
+    }
+
+    /// Marks `user_id` as logged in. The session gets a new id so one set before logging in
+    /// can't be reused.
+    pub fn log_in(&mut self, user_id: impl Into<String>) {
+        self.insert(USER_ID, user_id);
+        self.rotate = true;
+    }
+
+    /// Ends the session, deleting its data and cookie
+    pub fn log_out(&mut self) {
+        self.data.clear();
+        self.destroyed = true;
+    }
+}
+
+/// Loads and saves sessions in `S`. Cloning is cheap; clones share the store.
+pub struct Sessions<S> {
+    store: Arc<S>,
+    cookie: String,
+    ttl: Duration,
+    secure: bool,
+}
+
+impl<S> Clone for Sessions<S> {
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store.clone(),
+            cookie: self.cookie.clone(),
+            ttl: self.ttl,
+            secure: self.secure,
+        }
+    }
+}
+
+impl<S: SessionStore> Sessions<S> {
+    /// Reads `SESSION_COOKIE` (default `sid`), `SESSION_TTL_SECS` (default a day) and
+    /// `SESSION_SECURE` (set it to `true` when served over HTTPS)
+    pub fn new(store: S) -> Self {
+        Self {
+            store: Arc::new(store),
+            cookie: std::env::var("SESSION_COOKIE").unwrap_or_else(|_| "sid".to_string()),
+            ttl: Duration::from_secs(
+                std::env::var("SESSION_TTL_SECS")
+                    .ok()
+                    .and_then(|secs| secs.parse().ok())
+                    .unwrap_or(86_400),
+            ),
+            secure: std::env::var("SESSION_SECURE").is_ok_and(|secure| secure == "true"),
+        }
+    }
+
+    /// The session named by the request's cookie, or a new empty one
+    pub async fn load(&self, req: &HttpRequest) -> Session {
+        let Some(id) = self.cookie_value(req) else {
+            return Session::default();
+        };
+        match self.store.load(&id).await {
+            Some(data) => Session {
+                id: Some(id),
+                data,
+                ..Session::default()
+            },
+            None => Session::default(),
+        }
+    }
+
+    /// Stores `session` and sets its cookie on `res`. New sessions without data get no cookie.
+    pub async fn save(&self, session: Session, res: HttpResponse) -> HttpResponse {
+        if session.destroyed || session.rotate {
+            if let Some(id) = &session.id {
+                self.store.destroy(id).await;
+            }
+            if session.destroyed {
+                return res.set_header("Set-Cookie", &self.set_cookie("", Duration::ZERO));
+            }
+        }
+        if session.data.is_empty() && session.id.is_none() {
+            return res;
+        }
+
+        let id = match session.id {
+            Some(id) if !session.rotate => id,
+            _ => uuid::Uuid::new_v4().simple().to_string(),
+        };
+        self.store.save(&id, &session.data, self.ttl).await;
+        res.set_header("Set-Cookie", &self.set_cookie(&id, self.ttl))
+    }
+
+    /// Middleware answering `401 Unauthorized` for requests under `path` from sessions
+    /// without a logged-in user
+    pub fn require_login(&self, app: &mut App, path: &str) {
+        let sessions = self.clone();
+        app.use_pre_middleware(path, move |req, res| {
+            let sessions = sessions.clone();
+            async move {
+                if sessions.load(&req).await.user_id().is_some() {
+                    (req, None)
+                } else {
+                    let res = res.status(401).text("Unauthorized");
+                    (req, Some(res))
+                }
+            }
+        });
+    }
+
+    fn cookie_value(&self, req: &HttpRequest) -> Option<String> {
+        req.headers
+            .get("cookie")?
+            .split(';')
+            .filter_map(|pair| pair.trim().split_once('='))
+            .find(|(name, _)| *name == self.cookie)
+            .map(|(_, value)| value.to_string())
+    }
+
+    fn set_cookie(&self, value: &str, max_age: Duration) -> String {
+        format!(
+            "{}={}; Path=/; HttpOnly; SameSite=Lax; Max-Age={}{}",
+            self.cookie,
+            value,
+            max_age.as_secs(),
+            if self.secure { "; Secure" } else { "" }
+        )
+    }
+}
+
+/// Keeps sessions in process memory, so they're lost on restart; meant for development
+#[derive(Default)]
+pub struct MemoryStore {
+    sessions: Mutex<HashMap<String, (SessionData, Instant)>>,
+}
+
+impl SessionStore for MemoryStore {
+    async fn load(&self, id: &str) -> Option<SessionData> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let now = Instant::now();
+        sessions.retain(|_, (_, expires)| *expires > now);
+        sessions.get(id).map(|(data, _)| data.clone())
+    }
+
+    async fn save(&self, id: &str, data: &SessionData, ttl: Duration) {
+        let expires = Instant::now() + ttl;
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.insert(id.to_string(), (data.clone(), expires));
+    }
+
+    async fn destroy(&self, id: &str) {
+        self.sessions.lock().unwrap().remove(id);
+    }
+}
+"#;
+
+/// Redis session store of `src/session.rs`, for the redis component
+const SESSION_REDIS_STORE: &str = r#"
+/// Keeps sessions in Redis as JSON under `session:<id>`, expiring with their cookie
+#[derive(Clone)]
+pub struct RedisStore {
+    connection: ConnectionManager,
+}
+
+impl RedisStore {
+    /// Connects to `REDIS_URL`, defaulting to a local Redis
+    pub async fn connect() -> redis::RedisResult<Self> {
+        let url =
+            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let client = redis::Client::open(url)?;
+        Ok(Self {
+            connection: ConnectionManager::new(client).await?,
+        })
+    }
+}
+
+impl SessionStore for RedisStore {
+    async fn load(&self, id: &str) -> Option<SessionData> {
+        let json: Option<String> = self
+            .connection
+            .clone()
+            .get(format!("session:{}", id))
+            .await
+            .ok()?;
+        serde_json::from_str(&json?).ok()
+    }
+
+    async fn save(&self, id: &str, data: &SessionData, ttl: Duration) {
+        let json = serde_json::to_string(data).unwrap_or_default();
+        let result: redis::RedisResult<()> = self
+            .connection
+            .clone()
+            .set_ex(format!("session:{}", id), json, ttl.as_secs())
+            .await;
+        if let Err(err) = result {
+            eprintln!("failed to save session: {}", err);
+        }
+    }
+
+    async fn destroy(&self, id: &str) {
+        let _: redis::RedisResult<()> = self.connection.clone().del(format!("session:{}", id)).await;
+    }
+}
+"#;
+
+/// Database session store of `src/session.rs`: the pool type, connection code and upsert
+/// statement for `database`
+fn session_database_store(database: Database) -> String {
+    let (pool, connect, upsert, placeholders) = match database {
+        Database::Postgres => (
+            "sqlx::PgPool",
+            "sqlx::PgPool::connect(&url).await?",
+            "ON CONFLICT (id) DO UPDATE SET data = excluded.data, expires_at = excluded.expires_at",
+            ["$1", "$2", "$3"],
+        ),
+        Database::Sqlite => (
+            "sqlx::SqlitePool",
+            "sqlx::SqlitePool::connect_with(\n            url.parse::<sqlx::sqlite::SqliteConnectOptions>()?\n                .create_if_missing(true),\n        )\n        .await?",
+            "ON CONFLICT (id) DO UPDATE SET data = excluded.data, expires_at = excluded.expires_at",
+            ["?", "?", "?"],
+        ),
+        Database::Mysql => (
+            "sqlx::MySqlPool",
+            "sqlx::MySqlPool::connect(&url).await?",
+            "ON DUPLICATE KEY UPDATE data = VALUES(data), expires_at = VALUES(expires_at)",
+            ["?", "?", "?"],
+        ),
+    };
+    let [first, second, third] = placeholders;
+
+    format!(
+        r#"
+/// Keeps sessions in the project's database, in a `sessions` table created on connect
+#[derive(Clone)]
+pub struct DatabaseStore {{
+    pool: {pool},
+}}
+
+impl DatabaseStore {{
+    /// Connects to `DATABASE_URL`
+    pub async fn connect() -> Result<Self, sqlx::Error> {{
+        let url = std::env::var("DATABASE_URL")
+            .map_err(|_| sqlx::Error::Configuration("DATABASE_URL is not set".into()))?;
+        let pool = {connect};
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS sessions (id VARCHAR(64) PRIMARY KEY, data TEXT NOT NULL, expires_at BIGINT NOT NULL)",
+        )
+        .execute(&pool)
+        .await?;
+        Ok(Self {{ pool }})
+    }}
+}}
+
+/// Seconds since the Unix epoch
+fn unix_now() -> i64 {{
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}}
+
+impl SessionStore for DatabaseStore {{
+    async fn load(&self, id: &str) -> Option<SessionData> {{
+        let (json,): (String,) =
+            sqlx::query_as("SELECT data FROM sessions WHERE id = {first} AND expires_at > {second}")
+                .bind(id)
+                .bind(unix_now())
+                .fetch_optional(&self.pool)
+                .await
+                .ok()??;
+        serde_json::from_str(&json).ok()
+    }}
+
+    async fn save(&self, id: &str, data: &SessionData, ttl: Duration) {{
+        let json = serde_json::to_string(data).unwrap_or_default();
+        let result = sqlx::query(
+            "INSERT INTO sessions (id, data, expires_at) VALUES ({first}, {second}, {third}) {upsert}",
+        )
+        .bind(id)
+        .bind(json)
+        .bind(unix_now() + ttl.as_secs() as i64)
+        .execute(&self.pool)
+        .await;
+        if let Err(err) = result {{
+            eprintln!("failed to save session: {{}}", err);
+        }}
+    }}
+
+    async fn destroy(&self, id: &str) {{
+        let _ = sqlx::query("DELETE FROM sessions WHERE id = {first}")
+            .bind(id)
+            .execute(&self.pool)
+            .await;
+    }}
+}}
+"#
+    )
+}
+
+/// `src/session.rs` for the backend, with a Redis store when `redis` is set and a database
+/// store when a database was picked
+fn session_module(redis: bool, database: Option<Database>) -> String {
+    let mut stores = vec!["`MemoryStore` for development"];
+    if redis {
+        stores.push("`RedisStore`");
+    }
+    if database.is_some() {
+        stores.push("`DatabaseStore`");
+    }
+    let mut imports = String::new();
+    if redis {
+        imports.push_str("use redis::AsyncCommands;\nuse redis::aio::ConnectionManager;\n");
+    }
+    imports.push_str("use ripress::app::App;\nuse ripress::context::{HttpRequest, HttpResponse};\nuse std::collections::HashMap;\nuse std::future::Future;\nuse std::sync::{Arc, Mutex};\nuse std::time::{Duration, Instant};\n");
+
+    let mut module = format!(
+        r#"//! Cookie-based sessions. The session id is kept in a cookie and the data in a
+//! [`SessionStore`]: {}. Load the session in a handler, change it and save it with the
+//! response:
+//!
+//! ```ignore
+//! let mut session = sessions.load(&req).await;
+//! session.log_in(user.id.to_string());
+//! sessions.save(session, res.ok().text("Logged in")).await
+//! ```
+#![allow(dead_code)]
+
+{}"#,
+        stores.join(", "),
+        imports
+    );
+    module.push_str(SESSION_MODULE);
+    if redis {
+        module.push_str(SESSION_REDIS_STORE);
+    }
+    if let Some(database) = database {
+        module.push_str(&session_database_store(database));
+    }
+    module
+}
+
+/// Session module, its dependencies and `.env` settings for the `sessions` extra
+fn session_files(layout: &ProjectLayout, database: Option<Database>) -> Vec<GeneratedFile> {
+    let redis = layout.components.iter().any(|c| c == "redis");
+    let src = layout.backend_dir.join("src");
+    let mut dependencies = String::from("uuid = { version = \"1\", features = [\"v4\"] }\n");
+    if redis {
+        dependencies.push_str(
+            "redis = { version = \"0.32\", features = [\"tokio-comp\", \"connection-manager\"] }\n",
+        );
+    }
+    if redis || database.is_some() {
+        dependencies.push_str("serde_json = \"1\"\n");
+    }
+    if let Some(database) = database {
+        dependencies.push_str(&format!(
+            "sqlx = {{ version = \"0.8\", features = [\"runtime-tokio\", \"{}\"] }}\n",
+            database.key()
+        ));
+    }
+
+    vec![
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: dependencies,
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        },
+        GeneratedFile {
+            path: src.join("session.rs"),
+            contents: session_module(redis, database),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: src.join("main.rs"),
+            contents: "session".to_string(),
+            mode: WriteMode::WireModule { init: None },
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join(".env.example"),
+            contents: "SESSION_COOKIE=sid\nSESSION_TTL_SECS=86400\nSESSION_SECURE=false\n"
+                .to_string(),
+            mode: WriteMode::MergeEnv,
+        },
+    ]
+}
+
+/// `src/openapi.rs` for the backend: the utoipa description of the API and an example route
+const OPENAPI_MODULE: &str = r#"//! OpenAPI description of the HTTP API. Annotate handlers with `#[utoipa::path]` and list
+//! them and their schemas in `ApiDoc`; `hexstack generate api-client` turns the spec into
+//! the frontends' TypeScript client.
+#![allow(dead_code)]
+
+use ripress::app::App;
+use ripress::context::{HttpRequest, HttpResponse};
+use ripress::types::RouterFns;
+use serde::Serialize;
+use utoipa::{OpenApi, ToSchema};
+
+#[derive(OpenApi)]
+#[openapi(paths(hello), components(schemas(Greeting)))]
+pub struct ApiDoc;
+
+/// A greeting from the server
+#[derive(Serialize, ToSchema)]
+pub struct Greeting {
+    pub message: String,
+}
+
+/// Says hello
+#[utoipa::path(
+    get,
+    path = "/api/hello",
+    operation_id = "getHello",
+    responses((status = 200, description = "A greeting", body = Greeting))
+)]
+pub async fn hello(_req: HttpRequest, res: HttpResponse) -> HttpResponse {
+    res.ok().json(Greeting {
+        message: "Hello!".to_string(),
+    })
+}
+
+/// Registers the documented routes. Debug builds also serve the spec at `/openapi.json` and
+/// write it to `openapi.json` for `hexstack generate api-client`.
+pub fn register(app: &mut App) {
+    app.get("/api/hello", hello);
+
+    if cfg!(debug_assertions) {
+        match ApiDoc::openapi().to_pretty_json() {
+            Ok(spec) => {
+                if let Err(err) = std::fs::write("openapi.json", spec) {
+                    eprintln!("Failed to write openapi.json: {}", err);
+                }
+            }
+            Err(err) => eprintln!("Failed to serialize the OpenAPI spec: {}", err),
+        }
+        app.get("/openapi.json", |_, res| async move {
+            res.ok().json(ApiDoc::openapi())
+        });
+    }
+}
+"#;
+
+/// utoipa, the documented example route, the spec and the frontends' client for the `openapi`
+/// extra
+fn openapi_files(layout: &ProjectLayout) -> Vec<GeneratedFile> {
+    let spec = crate::openapi::example_spec(layout.name);
+    let mut files = vec![
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: "utoipa = \"5\"\nserde = { version = \"1\", features = [\"derive\"] }\n"
+                .to_string(),
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("src/openapi.rs"),
+            contents: OPENAPI_MODULE.to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("src/main.rs"),
+            contents: "openapi".to_string(),
+            mode: WriteMode::WireModule { init: None },
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("openapi.json"),
+            contents: format!("{:#}\n", spec),
+            mode: WriteMode::Create,
+        },
+    ];
+
+    // The example spec is always valid, so the client can't fail to generate
+    let client = crate::openapi::typescript_client(&spec).unwrap_or_default();
+    for (dir, frontend) in &layout.frontends {
+        if NODE_FRONTENDS.contains(frontend) {
+            files.extend(client.iter().map(|(name, contents)| GeneratedFile {
+                path: dir.join(crate::openapi::CLIENT_DIR).join(name),
+                contents: contents.clone(),
+                mode: WriteMode::Create,
+            }));
+        }
+    }
+    files
+}
+
+/// `lib.rs` of the shared crate, with example types exported to TypeScript
+const SHARED_LIB: &str = r#"//! Types shared by the backend and the frontends. Each `#[ts(export)]` type gets a TypeScript
+//! definition in the frontends' `src/types/` when you run `hexstack generate types`.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// A message exchanged between the backend and the frontends
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Message {
+    pub id: u32,
+    pub text: String,
+    pub author: Option<String>,
+}
+
+/// Body of an error response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ApiError {
+    pub message: String,
+}
+"#;
+
+/// What ts-rs exports for [`SHARED_LIB`], so the frontends have the types before the first
+/// `hexstack generate types`
+const SHARED_TYPESCRIPT: [(&str, &str); 2] = [
+    (
+        "Message.ts",
+        "// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.\n\n/**\n * A message exchanged between the backend and the frontends\n */\nexport type Message = { id: number, text: string, author: string | null, };\n",
+    ),
+    (
+        "ApiError.ts",
+        "// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.\n\n/**\n * Body of an error response\n */\nexport type ApiError = { message: string, };\n",
+    ),
+];
+
+/// The shared crate inside the backend directory, the backend's dependency on it and the
+/// exported TypeScript in each Node frontend
+fn shared_types_files(layout: &ProjectLayout) -> Vec<GeneratedFile> {
+    let shared = layout.backend_dir.join("shared");
+    let mut files = vec![
+        GeneratedFile {
+            path: shared.join("Cargo.toml"),
+            contents: r#"[package]
+name = "shared"
+version = "0.1.0"
+edition = "2024"
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+ts-rs = "11"
+"#
+            .to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: shared.join("src/lib.rs"),
+            contents: SHARED_LIB.to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: "shared = { path = \"shared\" }\n".to_string(),
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        },
+    ];
+    for (dir, frontend) in &layout.frontends {
+        if NODE_FRONTENDS.contains(frontend) {
+            files.extend(SHARED_TYPESCRIPT.map(|(name, contents)| GeneratedFile {
+                path: dir.join(TYPES_DIR).join(name),
+                contents: contents.to_string(),
+                mode: WriteMode::Create,
+            }));
+        }
+    }
+    files
+}
+
+/// `src/storage.rs` for the backend: an S3-compatible object store client
+const STORAGE_MODULE: &str = r#"//! S3-compatible object storage. In development files go to the MinIO container; browse them at
+//! http://localhost:9001 (minioadmin / minioadmin).
+#![allow(dead_code)]
+
+use object_store::aws::{AmazonS3, AmazonS3Builder};
+use object_store::path::Path;
+use object_store::{ObjectStore, PutPayload};
+use std::sync::Arc;
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Clone)]
+pub struct Storage {
+    store: Arc<AmazonS3>,
+}
+
+impl Storage {
+    /// Reads `S3_ENDPOINT`, `S3_BUCKET`, `S3_REGION`, `S3_ACCESS_KEY_ID` and
+    /// `S3_SECRET_ACCESS_KEY`, defaulting to the local MinIO
+    pub fn from_env() -> Result<Self, Error> {
+        let endpoint = env_or("S3_ENDPOINT", "http://localhost:9000");
+        let store = AmazonS3Builder::new()
+            .with_allow_http(endpoint.starts_with("http://"))
+            .with_endpoint(endpoint)
+            .with_bucket_name(env_or("S3_BUCKET", "uploads"))
+            .with_region(env_or("S3_REGION", "us-east-1"))
+            .with_access_key_id(env_or("S3_ACCESS_KEY_ID", "minioadmin"))
+            .with_secret_access_key(env_or("S3_SECRET_ACCESS_KEY", "minioadmin"))
+            .build()?;
+        Ok(Self {
+            store: Arc::new(store),
+        })
+    }
+
+    pub async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<(), Error> {
+        self.store
+            .put(&Path::from(key), PutPayload::from(bytes))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Vec<u8>, Error> {
+        let object = self.store.get(&Path::from(key)).await?;
+        Ok(object.bytes().await?.to_vec())
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<(), Error> {
+        self.store.delete(&Path::from(key)).await?;
+        Ok(())
+    }
+}
+
+fn env_or(name: &str, default: &str) -> String {
+    std::env::var(name).unwrap_or_else(|_| default.to_string())
+}
+"#;
+
+/// `src/uploads.rs` for the backend: multipart parsing and the upload route
+const UPLOADS_MODULE: &str = r#"//! Multipart upload route storing each file in object storage.
+#![allow(dead_code)]
+
+use crate::storage::{self, Storage};
+use ripress::app::App;
+use ripress::types::RouterFns;
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Largest request body accepted, in bytes
+const MAX_UPLOAD_SIZE: u64 = 10 * 1024 * 1024;
+
+/// A file from a multipart body
+pub struct Part {
+    pub name: String,
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
+/// A stored file, as returned to the client
+#[derive(Debug, Serialize)]
+pub struct UploadedFile {
+    pub key: String,
+    pub name: String,
+    pub content_type: String,
+    pub size: usize,
+}
+
+/// The files in a `multipart/form-data` body; fields that aren't files are skipped
+pub async fn parse_multipart(content_type: &str, body: Vec<u8>) -> Result<Vec<Part>, multer::Error> {
+    let boundary = multer::parse_boundary(content_type)?;
+    let stream = futures_util::stream::once(async move {
+        Ok::<_, std::io::Error>(bytes::Bytes::from(body))
+    });
+    let constraints = multer::Constraints::new()
+        .size_limit(multer::SizeLimit::new().whole_stream(MAX_UPLOAD_SIZE));
+    let mut multipart = multer::Multipart::with_constraints(stream, boundary, constraints);
+
+    let mut parts = Vec::new();
+    while let Some(field) = multipart.next_field().await? {
+        let Some(name) = field.file_name().map(str::to_string) else {
+            continue;
+        };
+        let content_type = field
+            .content_type()
+            .map_or_else(|| "application/octet-stream".to_string(), |mime| mime.to_string());
+        let bytes = field.bytes().await?.to_vec();
+        parts.push(Part {
+            name,
+            content_type,
+            bytes,
+        });
+    }
+    Ok(parts)
+}
+
+/// Stores each part under a unique key
+pub async fn store(storage: &Storage, parts: Vec<Part>) -> Result<Vec<UploadedFile>, storage::Error> {
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos());
+    let mut uploaded = Vec::new();
+    for (i, part) in parts.into_iter().enumerate() {
+        let key = format!("{:x}-{}-{}", stamp, i, safe_file_name(&part.name));
+        let size = part.bytes.len();
+        storage.put(&key, part.bytes).await?;
+        uploaded.push(UploadedFile {
+            key,
+            name: part.name,
+            content_type: part.content_type,
+            size,
+        });
+    }
+    Ok(uploaded)
+}
+
+/// The file name with anything but letters, digits, `.`, `-` and `_` replaced
+fn safe_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+        .collect()
+}
+
+/// Registers `POST /api/uploads`, which takes `multipart/form-data` and answers with the
+/// stored files
+pub fn register(app: &mut App, storage: Storage) {
+    app.post("/api/uploads", move |req, res| {
+        let storage = storage.clone();
+        async move {
+            let content_type = req.headers.get("content-type").unwrap_or_default().to_string();
+            let body = match req.bytes() {
+                Ok(body) => body.to_vec(),
+                Err(err) => return res.bad_request().text(err.to_string()),
+            };
+            let parts = match parse_multipart(&content_type, body).await {
+                Ok(parts) => parts,
+                Err(err) => return res.bad_request().text(err.to_string()),
+            };
+            match store(&storage, parts).await {
+                Ok(files) => res.ok().json(files),
+                Err(err) => res.internal_server_error().text(err.to_string()),
+            }
+        }
+    });
+}
+"#;
+
+/// Storage client, upload route, S3 settings and an upload component per frontend for the
+/// `uploads` extra
+fn uploads_files(layout: &ProjectLayout) -> Vec<GeneratedFile> {
+    let src = layout.backend_dir.join("src");
+    let mut files = vec![
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: "object_store = { version = \"0.12\", features = [\"aws\"] }\nmulter = \"3\"\nbytes = \"1\"\nfutures-util = \"0.3\"\nserde = { version = \"1\", features = [\"derive\"] }\n".to_string(),
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        },
+        GeneratedFile {
+            path: src.join("storage.rs"),
+            contents: STORAGE_MODULE.to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: src.join("uploads.rs"),
+            contents: UPLOADS_MODULE.to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: src.join("main.rs"),
+            contents: "storage".to_string(),
+            mode: WriteMode::WireModule { init: None },
+        },
+        GeneratedFile {
+            path: src.join("main.rs"),
+            contents: "uploads".to_string(),
+            mode: WriteMode::WireModule { init: None },
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join(".env.example"),
+            contents: "S3_ENDPOINT=http://localhost:9000\nS3_BUCKET=uploads\nS3_REGION=us-east-1\nS3_ACCESS_KEY_ID=minioadmin\nS3_SECRET_ACCESS_KEY=minioadmin\n".to_string(),
+            mode: WriteMode::MergeEnv,
+        },
+    ];
+    for (dir, frontend) in &layout.frontends {
+        if let Some((path, contents)) = upload_component(frontend) {
+            files.push(GeneratedFile {
+                path: dir.join(path),
+                contents,
+                mode: WriteMode::Create,
+            });
+        }
+    }
+    files
+}
+
+/// An upload form posting to the backend's upload route, for frontends with components
+fn upload_component(frontend: &str) -> Option<(&'static str, String)> {
+    let endpoint = format!("http://localhost:{}/api/uploads", BACKEND_PORT);
+    match frontend {
+        "react" | "nextjs" => Some((
+            "src/components/Upload.tsx",
+            format!(
+                r#"{}import {{ useState, type FormEvent }} from "react";
+
+type UploadedFile = {{ key: string; name: string; content_type: string; size: number }};
+
+export default function Upload() {{
+  const [uploaded, setUploaded] = useState<UploadedFile[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  async function onSubmit(event: FormEvent<HTMLFormElement>) {{
+    event.preventDefault();
+    setError(null);
+    const response = await fetch("{}", {{
+      method: "POST",
+      body: new FormData(event.currentTarget),
+    }});
+    if (response.ok) {{
+      setUploaded(await response.json());
+    }} else {{
+      setError(await response.text());
+    }}
+  }}
+
+  return (
+    <form onSubmit={{onSubmit}}>
+      <input type="file" name="file" multiple required />
+      <button type="submit">Upload</button>
+      {{error && <p role="alert">{{error}}</p>}}
+      <ul>
+        {{uploaded.map((file) => (
+          <li key={{file.key}}>
+            {{file.name}} ({{file.size}} bytes)
+          </li>
+        ))}}
+      </ul>
+    </form>
+  );
+}}
+"#,
+                if frontend == "nextjs" {
+                    "\"use client\";\n\n"
+                } else {
+                    ""
+                },
+                endpoint
+            ),
+        )),
+        "solid" => Some((
+            "src/components/Upload.tsx",
+            format!(
+                r#"import {{ createSignal, For, Show }} from "solid-js";
+
+type UploadedFile = {{ key: string; name: string; content_type: string; size: number }};
+
+export default function Upload() {{
+  const [uploaded, setUploaded] = createSignal<UploadedFile[]>([]);
+  const [error, setError] = createSignal<string | null>(null);
+
+  async function onSubmit(event: SubmitEvent) {{
+    event.preventDefault();
+    setError(null);
+    const response = await fetch("{}", {{
+      method: "POST",
+      body: new FormData(event.currentTarget as HTMLFormElement),
+    }});
+    if (response.ok) {{
+      setUploaded(await response.json());
+    }} else {{
+      setError(await response.text());
+    }}
+  }}
+
+  return (
+    <form onSubmit={{onSubmit}}>
+      <input type="file" name="file" multiple required />
+      <button type="submit">Upload</button>
+      <Show when={{error()}}>
+        <p role="alert">{{error()}}</p>
+      </Show>
+      <ul>
+        <For each={{uploaded()}}>
+          {{(file) => (
+            <li>
+              {{file.name}} ({{file.size}} bytes)
+            </li>
+          )}}
+        </For>
+      </ul>
+    </form>
+  );
+}}
+"#,
+                endpoint
+            ),
+        )),
+        "svelte" | "sveltekit" => Some((
+            "src/lib/Upload.svelte",
+            format!(
+                r#"<script lang="ts">
+  type UploadedFile = {{ key: string; name: string; content_type: string; size: number }};
+
+  let uploaded: UploadedFile[] = [];
+  let error: string | null = null;
+
+  async function onSubmit(event: SubmitEvent) {{
+    error = null;
+    const response = await fetch("{}", {{
+      method: "POST",
+      body: new FormData(event.currentTarget as HTMLFormElement),
+    }});
+    if (response.ok) {{
+      uploaded = await response.json();
+    }} else {{
+      error = await response.text();
+    }}
+  }}
+</script>
+
+<form on:submit|preventDefault={{onSubmit}}>
+  <input type="file" name="file" multiple required />
+  <button type="submit">Upload</button>
+  {{#if error}}
+    <p role="alert">{{error}}</p>
+  {{/if}}
+  <ul>
+    {{#each uploaded as file (file.key)}}
+      <li>{{file.name}} ({{file.size}} bytes)</li>
+    {{/each}}
+  </ul>
+</form>
+"#,
+                endpoint
+            ),
+        )),
+        "vanilla" => Some((
+            "src/upload.ts",
+            format!(
+                r#"type UploadedFile = {{ key: string; name: string; content_type: string; size: number }};
+
+/** Uploads the files picked in `form` and lists them below it */
+export function setupUpload(form: HTMLFormElement) {{
+  const list = document.createElement("ul");
+  form.after(list);
+
+  form.addEventListener("submit", async (event) => {{
+    event.preventDefault();
+    const response = await fetch("{}", {{
+      method: "POST",
+      body: new FormData(form),
+    }});
+    if (!response.ok) {{
+      list.textContent = await response.text();
+      return;
+    }}
+    const files: UploadedFile[] = await response.json();
+    list.replaceChildren(
+      ...files.map((file) => {{
+        const item = document.createElement("li");
+        item.textContent = `${{file.name}} (${{file.size}} bytes)`;
+        return item;
+      }}),
+    );
+  }});
+}}
+"#,
+                endpoint
+            ),
+        )),
+        _ => None,
+    }
+}
+
+/// `src/admin.rs` for the backend: the admin API over users, gated by `ADMIN_TOKEN`
+const ADMIN_MODULE: &str = r#"//! Admin API under `/api/admin`: create, list, update and delete users. Every request needs
+//! an `Authorization: Bearer <ADMIN_TOKEN>` header. Users are kept in memory; back `Users`
+//! with your lume models to persist them.
+#![allow(dead_code)]
+
+use ripress::app::App;
+use ripress::context::HttpRequest;
+use ripress::types::RouterFns;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct User {
+    pub id: u64,
+    pub name: String,
+    pub email: String,
+    pub role: String,
+}
+
+/// Body of the create and update requests
+#[derive(Debug, Deserialize)]
+pub struct UserInput {
+    pub name: String,
+    pub email: String,
+    pub role: String,
+}
+
+/// The users the admin area manages. Cloning is cheap; clones share the same list.
+#[derive(Clone, Default)]
+pub struct Users {
+    inner: Arc<Mutex<(u64, Vec<User>)>>,
+}
+
+impl Users {
+    pub fn list(&self) -> Vec<User> {
+        self.inner.lock().unwrap().1.clone()
+    }
+
+    pub fn create(&self, input: UserInput) -> User {
+        let mut inner = self.inner.lock().unwrap();
+        inner.0 += 1;
+        let user = User {
+            id: inner.0,
+            name: input.name,
+            email: input.email,
+            role: input.role,
+        };
+        inner.1.push(user.clone());
+        user
+    }
+
+    pub fn update(&self, id: u64, input: UserInput) -> Option<User> {
+        let mut inner = self.inner.lock().unwrap();
+        let user = inner.1.iter_mut().find(|user| user.id == id)?;
+        user.name = input.name;
+        user.email = input.email;
+        user.role = input.role;
+        Some(user.clone())
+    }
+
+    pub fn delete(&self, id: u64) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let before = inner.1.len();
+        inner.1.retain(|user| user.id != id);
+        inner.1.len() != before
+    }
+}
+
+/// Whether the request carries the admin token; without `ADMIN_TOKEN` nobody is let in
+fn authorized(req: &HttpRequest) -> bool {
+    let token = std::env::var("ADMIN_TOKEN").unwrap_or_default();
+    !token.is_empty()
+        && req.headers.get("authorization") == Some(format!("Bearer {}", token).as_str())
+}
+
+fn user_id(req: &HttpRequest) -> Option<u64> {
+    req.params.get("id").and_then(|id| id.parse().ok())
+}
+
+/// Registers the token check and the `/api/admin/users` routes
+pub fn register(app: &mut App, users: Users) {
+    app.use_pre_middleware("/api/admin", |req, res| async move {
+        if authorized(&req) {
+            (req, None)
+        } else {
+            let res = res.status(401).text("Unauthorized");
+            (req, Some(res))
+        }
+    });
+
+    let list = users.clone();
+    app.get("/api/admin/users", move |_req, res| {
+        let users = list.clone();
+        async move { res.ok().json(users.list()) }
+    });
+
+    let create = users.clone();
+    app.post("/api/admin/users", move |req, res| {
+        let users = create.clone();
+        async move {
+            match req.json::<UserInput>() {
+                Ok(input) => res.ok().json(users.create(input)),
+                Err(err) => res.bad_request().text(err.to_string()),
+            }
+        }
+    });
+
+    let update = users.clone();
+    app.put("/api/admin/users/:id", move |req, res| {
+        let users = update.clone();
+        async move {
+            let (Some(id), Ok(input)) = (user_id(&req), req.json::<UserInput>()) else {
+                return res
+                    .bad_request()
+                    .text("Expected a user id and name, email and role");
+            };
+            match users.update(id, input) {
+                Some(user) => res.ok().json(user),
+                None => res.status(404).text("No such user"),
+            }
+        }
+    });
+
+    app.delete("/api/admin/users/:id", move |req, res| {
+        let users = users.clone();
+        async move {
+            match user_id(&req) {
+                Some(id) if users.delete(id) => res.ok().text("Deleted"),
+                _ => res.status(404).text("No such user"),
+            }
+        }
+    });
+}
+"#;
+
+/// React admin page listing users with forms to add, edit and delete them
+const ADMIN_REACT: &str = r#"import { useEffect, useState, type FormEvent } from "react";
+import { adminApi, type User, type UserInput } from "../admin/api";
+
+const EMPTY: UserInput = { name: "", email: "", role: "user" };
+
+export default function Admin() {
+  const [token, setToken] = useState(() => localStorage.getItem("adminToken") ?? "");
+  const [users, setUsers] = useState<User[]>([]);
+  const [form, setForm] = useState<UserInput>(EMPTY);
+  const [editing, setEditing] = useState<number | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  async function run(action: () => Promise<unknown>) {
+    setError(null);
+    try {
+      await action();
+      setUsers(await adminApi(token).list());
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  useEffect(() => {
+    localStorage.setItem("adminToken", token);
+    if (token) run(async () => {});
+  }, [token]);
+
+  function onSubmit(event: FormEvent) {
+    event.preventDefault();
+    const api = adminApi(token);
+    run(() => (editing === null ? api.create(form) : api.update(editing, form)));
+    setForm(EMPTY);
+    setEditing(null);
+  }
+
+  return (
+    <section>
+      <h1>Admin</h1>
+      <label>
+        Admin token <input type="password" value={token} onChange={(e) => setToken(e.target.value)} />
+      </label>
+      {error && <p role="alert">{error}</p>}
+      <table>
+        <thead>
+          <tr><th>Name</th><th>Email</th><th>Role</th><th /></tr>
+        </thead>
+        <tbody>
+          {users.map((user) => (
+            <tr key={user.id}>
+              <td>{user.name}</td>
+              <td>{user.email}</td>
+              <td>{user.role}</td>
+              <td>
+                <button onClick={() => { setEditing(user.id); setForm(user); }}>Edit</button>
+                <button onClick={() => run(() => adminApi(token).remove(user.id))}>Delete</button>
+              </td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+      <form onSubmit={onSubmit}>
+        <input placeholder="Name" value={form.name} onChange={(e) => setForm({ ...form, name: e.target.value })} required />
+        <input placeholder="Email" type="email" value={form.email} onChange={(e) => setForm({ ...form, email: e.target.value })} required />
+        <input placeholder="Role" value={form.role} onChange={(e) => setForm({ ...form, role: e.target.value })} required />
+        <button type="submit">{editing === null ? "Add user" : "Save"}</button>
+      </form>
+    </section>
+  );
+}
+"#;
+
+/// Svelte admin page listing users with forms to add, edit and delete them
+const ADMIN_SVELTE: &str = r#"<script lang="ts">
+  import { onMount } from "svelte";
+  import { adminApi, type User, type UserInput } from "../admin/api";
+
+  const EMPTY: UserInput = { name: "", email: "", role: "user" };
+
+  let token = "";
+  let users: User[] = [];
+  let form: UserInput = { ...EMPTY };
+  let editing: number | null = null;
+  let error: string | null = null;
+
+  async function run(action: () => Promise<unknown>) {
+    error = null;
+    try {
+      await action();
+      users = await adminApi(token).list();
+    } catch (err) {
+      error = String(err);
+    }
+  }
+
+  function saveToken() {
+    localStorage.setItem("adminToken", token);
+    if (token) run(async () => {});
+  }
+
+  function onSubmit() {
+    const api = adminApi(token);
+    const input = form;
+    const id = editing;
+    run(() => (id === null ? api.create(input) : api.update(id, input)));
+    form = { ...EMPTY };
+    editing = null;
+  }
+
+  onMount(() => {
+    token = localStorage.getItem("adminToken") ?? "";
+    saveToken();
+  });
+</script>
+
+<section>
+  <h1>Admin</h1>
+  <label>
+    Admin token <input type="password" bind:value={token} on:change={saveToken} />
+  </label>
+  {#if error}
+    <p role="alert">{error}</p>
+  {/if}
+  <table>
+    <thead>
+      <tr><th>Name</th><th>Email</th><th>Role</th><th></th></tr>
+    </thead>
+    <tbody>
+      {#each users as user (user.id)}
+        <tr>
+          <td>{user.name}</td>
+          <td>{user.email}</td>
+          <td>{user.role}</td>
+          <td>
+            <button on:click={() => { editing = user.id; form = { ...user }; }}>Edit</button>
+            <button on:click={() => run(() => adminApi(token).remove(user.id))}>Delete</button>
+          </td>
+        </tr>
+      {/each}
+    </tbody>
+  </table>
+  <form on:submit|preventDefault={onSubmit}>
+    <input placeholder="Name" bind:value={form.name} required />
+    <input placeholder="Email" type="email" bind:value={form.email} required />
+    <input placeholder="Role" bind:value={form.role} required />
+    <button type="submit">{editing === null ? "Add user" : "Save"}</button>
+  </form>
+</section>
+"#;
+
+/// `src/admin/api.ts` for a Node frontend: typed calls to the admin API
+fn admin_client() -> String {
+    format!(
+        r#"export type User = {{ id: number; name: string; email: string; role: string }};
+export type UserInput = Omit<User, "id">;
+
+const BASE_URL = "http://localhost:{}/api/admin/users";
+
+/** Admin API calls authenticated with `token`, the backend's `ADMIN_TOKEN` */
+export function adminApi(token: string) {{
+  async function request<T>(path: string, init: RequestInit = {{}}): Promise<T> {{
+    const response = await fetch(BASE_URL + path, {{
+      ...init,
+      headers: {{
+        Authorization: `Bearer ${{token}}`,
+        "Content-Type": "application/json",
+      }},
+    }});
+    if (!response.ok) {{
+      throw new Error(await response.text());
+    }}
+    const type = response.headers.get("content-type") ?? "";
+    return (type.includes("json") ? response.json() : response.text()) as Promise<T>;
+  }}
+
+  return {{
+    list: () => request<User[]>(""),
+    create: (user: UserInput) =>
+      request<User>("", {{ method: "POST", body: JSON.stringify(user) }}),
+    update: (id: number, user: UserInput) =>
+      request<User>(`/${{id}}`, {{ method: "PUT", body: JSON.stringify(user) }}),
+    remove: (id: number) => request<string>(`/${{id}}`, {{ method: "DELETE" }}),
+  }};
+}}
+"#,
+        BACKEND_PORT
+    )
+}
+
+/// Admin API, its token and an admin page per frontend for the `admin` extra
+fn admin_files(layout: &ProjectLayout) -> Vec<GeneratedFile> {
+    let src = layout.backend_dir.join("src");
+    let mut files = vec![
+        GeneratedFile {
+            path: layout.backend_dir.join("Cargo.toml"),
+            contents: "serde = { version = \"1\", features = [\"derive\"] }\n".to_string(),
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        },
+        GeneratedFile {
+            path: src.join("admin.rs"),
+            contents: ADMIN_MODULE.to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: src.join("main.rs"),
+            contents: "admin".to_string(),
+            mode: WriteMode::WireModule { init: None },
+        },
+        GeneratedFile {
+            path: layout.backend_dir.join(".env.example"),
+            contents: "ADMIN_TOKEN=change-me-admin\n".to_string(),
+            mode: WriteMode::MergeEnv,
+        },
+    ];
+    for (dir, frontend) in &layout.frontends {
+        if !NODE_FRONTENDS.contains(frontend) {
+            continue;
+        }
+        files.push(GeneratedFile {
+            path: dir.join("src/admin/api.ts"),
+            contents: admin_client(),
+            mode: WriteMode::Create,
+        });
+        let page = match *frontend {
+            "react" => Some(("src/components/Admin.tsx", ADMIN_REACT.to_string())),
+            "nextjs" => Some((
+                "src/components/Admin.tsx",
+                format!("\"use client\";\n\n{}", ADMIN_REACT),
+            )),
+            "svelte" | "sveltekit" => Some(("src/lib/Admin.svelte", ADMIN_SVELTE.to_string())),
+            _ => None,
+        };
+        if let Some((path, contents)) = page {
+            files.push(GeneratedFile {
+                path: dir.join(path),
+                contents,
+                mode: WriteMode::Create,
+            });
+        }
+    }
+    files
+}
+
+fn compose_path(dir: &Path) -> String {
+    if dir.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        format!("./{}", dir.display())
+    }
+}
+
+/// Directory of the generated Helm chart
+pub const HELM_CHART_DIR: &str = "deploy/helm";
+
+/// Deployment and Service for the backend, exposing gRPC too when `backend.grpcPort` is set
+const HELM_BACKEND: &str = r#"apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: {{ .Release.Name }}-backend
+  labels:
+    app.kubernetes.io/name: backend
+    app.kubernetes.io/instance: {{ .Release.Name }}
+spec:
+  replicas: {{ .Values.backend.replicas }}
+  selector:
+    matchLabels:
+      app.kubernetes.io/name: backend
+      app.kubernetes.io/instance: {{ .Release.Name }}
+  template:
+    metadata:
+      labels:
+        app.kubernetes.io/name: backend
+        app.kubernetes.io/instance: {{ .Release.Name }}
+      annotations:
+        # Restart the pods when the environment changes
+        checksum/env: {{ include (print $.Template.BasePath "/secret.yaml") . | sha256sum }}
+    spec:
+      containers:
+        - name: backend
+          image: "{{ .Values.backend.image.repository }}:{{ .Values.backend.image.tag }}"
+          ports:
+            - name: http
+              containerPort: {{ .Values.backend.port }}
+            {{- with .Values.backend.grpcPort }}
+            - name: grpc
+              containerPort: {{ . }}
+            {{- end }}
+          envFrom:
+            - secretRef:
+                name: {{ .Release.Name }}-backend
+          readinessProbe:
+            tcpSocket:
+              port: http
+---
+apiVersion: v1
+kind: Service
+metadata:
+  name: {{ .Release.Name }}-backend
+spec:
+  selector:
+    app.kubernetes.io/name: backend
+    app.kubernetes.io/instance: {{ .Release.Name }}
+  ports:
+    - name: http
+      port: {{ .Values.backend.port }}
+      targetPort: http
+    {{- with .Values.backend.grpcPort }}
+    - name: grpc
+      port: {{ . }}
+      targetPort: grpc
+    {{- end }}
+"#;
+
+/// The backend's environment, from `backend.env` in the values
+const HELM_SECRET: &str = r#"apiVersion: v1
+kind: Secret
+metadata:
+  name: {{ .Release.Name }}-backend
+type: Opaque
+stringData:
+  {{- range $key, $value := .Values.backend.env }}
+  {{ $key }}: {{ $value | quote }}
+  {{- end }}
+"#;
+
+/// The jobs worker, running the backend image's `worker` binary
+const HELM_WORKER: &str = r#"{{- if .Values.worker.enabled }}
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: {{ .Release.Name }}-worker
+  labels:
+    app.kubernetes.io/name: worker
+    app.kubernetes.io/instance: {{ .Release.Name }}
+spec:
+  replicas: {{ .Values.worker.replicas }}
+  selector:
+    matchLabels:
+      app.kubernetes.io/name: worker
+      app.kubernetes.io/instance: {{ .Release.Name }}
+  template:
+    metadata:
+      labels:
+        app.kubernetes.io/name: worker
+        app.kubernetes.io/instance: {{ .Release.Name }}
+    spec:
+      containers:
+        - name: worker
+          image: "{{ .Values.backend.image.repository }}:{{ .Values.backend.image.tag }}"
+          command: ["worker"]
+          envFrom:
+            - secretRef:
+                name: {{ .Release.Name }}-backend
+{{- end }}
+"#;
+
+/// A Deployment and Service for every entry of `frontends`
+const HELM_FRONTENDS: &str = r#"{{- range .Values.frontends }}
+---
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: {{ $.Release.Name }}-{{ .name }}
+  labels:
+    app.kubernetes.io/name: {{ .name }}
+    app.kubernetes.io/instance: {{ $.Release.Name }}
+spec:
+  replicas: {{ .replicas }}
+  selector:
+    matchLabels:
+      app.kubernetes.io/name: {{ .name }}
+      app.kubernetes.io/instance: {{ $.Release.Name }}
+  template:
+    metadata:
+      labels:
+        app.kubernetes.io/name: {{ .name }}
+        app.kubernetes.io/instance: {{ $.Release.Name }}
+    spec:
+      containers:
+        - name: {{ .name }}
+          image: "{{ .image.repository }}:{{ .image.tag }}"
+          ports:
+            - name: http
+              containerPort: {{ .port }}
+---
+apiVersion: v1
+kind: Service
+metadata:
+  name: {{ $.Release.Name }}-{{ .name }}
+spec:
+  selector:
+    app.kubernetes.io/name: {{ .name }}
+    app.kubernetes.io/instance: {{ $.Release.Name }}
+  ports:
+    - name: http
+      port: {{ .port }}
+      targetPort: http
+{{- end }}
+"#;
+
+/// Routes `ingress.paths` on `ingress.host` to the chart's services
+const HELM_INGRESS: &str = r#"{{- if .Values.ingress.enabled }}
+apiVersion: networking.k8s.io/v1
+kind: Ingress
+metadata:
+  name: {{ .Release.Name }}
+spec:
+  {{- with .Values.ingress.className }}
+  ingressClassName: {{ . }}
+  {{- end }}
+  rules:
+    - host: {{ .Values.ingress.host | quote }}
+      http:
+        paths:
+          {{- range .Values.ingress.paths }}
+          - path: {{ .path }}
+            pathType: Prefix
+            backend:
+              service:
+                name: {{ $.Release.Name }}-{{ .service }}
+                port:
+                  name: http
+          {{- end }}
+{{- end }}
+"#;
+
+/// `values.yaml` for the chart: images named like the ones `docker compose build` tags, the
+/// backend's environment and the ingress routes
+fn helm_values(layout: &ProjectLayout, extras: &Extras) -> String {
+    let has = |component: &str| layout.components.iter().any(|c| c == component);
+    let mut env = Vec::new();
+    if extras.database.is_some() {
+        env.push("DATABASE_URL");
+    }
+    if has("redis") {
+        env.push("REDIS_URL");
+    }
+    if extras.email {
+        env.push("SMTP_HOST");
+    }
+    if extras.uploads && has("ripress") {
+        env.push("S3_ENDPOINT");
+    }
+    if extras.otel {
+        env.push("OTEL_EXPORTER_OTLP_ENDPOINT");
+    }
+    let env = if env.is_empty() {
+        " {}\n".to_string()
+    } else {
+        env.iter()
+            .map(|key| format!("\n    {}: \"\"", key))
+            .collect::<String>()
+            + "\n"
+    };
+    let grpc = if has("grpc") {
+        format!("  grpcPort: {}\n", GRPC_PORT)
+    } else {
+        String::new()
+    };
+
+    let frontends: Vec<(String, u16)> = layout
+        .frontends
+        .iter()
+        .filter_map(|(dir, frontend)| {
+            let (_, port) = frontend_dockerfile(frontend)?;
+            let name = dir.file_name()?.to_string_lossy().into_owned();
+            Some((name, port))
+        })
+        .collect();
+    let frontend_values = if frontends.is_empty() {
+        " []\n".to_string()
+    } else {
+        frontends
+            .iter()
+            .map(|(name, port)| {
+                format!(
+                    "\n  - name: {name}\n    image:\n      repository: {project}-{name}\n      tag: latest\n    port: {port}\n    replicas: 1",
+                    name = name,
+                    project = layout.name,
+                    port = port
+                )
+            })
+            .collect::<String>()
+            + "\n"
+    };
+    // The first frontend serves the site and the backend answers under /api
+    let paths = match frontends.first() {
+        Some((name, _)) => format!(
+            "    - path: /api\n      service: backend\n    - path: /\n      service: {}\n",
+            name
+        ),
+        None => "    - path: /\n      service: backend\n".to_string(),
+    };
+
+    format!(
+        r#"# Images are named like the ones `docker compose build` tags; push them to a registry
+# your cluster can pull from and point the repositories there.
+
+backend:
+  image:
+    repository: {name}-backend
+    tag: latest
+  replicas: 1
+  port: {port}
+{grpc}  # Set these for your cluster, e.g. `--set backend.env.DATABASE_URL=...`
+  env:{env}
+worker:
+  enabled: {worker}
+  replicas: 1
+
+frontends:{frontends}
+ingress:
+  enabled: true
+  className: nginx
+  host: {name}.local
+  paths:
+{paths}"#,
+        name = layout.name,
+        port = BACKEND_PORT,
+        grpc = grpc,
+        env = env,
+        worker = has("jobs") && has("redis"),
+        frontends = frontend_values,
+        paths = paths
+    )
+}
+
+/// A minimal Helm chart in `deploy/helm` deploying the backend, the worker and the frontends
+/// built by the docker extra, with an ingress in front
+fn helm_files(layout: &ProjectLayout, extras: &Extras) -> Vec<GeneratedFile> {
+    let chart = PathBuf::from(HELM_CHART_DIR);
+    let template = |name: &str, contents: &str| GeneratedFile {
+        path: chart.join("templates").join(name),
+        contents: contents.to_string(),
+        mode: WriteMode::Create,
+    };
+
+    vec![
+        GeneratedFile {
+            path: chart.join("Chart.yaml"),
+            contents: format!(
+                "apiVersion: v2\nname: {}\ndescription: Deploys {} to Kubernetes\ntype: application\nversion: 0.1.0\nappVersion: \"0.1.0\"\n",
+                layout.name, layout.name
+            ),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: chart.join("values.yaml"),
+            contents: helm_values(layout, extras),
+            mode: WriteMode::Create,
+        },
+        template("backend.yaml", HELM_BACKEND),
+        template("secret.yaml", HELM_SECRET),
+        template("worker.yaml", HELM_WORKER),
+        template("frontends.yaml", HELM_FRONTENDS),
+        template("ingress.yaml", HELM_INGRESS),
+    ]
+}
+
+/// Multi-stage build of the backend crate. The binary name isn't known up front, so the
+/// executable is picked out of `target/release`.
+fn backend_dockerfile(server_rendered: bool, worker: bool, grpc: bool) -> String {
+    let (protoc, ports) = if grpc {
+        (
+            "RUN apt-get update && apt-get install -y --no-install-recommends protobuf-compiler\n",
+            format!("{} {}", BACKEND_PORT, GRPC_PORT),
+        )
+    } else {
+        ("", BACKEND_PORT.to_string())
+    };
+    let mut assets = String::new();
+    if server_rendered {
+        assets.push_str(
+            "COPY --from=build /app/templates ./templates\nCOPY --from=build /app/static ./static\n",
+        );
+    }
+    if worker {
+        assets.push_str("COPY --from=build /app/target/release/worker /usr/local/bin/worker\n");
+    }
+
+    format!(
+        r#"FROM rust:1 AS build
+{}WORKDIR /app
+COPY . .
+RUN cargo build --release \
+    && find target/release -maxdepth 1 -type f -perm -u+x ! -name worker -exec cp {{}} /app/server \;
+
+FROM debian:bookworm-slim
+RUN apt-get update \
+    && apt-get install -y --no-install-recommends ca-certificates \
+    && rm -rf /var/lib/apt/lists/*
+WORKDIR /app
+COPY --from=build /app/server /usr/local/bin/server
+{}EXPOSE {}
+CMD ["server"]
+"#,
+        protoc, assets, ports
+    )
+}
+
+/// Dockerfile for a frontend and the port it serves on, if it can run in its own container.
+/// Leptos and Dioxus build their own servers and aren't covered yet.
+fn frontend_dockerfile(frontend: &str) -> Option<(String, u16)> {
+    const NPM_BUILD: &str =
+        "WORKDIR /app\nCOPY package*.json ./\nRUN npm install\nCOPY . .\nRUN npm run build\n";
+    let static_site = |build: String| {
+        format!(
+            "{}\nFROM nginx:alpine\nCOPY --from=build /app/dist /usr/share/nginx/html\nEXPOSE 80\n",
+            build
+        )
+    };
+
+    match frontend {
+        "react" | "svelte" | "solid" | "astro" | "vanilla" => Some((
+            static_site(format!("FROM node:22-alpine AS build\n{}", NPM_BUILD)),
+            80,
+        )),
+        "yew" => Some((
+            static_site(
+                "FROM rust:1 AS build\nRUN rustup target add wasm32-unknown-unknown && cargo install trunk --locked\nWORKDIR /app\nCOPY . .\nRUN trunk build --release\n".to_string(),
+            ),
+            80,
+        )),
+        "nextjs" => Some((
+            format!(
+                "FROM node:22-alpine\n{}ENV PORT=3000\nEXPOSE 3000\nCMD [\"npm\", \"start\"]\n",
+                NPM_BUILD
+            ),
+            3000,
+        )),
+        "sveltekit" => Some((
+            format!(
+                "FROM node:22-alpine\n{}EXPOSE 3000\nCMD [\"npm\", \"run\", \"preview\", \"--\", \"--host\", \"0.0.0.0\", \"--port\", \"3000\"]\n",
+                NPM_BUILD
+            ),
+            3000,
+        )),
+        _ => None,
+    }
+}
+
+/// Writes generated files into the project, returning the paths that were skipped because the
+/// template already has them
+pub fn write_files(workspace: &Workspace, files: &[GeneratedFile]) -> Result<Vec<PathBuf>> {
+    let mut skipped = Vec::new();
+
+    for file in files {
+        let contents = match (file.mode, workspace.read_to_string(&file.path)) {
+            (WriteMode::Overwrite, _) => file.contents.clone(),
+            (
+                WriteMode::Create
+                | WriteMode::Executable
+                | WriteMode::MergeEnv
+                | WriteMode::AppendLines
+                | WriteMode::AppendSection,
+                None,
+            ) => file.contents.clone(),
+            (WriteMode::AppendLines, Some(existing)) => append_lines(&existing, &file.contents),
+            (WriteMode::AppendSection, Some(existing)) => {
+                let heading = file.contents.lines().next().unwrap_or_default();
+                if existing.lines().any(|line| line.trim() == heading.trim()) {
+                    skipped.push(file.path.clone());
+                    continue;
+                }
+                format!("{}\n\n{}", existing.trim_end(), file.contents)
+            }
+            (WriteMode::MergeEnv, Some(existing)) => merge_env(&existing, &file.contents),
+            (WriteMode::Dependencies { table }, Some(existing)) => {
+                merge_dependencies(&existing, table, &file.contents)
+            }
+            (WriteMode::Workspace, existing) => {
+                match workspace_manifest(existing.as_deref(), &file.contents) {
+                    Some(manifest) => manifest,
+                    None => {
+                        skipped.push(file.path.clone());
+                        continue;
+                    }
+                }
+            }
+            (WriteMode::DefaultRun, Some(existing)) => match package_name(&existing) {
+                Some(name) => merge_dependencies(
+                    &existing,
+                    "package",
+                    &format!("default-run = \"{}\"\n", name),
+                ),
+                None => {
+                    skipped.push(file.path.clone());
+                    continue;
+                }
+            },
+            (WriteMode::WireModule { init }, Some(existing)) => {
+                match wire_module(&existing, &file.contents, init) {
+                    Some(wired) => wired,
+                    None => {
+                        skipped.push(file.path.clone());
+                        continue;
+                    }
+                }
+            }
+            (WriteMode::Prepend, Some(existing)) if !existing.starts_with(&file.contents) => {
+                format!("{}\n{}", file.contents, existing)
+            }
+            (WriteMode::MergePackageJson, Some(existing)) => {
+                merge_package_json(&existing, &file.contents)
+                    .with_context(|| format!("Failed to update '{}'", file.path.display()))?
+            }
+            (WriteMode::Create | WriteMode::Executable | WriteMode::Prepend, Some(_))
+            | (
+                WriteMode::Dependencies { .. }
+                | WriteMode::DefaultRun
+                | WriteMode::WireModule { .. }
+                | WriteMode::Prepend
+                | WriteMode::MergePackageJson,
+                None,
+            ) => {
+                skipped.push(file.path.clone());
+                continue;
+            }
+        };
+
+        workspace.write(&file.path, contents)?;
+        if file.mode == WriteMode::Executable {
+            workspace.set_executable(&file.path)?;
+        }
+    }
+
+    Ok(skipped)
+}
+
+/// Appends the `KEY=value` lines of `additions` whose keys `existing` doesn't define
+pub fn merge_env(existing: &str, additions: &str) -> String {
+    let defined: Vec<&str> = existing
+        .lines()
+        .filter_map(|line| line.split_once('=').map(|(key, _)| key.trim()))
+        .collect();
+
+    let mut merged = existing.to_string();
+    for line in additions.lines() {
+        let Some((key, _)) = line.split_once('=') else {
+            continue;
+        };
+        if defined.contains(&key.trim()) {
+            continue;
+        }
+        if !merged.is_empty() && !merged.ends_with('\n') {
+            merged.push('\n');
+        }
+        merged.push_str(line);
+        merged.push('\n');
+    }
+    merged
+}
+
+/// Adds the `name = ...` lines of `additions` that `existing` doesn't list to its `[table]`,
+/// creating the table when needed
+pub fn merge_dependencies(existing: &str, table: &str, additions: &str) -> String {
+    let header_line = format!("[{}]", table);
+    let mut lines: Vec<String> = existing.lines().map(str::to_string).collect();
+    let header = lines.iter().position(|line| line.trim() == header_line);
+    let (start, mut end) = match header {
+        Some(header) => {
+            let end = lines[header + 1..]
+                .iter()
+                .position(|line| line.trim_start().starts_with('['))
+                .map_or(lines.len(), |offset| header + 1 + offset);
+            (header + 1, end)
+        }
+        None => {
+            if lines.last().is_some_and(|line| !line.is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push(header_line);
+            (lines.len(), lines.len())
+        }
+    };
+
+    // Insert after the last dependency rather than after trailing blank lines
+    while end > start && lines[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+
+    for addition in additions.lines() {
+        let Some((name, _)) = addition.split_once('=') else {
+            continue;
+        };
+        let listed = lines[start..end].iter().any(|line| {
+            line.split_once('=')
+                .is_some_and(|(key, _)| key.trim() == name.trim())
+        });
+        if !listed {
+            lines.insert(end, addition.to_string());
+            end += 1;
+        }
+    }
+
+    let mut merged = lines.join("\n");
+    merged.push('\n');
+    merged
+}
+
+/// Adds `mod <module>;` to a main.rs and calls `init` at the start of `main`. Returns `None`
+/// when the module is already declared or `main` can't be found.
+pub fn wire_module(main_rs: &str, module: &str, init: Option<&str>) -> Option<String> {
+    let declaration = format!("mod {};", module.trim());
+    if main_rs.lines().any(|line| line.trim() == declaration) {
+        return None;
+    }
+
+    let mut lines: Vec<&str> = main_rs.lines().collect();
+    let main = lines
+        .iter()
+        .position(|line| line.contains("fn main(") && line.trim_end().ends_with('{'))?;
+    let call = init.map(|init| format!("    {}", init));
+    if let Some(call) = &call {
+        lines.insert(main + 1, call);
+    }
+
+    let mut wired = format!("{}\n", declaration);
+    if !lines.first().is_some_and(|line| line.starts_with("mod ")) {
+        wired.push('\n');
+    }
+    wired.push_str(&lines.join("\n"));
+    wired.push('\n');
+    Some(wired)
+}
+
+/// Appends the lines of `additions` that `existing` doesn't contain
+pub fn append_lines(existing: &str, additions: &str) -> String {
+    let mut merged = existing.to_string();
+    for line in additions.lines() {
+        if existing.lines().any(|l| l.trim() == line.trim()) {
+            continue;
+        }
+        if !merged.is_empty() && !merged.ends_with('\n') {
+            merged.push('\n');
+        }
+        merged.push_str(line);
+        merged.push('\n');
+    }
+    merged
+}
+
+/// Adds the entries of every object in `additions` to the same object of the package.json in
+/// `existing`, without touching entries it already has
+pub fn merge_package_json(existing: &str, additions: &str) -> Result<String> {
+    let mut package: serde_json::Value =
+        serde_json::from_str(existing).context("Invalid package.json")?;
+    let additions: serde_json::Value = serde_json::from_str(additions)?;
+
+    let (Some(package_object), Some(additions)) = (package.as_object_mut(), additions.as_object())
+    else {
+        anyhow::bail!("package.json is not an object");
+    };
+    for (key, entries) in additions {
+        let target = package_object
+            .entry(key.clone())
+            .or_insert_with(|| serde_json::json!({}));
+        if let (Some(target), Some(entries)) = (target.as_object_mut(), entries.as_object()) {
+            for (name, value) in entries {
+                target.entry(name.clone()).or_insert_with(|| value.clone());
+            }
+        }
+    }
+
+    let mut merged = serde_json::to_string_pretty(&package)?;
+    merged.push('\n');
+    Ok(merged)
+}
+
+/// The `[package]` name of a Cargo.toml
+fn package_name(cargo_toml: &str) -> Option<String> {
+    let manifest: toml::Table = toml::from_str(cargo_toml).ok()?;
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
@@ -0,0 +1,138 @@
+/// Git forges whose shorthands, browse URLs and archive downloads hexstack understands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Host {
+    GitHub,
+    GitLab,
+    Bitbucket,
+    Codeberg,
+}
+
+impl Host {
+    pub const ALL: [Host; 4] = [Host::GitHub, Host::GitLab, Host::Bitbucket, Host::Codeberg];
+
+    pub fn domain(self) -> &'static str {
+        match self {
+            Host::GitHub => "github.com",
+            Host::GitLab => "gitlab.com",
+            Host::Bitbucket => "bitbucket.org",
+            Host::Codeberg => "codeberg.org",
+        }
+    }
+
+    /// Prefix used in `prefix:owner/repo` shorthands
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Host::GitHub => "github",
+            Host::GitLab => "gitlab",
+            Host::Bitbucket => "bitbucket",
+            Host::Codeberg => "codeberg",
+        }
+    }
+
+    /// Detects the forge from an `https://` or `git@host:` repository URL
+    pub fn from_url(url: &str) -> Option<Self> {
+        let (domain, _) = split_url(url)?;
+        Self::ALL.into_iter().find(|host| host.domain() == domain)
+    }
+
+    /// Path segment separating the repository from a browsed ref in web URLs
+    fn browse_marker(self) -> &'static str {
+        match self {
+            Host::GitHub => "/tree/",
+            Host::GitLab => "/-/tree/",
+            Host::Bitbucket => "/src/",
+            Host::Codeberg => "/src/branch/",
+        }
+    }
+
+    /// Tarball download of `repo_url` at `rev`, for machines without git
+    pub fn archive_url(self, repo_url: &str, rev: &str) -> String {
+        let repo = https_url(repo_url);
+        match self {
+            Host::GitHub | Host::Codeberg => format!("{}/archive/{}.tar.gz", repo, rev),
+            Host::GitLab => {
+                let name = repo.rsplit('/').next().unwrap_or_default();
+                format!("{}/-/archive/{}/{}-{}.tar.gz", repo, rev, name, rev)
+            }
+            Host::Bitbucket => format!("{}/get/{}.tar.gz", repo, rev),
+        }
+    }
+}
+
+/// Expands `[host:]owner/repo[#subdir]` to a repository URL. Bare `owner/repo` means GitHub;
+/// GitLab shorthands may name nested groups.
+pub fn expand_shorthand(spec: &str) -> Option<String> {
+    let (spec, subdir) = match spec.split_once('#') {
+        Some((spec, subdir)) => (spec, Some(subdir)),
+        None => (spec, None),
+    };
+    let (host, path) = match spec.split_once(':') {
+        Some((prefix, path)) => (Host::ALL.into_iter().find(|h| h.prefix() == prefix)?, path),
+        None => (Host::GitHub, spec),
+    };
+
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let segments: Vec<&str> = path.split('/').collect();
+    let valid_segment = |part: &&str| {
+        !part.is_empty()
+            && !part.starts_with(['-', '.'])
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    let valid_len = match host {
+        Host::GitLab => segments.len() >= 2,
+        _ => segments.len() == 2,
+    };
+    if !valid_len || !segments.iter().all(valid_segment) {
+        return None;
+    }
+
+    let url = format!("https://{}/{}", host.domain(), path);
+    Some(match subdir {
+        Some(subdir) => format!("{}#{}", url, subdir),
+        None => url,
+    })
+}
+
+/// Turns a forge web URL pointing into a repository (e.g. `.../tree/main/api`) into the
+/// `repo#subdir` form. Other URLs are returned unchanged.
+pub fn normalize_url(url: &str) -> String {
+    let Some(host) = Host::from_url(url) else {
+        return url.to_string();
+    };
+    if url.contains('#') {
+        return url.to_string();
+    }
+
+    let Some((repo, browsed)) = url.split_once(host.browse_marker()) else {
+        return url.trim_end_matches('/').to_string();
+    };
+
+    // The first segment is the branch or tag; the rest is the subdirectory
+    match browsed.trim_end_matches('/').split_once('/') {
+        Some((_, subdir)) if !subdir.is_empty() => format!("{}#{}", repo, subdir),
+        _ => repo.to_string(),
+    }
+}
+
+fn split_url(url: &str) -> Option<(&str, &str)> {
+    if let Some(rest) = url.strip_prefix("git@") {
+        return rest.split_once(':');
+    }
+    let rest = url.split_once("://")?.1;
+    let (domain, path) = rest.split_once('/').unwrap_or((rest, ""));
+    // Drop credentials and ports
+    let domain = domain.rsplit('@').next().unwrap_or(domain);
+    let domain = domain.split(':').next().unwrap_or(domain);
+    Some((domain, path))
+}
+
+fn https_url(repo_url: &str) -> String {
+    let url = match (repo_url.starts_with("git@"), split_url(repo_url)) {
+        (true, Some((domain, path))) => format!("https://{}/{}", domain, path),
+        _ => repo_url.to_string(),
+    };
+    let url = url.trim_end_matches('/');
+    url.strip_suffix(".git").unwrap_or(url).to_string()
+}
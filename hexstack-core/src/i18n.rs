@@ -0,0 +1,178 @@
+//! Translated text for prompts, errors and the next steps printed after a project is created.
+//! Messages are [Fluent](https://projectfluent.org) files: English and the bundled translations
+//! are compiled in from `locales/`, and `<config dir>/locales/<language>.ftl` adds a translation,
+//! or corrects one, without waiting for a release. Messages a translation doesn't have are shown
+//! in English.
+//!
+//! Until [`init`] picks a language everything is English, so tools embedding the engine get the
+//! same text whatever the environment says.
+
+use fluent_bundle::FluentResource;
+use fluent_bundle::concurrent::FluentBundle;
+use std::path::Path;
+use std::sync::OnceLock;
+use tracing::warn;
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::{FluentArgs, FluentValue};
+
+/// Directory under the global config directory holding community translations
+pub const LOCALES_DIR: &str = "locales";
+
+/// The language every other translation falls back to
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// Translations compiled into hexstack, by language tag
+pub const BUNDLED: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+];
+
+/// Environment variables naming the user's language, most specific first
+const LANGUAGE_ENV: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+static MESSAGES: OnceLock<Messages> = OnceLock::new();
+
+/// Bundles to look messages up in, the chosen language first and English last
+pub struct Messages {
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Messages {
+    /// Loads the messages for `language`, from the bundled translations and the `.ftl` files in
+    /// `user_dir`
+    pub fn load(language: Option<&LanguageIdentifier>, user_dir: Option<&Path>) -> Self {
+        let mut tags = Vec::new();
+        if let Some(language) = language {
+            tags.push(language.to_string());
+            tags.push(language.language.as_str().to_string());
+        }
+        tags.push(FALLBACK_LANGUAGE.to_string());
+        tags.dedup();
+
+        let bundles = tags
+            .iter()
+            .filter_map(|tag| bundle(tag, user_dir))
+            .collect();
+        Self { bundles }
+    }
+
+    /// The message `id` with `args` filled in, or `id` itself when no bundle has it
+    pub fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        self.get(id, args).unwrap_or_else(|| id.to_string())
+    }
+
+    /// The message `id` with `args` filled in, `None` when no bundle has it
+    pub fn get(&self, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+        self.bundles.iter().find_map(|bundle| {
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+            Some(
+                bundle
+                    .format_pattern(pattern, args, &mut errors)
+                    .into_owned(),
+            )
+        })
+    }
+}
+
+/// The bundled translation for `tag`, with the user's file for it layered on top
+fn bundle(tag: &str, user_dir: Option<&Path>) -> Option<FluentBundle<FluentResource>> {
+    let language: LanguageIdentifier = tag.parse().ok()?;
+    let mut bundle = FluentBundle::new_concurrent(vec![language]);
+    // Isolation marks around arguments only help right-to-left scripts and show up in terminals
+    bundle.set_use_isolating(false);
+
+    let bundled = BUNDLED
+        .iter()
+        .find(|(bundled, _)| *bundled == tag)
+        .map(|(_, source)| source.to_string());
+    let user_file = user_dir.map(|dir| dir.join(format!("{}.ftl", tag)));
+    let user = user_file
+        .as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok());
+    if bundled.is_none() && user.is_none() {
+        return None;
+    }
+
+    if let Some(source) = bundled {
+        let _ = bundle.add_resource(resource(source, tag));
+    }
+    if let (Some(source), Some(path)) = (user, &user_file) {
+        bundle.add_resource_overriding(resource(source, &path.display().to_string()));
+    }
+    Some(bundle)
+}
+
+/// Parses a Fluent file, keeping the messages around any syntax errors
+fn resource(source: String, name: &str) -> FluentResource {
+    FluentResource::try_new(source).unwrap_or_else(|(resource, errors)| {
+        warn!(
+            "Ignoring {} broken message(s) in the {} translation",
+            errors.len(),
+            name
+        );
+        resource
+    })
+}
+
+/// Picks the language for the rest of the process: `configured` (the `language` setting), or
+/// else the one `LC_ALL`, `LC_MESSAGES` or `LANG` names. Only the first call has an effect.
+pub fn init(configured: Option<&str>, user_dir: Option<&Path>) {
+    let language = configured
+        .map(str::to_string)
+        .or_else(language_from_env)
+        .and_then(|value| parse_language(&value));
+    let _ = MESSAGES.set(Messages::load(language.as_ref(), user_dir));
+}
+
+/// The language the environment asks for, e.g. `de_DE.UTF-8`
+fn language_from_env() -> Option<String> {
+    LANGUAGE_ENV
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+}
+
+/// Turns a locale such as `de_DE.UTF-8` or `pt-BR` into a language tag. `C` and `POSIX` mean
+/// no particular language.
+pub fn parse_language(value: &str) -> Option<LanguageIdentifier> {
+    let tag = value.split(['.', '@']).next()?.replace('_', "-");
+    if tag.is_empty() || tag == "C" || tag == "POSIX" {
+        return None;
+    }
+    tag.parse().ok()
+}
+
+/// The message `id` in the chosen language; see [`t!`](crate::t)
+pub fn message(id: &str, args: Option<&FluentArgs>) -> String {
+    MESSAGES
+        .get_or_init(|| Messages::load(None, None))
+        .format(id, args)
+}
+
+/// The message `id` in the chosen language, for ids built at runtime that a translation may not
+/// have, such as `component-<key>`
+pub fn lookup(id: &str) -> Option<String> {
+    MESSAGES
+        .get_or_init(|| Messages::load(None, None))
+        .get(id, None)
+}
+
+/// Looks up a translated message, filling in its named arguments:
+///
+/// ```
+/// let line = hexstack_core::t!("created", name = "my-app");
+/// assert_eq!(line, "Project 'my-app' created successfully!");
+/// ```
+#[macro_export]
+macro_rules! t {
+    ($id:literal) => {
+        $crate::i18n::message($id, None)
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::i18n::FluentArgs::new();
+        $(args.set(stringify!($name), $crate::i18n::FluentValue::from($value));)+
+        $crate::i18n::message($id, Some(&args))
+    }};
+}
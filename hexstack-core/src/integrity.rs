@@ -0,0 +1,68 @@
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Computes a sha256 over every file of a template tree (excluding `.git`), so the same content
+/// hashes identically whether it was cloned or downloaded as an archive
+pub fn tree_checksum(dir: &Path) -> Result<String> {
+    let files = list_files(dir)?;
+
+    let mut hasher = Sha256::new();
+    for relative in &files {
+        let path = dir.join(relative);
+        let contents = match fs::symlink_metadata(&path) {
+            Ok(meta) if meta.file_type().is_symlink() => fs::read_link(&path)
+                .with_context(|| format!("Failed to read link '{}'", path.display()))?
+                .to_string_lossy()
+                .into_owned()
+                .into_bytes(),
+            _ => fs::read(&path).with_context(|| format!("Failed to read '{}'", path.display()))?,
+        };
+
+        let name = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(&contents);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Compares an expected checksum (optionally prefixed with `sha256:`) against a tree
+pub fn matches(expected: &str, actual: &str) -> bool {
+    let expected = expected.trim();
+    let expected = expected.strip_prefix("sha256:").unwrap_or(expected);
+    expected.eq_ignore_ascii_case(actual)
+}
+
+/// Lists every file below `dir` (excluding `.git`) relative to it, in a stable order
+pub fn list_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read '{}'", dir.display()))? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            if entry.file_name() != ".git" {
+                collect_files(root, &path, files)?;
+            }
+        } else {
+            files.push(path.strip_prefix(root).unwrap_or(&path).to_path_buf());
+        }
+    }
+
+    Ok(())
+}
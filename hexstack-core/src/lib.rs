@@ -0,0 +1,49 @@
+//! The scaffolding engine behind the `hexstack` CLI. It resolves templates from the catalog,
+//! clones and patches them, and writes the lock file, without prompting or drawing on the
+//! terminal. Use [`ProjectSetupBuilder`] to generate a project and a [`ProgressReporter`] to
+//! follow along.
+
+pub mod archive;
+pub mod builder;
+pub mod catalog;
+pub mod config;
+pub mod error;
+pub mod examples;
+pub mod extras;
+pub mod hosts;
+pub mod i18n;
+pub mod integrity;
+pub mod lockfile;
+pub mod manifest;
+pub mod openapi;
+pub mod package_manager;
+pub mod preflight;
+pub mod progress;
+pub mod proxy;
+pub mod registry;
+pub mod remote;
+pub mod report;
+pub mod retry;
+pub mod runner;
+pub mod services;
+pub mod setup;
+pub mod validation;
+pub mod workspace;
+
+pub use crate::builder::ProjectSetupBuilder;
+pub use crate::catalog::{Catalog, CatalogTemplate, ComponentInfo, FrontendInfo};
+pub use crate::error::HexstackError;
+pub use crate::examples::Example;
+pub use crate::extras::{Database, Extras, Styling};
+pub use crate::package_manager::PackageManager;
+pub use crate::progress::{JsonLinesReporter, ProgressReporter, ScaffoldEvent, SilentReporter};
+pub use crate::registry::RegistryEntry;
+pub use crate::report::{CommandRecord, CreateReport, Preview, StepRecord, TemplateReport};
+pub use crate::retry::RetryPolicy;
+pub use crate::runner::{CommandOutput, CommandRunner, Invocation, RunFuture, SystemRunner};
+pub use crate::services::Service;
+pub use crate::setup::FrontendApp;
+pub use crate::validation::ValidName;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,143 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::extras::Extras;
+use crate::services::Service;
+use crate::setup::FrontendApp;
+
+/// Written to the root of every generated project
+pub const LOCK_FILE: &str = ".hexstack.lock";
+
+const LOCK_HEADER: &str = "# Generated by hexstack. Commit this file and run\n# `hexstack new --locked .hexstack.lock` to reproduce this scaffold.\n\n";
+
+/// Everything needed to reproduce a scaffold byte for byte
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LockFile {
+    pub hexstack_version: String,
+    pub project_name: String,
+    pub template: LockedTemplate,
+    /// Database and other additions chosen when the project was created
+    #[serde(default, skip_serializing_if = "Extras::is_empty")]
+    pub extras: Extras,
+    /// Frontend apps under `apps/` when the project has more than one frontend
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub apps: Vec<FrontendApp>,
+    /// Services under `services/` when the project was created with `--services`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub services: Vec<Service>,
+    /// Provider settings saved by `hexstack deploy`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deploy: Option<DeploySettings>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LockedTemplate {
+    pub name: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdir: Option<String>,
+    /// Exact commit the project was generated from
+    pub commit: String,
+    /// Checksum of the template tree at that commit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub components: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frontend: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub desktop: bool,
+}
+
+impl LockFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read lock file '{}'", path.display()))?;
+        let lock: Self = toml::from_str(&contents)
+            .with_context(|| format!("Invalid lock file '{}'", path.display()))?;
+
+        if lock.template.commit.trim().is_empty() {
+            anyhow::bail!(
+                "Lock file '{}' does not pin a template commit",
+                path.display()
+            );
+        }
+
+        Ok(lock)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_toml()?)
+            .with_context(|| format!("Failed to write lock file '{}'", path.display()))
+    }
+
+    /// The lock file as written to disk, header included
+    pub fn to_toml(&self) -> Result<String> {
+        let contents = toml::to_string_pretty(self).context("Failed to serialize lock file")?;
+        Ok(format!("{}{}", LOCK_HEADER, contents))
+    }
+
+    /// The template URL in the `repo#subdir` form used by the catalog
+    pub fn template_url(&self) -> String {
+        match &self.template.subdir {
+            Some(subdir) => format!("{}#{}", self.template.url, subdir),
+            None => self.template.url.clone(),
+        }
+    }
+}
+
+/// Where `hexstack deploy` ships the project
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    /// Build the Docker images and push them to a registry
+    Docker,
+    /// Deploy the backend to Fly.io, built remotely from its Dockerfile
+    Fly,
+    /// Deploy a backend using `shuttle-runtime` to Shuttle
+    Shuttle,
+}
+
+impl Provider {
+    pub const ALL: [Provider; 3] = [Provider::Docker, Provider::Fly, Provider::Shuttle];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "docker" => Some(Provider::Docker),
+            "fly" | "fly.io" => Some(Provider::Fly),
+            "shuttle" => Some(Provider::Shuttle),
+            _ => None,
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Provider::Docker => "docker",
+            Provider::Fly => "fly",
+            Provider::Shuttle => "shuttle",
+        }
+    }
+
+    /// The provider's CLI and the command that installs it
+    pub fn cli(self) -> (&'static str, &'static str) {
+        match self {
+            Provider::Docker => ("docker", "https://docs.docker.com/get-docker/"),
+            Provider::Fly => ("fly", "curl -L https://fly.io/install.sh | sh"),
+            Provider::Shuttle => ("shuttle", "cargo install cargo-shuttle --locked"),
+        }
+    }
+}
+
+/// Deploy settings saved in `.hexstack.lock` by `hexstack deploy`, so later runs need no flags
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DeploySettings {
+    pub provider: Provider,
+    /// Registry the Docker images are pushed to, e.g. `ghcr.io/me`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+    /// App name at the provider; the project name when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app: Option<String>,
+}
@@ -0,0 +1,461 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::extras::{Extras, GeneratedFile, ProjectLayout, WriteMode};
+use crate::setup::{NODE_FRONTENDS, frontend_tool};
+use crate::workspace::Workspace;
+
+/// File at the root of a template repository describing the template
+pub const MANIFEST_FILE: &str = "hexstack.toml";
+
+/// Placeholders substituted in template files, written as `{{name}}`
+pub const PLACEHOLDERS: [&str; 5] = [
+    "project_name",
+    "crate_name",
+    "hexstack_version",
+    "database",
+    "database_url",
+];
+
+/// Files of a template repository that are about the starter rather than the projects made from
+/// it: its license, community files and GitHub metadata. Removed after cloning unless the
+/// manifest's `[cleanup]` table says otherwise.
+pub const DEFAULT_METADATA_FILES: [&str; 14] = [
+    "LICENSE",
+    "LICENSE.md",
+    "LICENSE.txt",
+    "LICENSE-MIT",
+    "LICENSE-APACHE",
+    "CHANGELOG.md",
+    "CODE_OF_CONDUCT.md",
+    "CONTRIBUTING.md",
+    "SECURITY.md",
+    ".github/FUNDING.yml",
+    ".github/ISSUE_TEMPLATE",
+    ".github/PULL_REQUEST_TEMPLATE.md",
+    ".github/pull_request_template.md",
+    ".github/CODEOWNERS",
+];
+
+/// Directories never touched when rendering placeholders
+pub const SKIPPED_DIRS: [&str; 4] = [".git", "node_modules", "target", "dist"];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TemplateManifest {
+    pub template: TemplateInfo,
+    /// Versions the template is tested with, pinned in projects with the toolchain extra
+    #[serde(default, skip_serializing_if = "Toolchain::is_empty")]
+    pub toolchain: Toolchain,
+    /// Template repository files left out of generated projects
+    #[serde(default, skip_serializing_if = "Cleanup::is_default")]
+    pub cleanup: Cleanup,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TemplateInfo {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub components: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frontend: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_hexstack_version: Option<String>,
+}
+
+/// The `[toolchain]` table of a template manifest
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Toolchain {
+    /// Rust channel or version, e.g. `1.85.0`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rust: Option<String>,
+    /// Node version, e.g. `22`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+}
+
+impl Toolchain {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `rust-toolchain.toml` and `.nvmrc` at the project root pinning the declared versions;
+    /// `.nvmrc` only when a frontend runs on Node
+    pub fn files(&self, layout: &ProjectLayout) -> Vec<GeneratedFile> {
+        let mut files = Vec::new();
+        if let Some(rust) = &self.rust {
+            // Rust frontends compile to WASM, so rustup has to install the target too
+            let wasm = layout
+                .frontends
+                .iter()
+                .any(|(_, frontend)| frontend_tool(frontend).is_some());
+            files.push(GeneratedFile {
+                path: PathBuf::from("rust-toolchain.toml"),
+                contents: format!(
+                    "[toolchain]\nchannel = \"{}\"\ncomponents = [\"rustfmt\", \"clippy\"]\n{}",
+                    rust,
+                    if wasm {
+                        "targets = [\"wasm32-unknown-unknown\"]\n"
+                    } else {
+                        ""
+                    }
+                ),
+                mode: WriteMode::Create,
+            });
+        }
+        if let Some(node) = &self.node
+            && layout
+                .frontends
+                .iter()
+                .any(|(_, frontend)| NODE_FRONTENDS.contains(frontend))
+        {
+            files.push(GeneratedFile {
+                path: PathBuf::from(".nvmrc"),
+                contents: format!("{}\n", node),
+                mode: WriteMode::Create,
+            });
+        }
+        files
+    }
+}
+
+/// The `[cleanup]` table of a template manifest
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Cleanup {
+    /// Files and directories to remove, instead of [`DEFAULT_METADATA_FILES`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remove: Option<Vec<String>>,
+    /// Files to keep even though they would be removed, e.g. a LICENSE projects should inherit
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keep: Vec<String>,
+    /// Drop the badges from README.md and title it with the project name
+    #[serde(default = "default_readme")]
+    pub readme: bool,
+}
+
+fn default_readme() -> bool {
+    true
+}
+
+impl Default for Cleanup {
+    fn default() -> Self {
+        Self {
+            remove: None,
+            keep: Vec::new(),
+            readme: default_readme(),
+        }
+    }
+}
+
+impl Cleanup {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The files and directories to remove, relative to the template root
+    pub fn paths(&self) -> Vec<&str> {
+        let remove = match &self.remove {
+            Some(remove) => remove.iter().map(String::as_str).collect(),
+            None => DEFAULT_METADATA_FILES.to_vec(),
+        };
+        remove
+            .into_iter()
+            .filter(|path| !self.keep.iter().any(|keep| keep == path))
+            .collect()
+    }
+}
+
+impl TemplateManifest {
+    /// Reads the manifest from a template directory, if it has one
+    pub fn load(dir: &Path) -> Result<Option<Self>> {
+        let path = dir.join(MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read '{}'", path.display()))?;
+        let manifest = toml::from_str(&contents)
+            .with_context(|| format!("Invalid template manifest '{}'", path.display()))?;
+
+        Ok(Some(manifest))
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self).context("Failed to serialize template manifest")
+    }
+}
+
+/// Values for every supported placeholder, derived from the project name and selected extras
+pub fn placeholder_values(project_name: &str, extras: &Extras) -> HashMap<&'static str, String> {
+    let crate_name = project_name.replace(['-', '.'], "_");
+    let mut values = HashMap::from([
+        ("project_name", project_name.to_string()),
+        ("hexstack_version", env!("CARGO_PKG_VERSION").to_string()),
+    ]);
+    values.extend(extras.placeholder_values(&crate_name));
+    values.insert("crate_name", crate_name);
+    values
+}
+
+/// Replaces `{{placeholder}}` occurrences, leaving unknown placeholders untouched
+pub fn render(text: &str, values: &HashMap<&'static str, String>) -> String {
+    let mut rendered = text.to_string();
+    for (key, value) in values {
+        rendered = rendered.replace(&format!("{{{{{}}}}}", key), value);
+    }
+    rendered
+}
+
+/// Renders placeholders in every text file of `workspace`, returning how many files changed
+pub fn apply_placeholders(
+    workspace: &Workspace,
+    project_name: &str,
+    extras: &Extras,
+) -> Result<usize> {
+    let values = placeholder_values(project_name, extras);
+    let mut changed = 0;
+
+    for_each_text_file(workspace.root(), &mut |path, contents| {
+        let rendered = render(&contents, &values);
+        if rendered != contents {
+            workspace.write(path, rendered)?;
+            changed += 1;
+        }
+        Ok(())
+    })?;
+
+    Ok(changed)
+}
+
+/// Removes the template's own metadata from `workspace` as `cleanup` describes, and retitles
+/// its README for `project_name`. Returns the removed paths.
+pub fn strip_metadata(
+    workspace: &Workspace,
+    cleanup: &Cleanup,
+    project_name: &str,
+) -> Result<Vec<PathBuf>> {
+    let mut removed = Vec::new();
+    for path in cleanup.paths() {
+        let full = workspace.path(path)?;
+        if full.is_dir() {
+            workspace.remove_dir_all(path)?;
+        } else if full.is_file() {
+            fs::remove_file(&full)
+                .with_context(|| format!("Failed to remove '{}'", full.display()))?;
+        } else {
+            continue;
+        }
+        removed.push(PathBuf::from(path));
+    }
+
+    if cleanup.readme
+        && let Some(readme) = workspace.read_to_string("README.md")
+    {
+        let cleaned = clean_readme(&readme, project_name);
+        if cleaned != readme {
+            workspace.write("README.md", cleaned)?;
+        }
+    }
+    Ok(removed)
+}
+
+/// `readme` without the starter's badges and with its first `# ` heading replaced by
+/// `project_name`. Badges are lines made of images only, linked or not.
+pub fn clean_readme(readme: &str, project_name: &str) -> String {
+    let mut cleaned = String::new();
+    let mut titled = false;
+    let mut blank = false;
+    for line in readme.lines() {
+        let trimmed = line.trim();
+        if is_badge_line(trimmed) {
+            continue;
+        }
+        // Removed badges leave their blank lines behind
+        if trimmed.is_empty() {
+            if blank {
+                continue;
+            }
+            blank = true;
+        } else {
+            blank = false;
+        }
+        if !titled && trimmed.starts_with("# ") {
+            titled = true;
+            cleaned.push_str(&format!("# {}\n", project_name));
+            continue;
+        }
+        cleaned.push_str(line);
+        cleaned.push('\n');
+    }
+    cleaned
+}
+
+/// Whether `line` holds nothing but markdown images such as `[![CI](badge.svg)](actions)`
+fn is_badge_line(line: &str) -> bool {
+    let mut rest = line;
+    while !rest.is_empty() {
+        let linked = rest.starts_with("[![");
+        let image = if linked { &rest[1..] } else { rest };
+        let Some((_, after)) = image
+            .strip_prefix("![")
+            .and_then(|image| image.split_once("]("))
+            .and_then(|(_, url)| url.split_once(')'))
+        else {
+            return false;
+        };
+        rest = match (linked, after.strip_prefix("](")) {
+            (false, _) => after,
+            (true, Some(link)) => match link.split_once(')') {
+                Some((_, after)) => after,
+                None => return false,
+            },
+            (true, None) => return false,
+        };
+        rest = rest.trim_start();
+    }
+    !line.is_empty()
+}
+
+/// Sets the `name` in the `[package]` of a Cargo.toml, leaving the rest of the file as it is
+pub fn rename_package(cargo_toml: &str, name: &str) -> String {
+    let mut in_package = false;
+    let mut renamed = String::new();
+    for line in cargo_toml.lines() {
+        if line.trim_start().starts_with('[') {
+            in_package = line.trim() == "[package]";
+        }
+        if in_package
+            && line
+                .split_once('=')
+                .is_some_and(|(key, _)| key.trim() == "name")
+        {
+            renamed.push_str(&format!("name = \"{}\"\n", name));
+        } else {
+            renamed.push_str(line);
+            renamed.push('\n');
+        }
+    }
+    renamed
+}
+
+/// Points the dependencies on package `old` in a Cargo.toml at `new` through `package = "new"`,
+/// so the dependency keeps its key and the code using it compiles unchanged. Dependencies
+/// inherited with `workspace = true` follow `[workspace.dependencies]` and are left alone.
+pub fn rename_dependency(cargo_toml: &str, old: &str, new: &str) -> String {
+    let mut in_dependencies = false;
+    let mut renamed = String::new();
+    for line in cargo_toml.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            let table = trimmed.trim_matches(['[', ']']);
+            in_dependencies = table.ends_with("dependencies");
+            renamed.push_str(line);
+            renamed.push('\n');
+            // `[dependencies.old]` spells the dependency as a table of its own
+            if table
+                .strip_suffix(old)
+                .and_then(|table| table.strip_suffix('.'))
+                .is_some_and(|table| table.ends_with("dependencies"))
+            {
+                renamed.push_str(&format!("package = \"{}\"\n", new));
+            }
+            continue;
+        }
+        if in_dependencies
+            && let Some((key, value)) = line.split_once('=')
+            && key.trim() == old
+            && let Some(fields) = value.trim().strip_prefix('{')
+            && !fields.contains("package")
+            && !fields.contains("workspace")
+        {
+            renamed.push_str(&format!(
+                "{}= {{ package = \"{}\",{}\n",
+                key,
+                new,
+                fields.trim_end()
+            ));
+        } else {
+            renamed.push_str(line);
+            renamed.push('\n');
+        }
+    }
+    renamed
+}
+
+/// Renames the backend's package, in `backend/Cargo.toml` or else the root Cargo.toml, to
+/// `name`, and updates the dependencies on it in the project's other Cargo.toml files. Returns
+/// the template's package name, or `None` when there was nothing to rename.
+pub fn rename_backend_package(workspace: &Workspace, name: &str) -> Result<Option<String>> {
+    let manifest_path = ["backend/Cargo.toml", "Cargo.toml"]
+        .into_iter()
+        .find(|path| workspace.root().join(path).is_file());
+    let Some(manifest_path) = manifest_path else {
+        return Ok(None);
+    };
+    let Some(cargo_toml) = workspace.read_to_string(manifest_path) else {
+        return Ok(None);
+    };
+    let package: toml::Table = toml::from_str(&cargo_toml)
+        .with_context(|| format!("Failed to parse the template's {}", manifest_path))?;
+    let old = match package
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(|name| name.as_str())
+    {
+        Some(old) if old != name => old.to_string(),
+        _ => return Ok(None),
+    };
+
+    workspace.write(manifest_path, rename_package(&cargo_toml, name))?;
+    let renamed = workspace.path(manifest_path)?;
+    for_each_text_file(workspace.root(), &mut |path, contents| {
+        if path.file_name().is_some_and(|n| n == "Cargo.toml") && path != renamed {
+            let updated = rename_dependency(&contents, &old, name);
+            if updated != contents {
+                workspace.write(path, updated)?;
+            }
+        }
+        Ok(())
+    })?;
+    Ok(Some(old))
+}
+
+/// Calls `visit` with every text file of a template, skipping build output, dependencies, git
+/// metadata and the manifest itself, which documents the placeholders and is never rendered
+pub fn for_each_text_file(
+    dir: &Path,
+    visit: &mut dyn FnMut(&Path, String) -> Result<()>,
+) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read '{}'", dir.display()))? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            let skipped = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| SKIPPED_DIRS.contains(&n));
+            if !skipped {
+                for_each_text_file(&path, visit)?;
+            }
+            continue;
+        }
+
+        if path.file_name().is_some_and(|n| n == MANIFEST_FILE) {
+            continue;
+        }
+
+        // Binary files are left alone
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        visit(&path, contents)?;
+    }
+
+    Ok(())
+}
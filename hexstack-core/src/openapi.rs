@@ -0,0 +1,411 @@
+use anyhow::Result;
+use serde_json::{Value, json};
+use std::collections::BTreeSet;
+
+/// Directory of a frontend the client is generated into
+pub const CLIENT_DIR: &str = "src/api";
+
+const HEADER: &str =
+    "// Generated by `hexstack generate api-client` from the OpenAPI spec. Don't edit by hand.\n";
+
+/// Spec of the example route in the generated backend, used for the first client before the
+/// backend has run and written its own
+pub fn example_spec(project_name: &str) -> Value {
+    json!({
+        "openapi": "3.1.0",
+        "info": {"title": project_name, "version": "0.1.0"},
+        "paths": {
+            "/api/hello": {
+                "get": {
+                    "summary": "Says hello",
+                    "operationId": "getHello",
+                    "responses": {
+                        "200": {
+                            "description": "A greeting",
+                            "content": {
+                                "application/json": {
+                                    "schema": {"$ref": "#/components/schemas/Greeting"}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Greeting": {
+                    "type": "object",
+                    "description": "A greeting from the server",
+                    "required": ["message"],
+                    "properties": {"message": {"type": "string"}}
+                }
+            }
+        }
+    })
+}
+
+/// `types.ts` with a type per schema and `index.ts` with a function per operation
+pub fn typescript_client(spec: &Value) -> Result<Vec<(&'static str, String)>> {
+    if !spec["openapi"].is_string() && !spec["swagger"].is_string() {
+        anyhow::bail!("Not an OpenAPI spec: the `openapi` version field is missing");
+    }
+
+    let mut types = String::from(HEADER);
+    let schemas = spec["components"]["schemas"].as_object();
+    for (name, schema) in schemas.into_iter().flatten() {
+        types.push('\n');
+        types.push_str(&doc_comment(schema["description"].as_str(), ""));
+        let name = type_name(name);
+        if schema["type"] == "object" && schema["properties"].is_object() {
+            types.push_str(&format!(
+                "export interface {} {}\n",
+                name,
+                object_type(schema, &mut BTreeSet::new(), "")
+            ));
+        } else {
+            types.push_str(&format!(
+                "export type {} = {};\n",
+                name,
+                ts_type(schema, &mut BTreeSet::new())
+            ));
+        }
+    }
+    if schemas.is_none_or(|schemas| schemas.is_empty()) {
+        types.push_str("\nexport {};\n");
+    }
+
+    let mut referenced = BTreeSet::new();
+    let mut operations = String::new();
+    for (path, item) in spec["paths"].as_object().into_iter().flatten() {
+        for method in ["get", "post", "put", "patch", "delete"] {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+            operations.push('\n');
+            operations.push_str(&operation_function(
+                path,
+                method,
+                operation,
+                &mut referenced,
+            ));
+        }
+    }
+
+    let mut client = String::from(HEADER);
+    if !referenced.is_empty() {
+        client.push_str(&format!(
+            "import type {{ {} }} from \"./types\";\n",
+            referenced.into_iter().collect::<Vec<_>>().join(", ")
+        ));
+    }
+    client.push_str(
+        r#"
+export * from "./types";
+
+/** Where the backend is served; change it with `setBaseUrl` */
+export let baseUrl = "http://localhost:3000";
+
+export function setBaseUrl(url: string) {
+  baseUrl = url;
+}
+
+async function request<T>(
+  method: string,
+  path: string,
+  options: { query?: Record<string, unknown>; body?: unknown } = {},
+): Promise<T> {
+  const url = new URL(path, baseUrl);
+  for (const [key, value] of Object.entries(options.query ?? {})) {
+    if (value !== undefined && value !== null) {
+      url.searchParams.set(key, String(value));
+    }
+  }
+  const hasBody = options.body !== undefined;
+  const response = await fetch(url, {
+    method,
+    headers: hasBody ? { "Content-Type": "application/json" } : undefined,
+    body: hasBody ? JSON.stringify(options.body) : undefined,
+  });
+  if (!response.ok) {
+    throw new Error(`${method} ${path} failed with status ${response.status}`);
+  }
+  const text = await response.text();
+  return (text ? JSON.parse(text) : undefined) as T;
+}
+"#,
+    );
+    client.push_str(&operations);
+
+    Ok(vec![("types.ts", types), ("index.ts", client)])
+}
+
+fn operation_function(
+    path: &str,
+    method: &str,
+    operation: &Value,
+    referenced: &mut BTreeSet<String>,
+) -> String {
+    let name = operation["operationId"]
+        .as_str()
+        .map(camel_case)
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| fallback_operation_name(method, path));
+
+    let mut params = Vec::new();
+    let mut url = path.to_string();
+    let mut query_fields = Vec::new();
+    let mut query_required = false;
+    for param in operation["parameters"].as_array().into_iter().flatten() {
+        let Some(param_name) = param["name"].as_str() else {
+            continue;
+        };
+        let ty = ts_type(&param["schema"], referenced);
+        match param["in"].as_str() {
+            Some("path") => {
+                let arg = camel_case(param_name);
+                url = url.replace(
+                    &format!("{{{}}}", param_name),
+                    &format!("${{encodeURIComponent(String({}))}}", arg),
+                );
+                params.push(format!("{}: {}", arg, ty));
+            }
+            Some("query") => {
+                let required = param["required"] == true;
+                query_required |= required;
+                query_fields.push(format!(
+                    "{}{}: {}",
+                    property_name(param_name),
+                    if required { "" } else { "?" },
+                    ty
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    let body = json_schema(&operation["requestBody"]["content"]);
+    let mut options = Vec::new();
+    if let Some(schema) = body {
+        // A required query can't follow an optional parameter
+        let optional = operation["requestBody"]["required"] != true && !query_required;
+        params.push(format!(
+            "body{}: {}",
+            if optional { "?" } else { "" },
+            ts_type(schema, referenced)
+        ));
+        options.push("body");
+    }
+    if !query_fields.is_empty() {
+        params.push(format!(
+            "query{}: {{ {} }}",
+            if query_required { "" } else { "?" },
+            query_fields.join("; ")
+        ));
+        options.push("query");
+    }
+
+    let response = operation["responses"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(status, _)| status.starts_with('2'))
+        .find_map(|(_, response)| json_schema(&response["content"]))
+        .map_or_else(|| "void".to_string(), |schema| ts_type(schema, referenced));
+
+    let options = if options.is_empty() {
+        String::new()
+    } else {
+        format!(", {{ {} }}", options.join(", "))
+    };
+    let summary = operation["summary"]
+        .as_str()
+        .or(operation["description"].as_str());
+    format!(
+        "{}export function {}({}): Promise<{}> {{\n  return request<{}>(\"{}\", `{}`{});\n}}\n",
+        doc_comment(summary, ""),
+        name,
+        params.join(", "),
+        response,
+        response,
+        method.to_uppercase(),
+        url,
+        options
+    )
+}
+
+/// The JSON schema of a request body's or response's `content`
+fn json_schema(content: &Value) -> Option<&Value> {
+    content
+        .as_object()?
+        .iter()
+        .find(|(media_type, _)| media_type.contains("json"))
+        .map(|(_, media)| &media["schema"])
+        .filter(|schema| !schema.is_null())
+}
+
+fn ts_type(schema: &Value, referenced: &mut BTreeSet<String>) -> String {
+    let ty = base_ts_type(schema, referenced);
+    if schema["nullable"] == true {
+        format!("{} | null", ty)
+    } else {
+        ty
+    }
+}
+
+fn base_ts_type(schema: &Value, referenced: &mut BTreeSet<String>) -> String {
+    if let Some(reference) = schema["$ref"].as_str() {
+        let name = type_name(reference.rsplit('/').next().unwrap_or(reference));
+        referenced.insert(name.clone());
+        return name;
+    }
+    if let Some(values) = schema["enum"].as_array() {
+        return values
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+    for (key, separator) in [("oneOf", " | "), ("anyOf", " | "), ("allOf", " & ")] {
+        if let Some(variants) = schema[key].as_array() {
+            return variants
+                .iter()
+                .map(|variant| ts_type(variant, referenced))
+                .collect::<Vec<_>>()
+                .join(separator);
+        }
+    }
+    // OpenAPI 3.1 writes nullable types as `["string", "null"]`
+    if let Some(types) = schema["type"].as_array() {
+        return types
+            .iter()
+            .map(|ty| {
+                let mut single = schema.clone();
+                single["type"] = ty.clone();
+                base_ts_type(&single, referenced)
+            })
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+
+    match schema["type"].as_str() {
+        Some("string") => "string".to_string(),
+        Some("integer" | "number") => "number".to_string(),
+        Some("boolean") => "boolean".to_string(),
+        Some("null") => "null".to_string(),
+        Some("array") => format!("Array<{}>", ts_type(&schema["items"], referenced)),
+        Some("object") if schema["properties"].is_object() => object_type(schema, referenced, ""),
+        Some("object") => match &schema["additionalProperties"] {
+            additional if additional.is_object() => {
+                format!("Record<string, {}>", ts_type(additional, referenced))
+            }
+            _ => "Record<string, unknown>".to_string(),
+        },
+        _ => "unknown".to_string(),
+    }
+}
+
+/// `{ ... }` with a line per property, indented one level deeper than `indent`
+fn object_type(schema: &Value, referenced: &mut BTreeSet<String>, indent: &str) -> String {
+    let required: Vec<&str> = schema["required"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    let inner = format!("{}  ", indent);
+    let mut body = String::from("{\n");
+    for (name, property) in schema["properties"].as_object().into_iter().flatten() {
+        body.push_str(&doc_comment(property["description"].as_str(), &inner));
+        let ty = if property["type"] == "object" && property["properties"].is_object() {
+            object_type(property, referenced, &inner)
+        } else {
+            ts_type(property, referenced)
+        };
+        body.push_str(&format!(
+            "{}{}{}: {};\n",
+            inner,
+            property_name(name),
+            if required.contains(&name.as_str()) {
+                ""
+            } else {
+                "?"
+            },
+            ty
+        ));
+    }
+    body.push_str(indent);
+    body.push('}');
+    body
+}
+
+fn doc_comment(text: Option<&str>, indent: &str) -> String {
+    match text.map(str::trim).filter(|text| !text.is_empty()) {
+        Some(text) => format!("{}/** {} */\n", indent, text.replace("*/", "* /")),
+        None => String::new(),
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn property_name(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_string()
+    } else {
+        Value::from(name).to_string()
+    }
+}
+
+/// PascalCase name for a schema, e.g. `todo.Item` becomes `TodoItem`
+fn type_name(name: &str) -> String {
+    let mut pascal = camel_case(name);
+    if let Some(first) = pascal.get(..1) {
+        pascal.replace_range(..1, &first.to_uppercase());
+    }
+    pascal
+}
+
+/// camelCase identifier from any name, e.g. `list_todos` or `list-todos` become `listTodos`
+fn camel_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut upper_next = false;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if result.is_empty() && c.is_ascii_digit() {
+                result.push('_');
+            }
+            if upper_next && !result.is_empty() {
+                result.push(c.to_ascii_uppercase());
+            } else if result.is_empty() {
+                result.push(c.to_ascii_lowercase());
+            } else {
+                result.push(c);
+            }
+            upper_next = false;
+        } else {
+            upper_next = true;
+        }
+    }
+    result
+}
+
+/// Name for an operation without an `operationId`, e.g. `GET /todos/{id}` becomes `getTodosById`
+fn fallback_operation_name(method: &str, path: &str) -> String {
+    let mut name = method.to_string();
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(param) => {
+                name.push_str("By");
+                name.push_str(&type_name(param));
+            }
+            None => name.push_str(&type_name(segment)),
+        }
+    }
+    name
+}
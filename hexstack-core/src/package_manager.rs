@@ -0,0 +1,97 @@
+//! The JavaScript package manager a Node frontend is installed and run with. The lock file a
+//! template ships decides, unless `--package-manager` or the `package_manager` setting of the
+//! global config picks one; npm is used when neither says anything.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Values accepted by [`PackageManager::parse`]
+pub const PACKAGE_MANAGERS: [&str; 4] = ["npm", "pnpm", "yarn", "bun"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageManager {
+    #[default]
+    Npm,
+    Pnpm,
+    Yarn,
+    Bun,
+}
+
+/// Lock files and the package manager that writes them, in the order they are looked for
+const LOCK_FILES: [(&str, PackageManager); 5] = [
+    ("pnpm-lock.yaml", PackageManager::Pnpm),
+    ("bun.lockb", PackageManager::Bun),
+    ("bun.lock", PackageManager::Bun),
+    ("yarn.lock", PackageManager::Yarn),
+    ("package-lock.json", PackageManager::Npm),
+];
+
+impl PackageManager {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "npm" => Some(PackageManager::Npm),
+            "pnpm" => Some(PackageManager::Pnpm),
+            "yarn" => Some(PackageManager::Yarn),
+            "bun" => Some(PackageManager::Bun),
+            _ => None,
+        }
+    }
+
+    /// The package manager whose lock file `dir` contains
+    pub fn detect(dir: &Path) -> Option<Self> {
+        LOCK_FILES
+            .iter()
+            .find(|(file, _)| dir.join(file).is_file())
+            .map(|(_, manager)| *manager)
+    }
+
+    /// `preferred` when set, otherwise the one [`detect`](Self::detect) finds, otherwise npm
+    pub fn resolve(dir: &Path, preferred: Option<Self>) -> Self {
+        preferred.or_else(|| Self::detect(dir)).unwrap_or_default()
+    }
+
+    pub fn binary(self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Bun => "bun",
+        }
+    }
+
+    pub fn install_command(self) -> String {
+        format!("{} install", self.binary())
+    }
+
+    /// The command running `script` from package.json
+    pub fn run_command(self, script: &str) -> String {
+        match self {
+            PackageManager::Npm | PackageManager::Bun => {
+                format!("{} run {}", self.binary(), script)
+            }
+            PackageManager::Pnpm | PackageManager::Yarn => format!("{} {}", self.binary(), script),
+        }
+    }
+
+    /// Installing the dependencies, then starting the dev server
+    pub fn dev_steps(self) -> [String; 2] {
+        [self.install_command(), self.run_command("dev")]
+    }
+
+    /// How to get the package manager when it isn't on the PATH
+    pub fn install_instructions(self) -> &'static str {
+        match self {
+            PackageManager::Npm => "it comes with Node.js, see https://nodejs.org",
+            PackageManager::Pnpm => "corepack enable pnpm   # or: npm install -g pnpm",
+            PackageManager::Yarn => "corepack enable yarn   # or: npm install -g yarn",
+            PackageManager::Bun => "see https://bun.sh/docs/installation",
+        }
+    }
+}
+
+impl std::fmt::Display for PackageManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.binary())
+    }
+}
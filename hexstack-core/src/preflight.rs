@@ -0,0 +1,148 @@
+//! Checks for the tools a scaffold needs before anything is asked or written, so a missing cargo
+//! shows up as the first thing and not after every prompt has been answered, and for the disk
+//! space a template needs before it is cloned. A missing git only gives a warning: the template
+//! is downloaded as an archive instead.
+
+use crate::error::HexstackError;
+use crate::setup::{NODE_FRONTENDS, on_path};
+use std::io;
+use std::path::Path;
+
+/// Free space required on top of the template itself, for cargo's lock file, npm installs and
+/// the rest of the scaffold
+pub const MIN_FREE_BYTES: u64 = 100 * 1024 * 1024;
+
+/// How much room a template takes per byte of its compressed archive: the packed history of a
+/// clone plus the checked out files
+const UNPACK_FACTOR: u64 = 4;
+
+/// Tools every scaffold runs: cargo to update the template's dependencies. git is optional, as
+/// templates are downloaded as archives without it.
+pub const BASE_TOOLS: [&str; 1] = ["cargo"];
+
+/// Tools the chosen frontends need on top of [`BASE_TOOLS`]
+pub fn frontend_tools<'a>(frontends: impl IntoIterator<Item = &'a str>) -> Vec<&'static str> {
+    let mut tools = Vec::new();
+    if frontends
+        .into_iter()
+        .any(|frontend| NODE_FRONTENDS.contains(&frontend))
+    {
+        tools.extend(["node", "npm"]);
+    }
+    tools
+}
+
+/// Fails with [`HexstackError::ToolMissing`] for the first of `tools` that isn't on the PATH,
+/// with instructions for installing it on this OS
+pub fn check(tools: &[&str]) -> Result<(), HexstackError> {
+    check_with(tools, on_path, std::env::consts::OS)
+}
+
+/// [`check`] with the lookup and the OS spelled out
+pub fn check_with(
+    tools: &[&str],
+    installed: impl Fn(&str) -> bool,
+    os: &str,
+) -> Result<(), HexstackError> {
+    match tools.iter().find(|tool| !installed(tool)) {
+        Some(tool) => Err(HexstackError::tool_missing(
+            tool,
+            &install_instructions(tool, os),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// How to install `tool` on `os` (as in `std::env::consts::OS`)
+pub fn install_instructions(tool: &str, os: &str) -> String {
+    let instructions = match (tool, os) {
+        ("git", "macos") => "xcode-select --install   # or: brew install git",
+        ("git", "windows") => "winget install --id Git.Git -e",
+        ("git", _) => "sudo apt install git   # or your distribution's package manager",
+        ("cargo", "windows") => "winget install Rustlang.Rustup   # or see https://rustup.rs",
+        ("cargo", _) => "curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh",
+        ("node" | "npm", "macos") => "brew install node   # or see https://nodejs.org",
+        ("node" | "npm", "windows") => "winget install OpenJS.NodeJS.LTS",
+        ("node" | "npm", _) => {
+            "see https://nodejs.org/en/download/package-manager, or install your distribution's nodejs and npm packages"
+        }
+        _ => return format!("install `{}` and make sure it is on your PATH", tool),
+    };
+    instructions.to_string()
+}
+
+/// Bytes a template of `size` needs free, [`MIN_FREE_BYTES`] when its size is unknown
+pub fn required_space(size: Option<u64>) -> u64 {
+    size.unwrap_or(0)
+        .saturating_mul(UNPACK_FACTOR)
+        .saturating_add(MIN_FREE_BYTES)
+}
+
+/// Fails with [`HexstackError::InsufficientSpace`] when `available` bytes in `dir` are fewer
+/// than a template of `size` needs
+pub fn check_space(dir: &Path, size: Option<u64>, available: u64) -> Result<(), HexstackError> {
+    let needed = required_space(size);
+    if available < needed {
+        return Err(HexstackError::InsufficientSpace {
+            path: dir.to_path_buf(),
+            needed,
+            available,
+        });
+    }
+    Ok(())
+}
+
+/// Bytes available to this user on the filesystem holding `dir`, or its closest existing parent
+pub fn available_space(dir: &Path) -> io::Result<u64> {
+    let existing = dir
+        .ancestors()
+        .find(|dir| dir.exists())
+        .unwrap_or(Path::new("."));
+    free_bytes(existing)
+}
+
+#[cfg(unix)]
+fn free_bytes(dir: &Path) -> io::Result<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(dir.as_os_str().as_bytes())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `path` is NUL-terminated and `stat` is only read after statvfs filled it in
+    if unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let stat = unsafe { stat.assume_init() };
+    #[allow(clippy::unnecessary_cast)]
+    Ok((stat.f_bavail as u64).saturating_mul(stat.f_frsize as u64))
+}
+
+#[cfg(windows)]
+fn free_bytes(dir: &Path) -> io::Result<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let path: Vec<u16> = dir.as_os_str().encode_wide().chain([0]).collect();
+    let mut available = 0u64;
+    // SAFETY: `path` is NUL-terminated and the unused totals may be null
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(
+            path.as_ptr(),
+            &mut available,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    if ok == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(available)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn free_bytes(_dir: &Path) -> io::Result<u64> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "free disk space is not available on this platform",
+    ))
+}
@@ -0,0 +1,291 @@
+use serde::Serialize;
+use std::fmt;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::debug;
+
+use crate::report::{CreateReport, StepRecord, TemplateReport, elapsed_ms};
+
+/// Receives the progress of a scaffold. `hexstack new` draws a progress bar; library users can
+/// stay silent or consume the events as JSON lines without a TTY.
+pub trait ProgressReporter: Send + Sync {
+    /// Called once before the first step with the number of steps the scaffold takes
+    fn start(&self, total_steps: u64);
+    /// What the scaffold is working on now
+    fn message(&self, message: &str);
+    /// One step is done
+    fn step(&self);
+    /// Something went wrong that doesn't stop the scaffold
+    fn warn(&self, message: &str);
+    fn finish(&self, message: &str);
+    /// A milestone of the scaffold, for custom UIs and logging
+    fn event(&self, _event: &ScaffoldEvent) {}
+    /// Whether the next steps and other notes meant for a person should be printed
+    fn human_output(&self) -> bool {
+        true
+    }
+}
+
+/// The stages of a scaffold, in the order they run. Each one is a step of the progress bar, whose
+/// length is the number of steps in `ProjectSetup::plan`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    /// Picking the template for the components and frontend
+    Resolve,
+    /// Cloning the template, once per frontend app or service
+    Fetch,
+    /// Filling in placeholders and writing the example and extras
+    PostProcess,
+    /// Replacing the template's git history with a fresh repository
+    GitInit,
+    /// Updating the Cargo dependencies
+    Install,
+    /// Writing the lock file
+    Finalize,
+}
+
+/// Milestones of a scaffold, passed to [`ProgressReporter::event`] and the hooks registered
+/// with `ProjectSetupBuilder::on_event`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ScaffoldEvent {
+    /// The scaffold moved on to `step`
+    StepStarted {
+        step: Step,
+    },
+    /// A template was picked for the project, a frontend app or a service
+    TemplateResolved {
+        name: String,
+        url: String,
+    },
+    CloneStarted {
+        url: String,
+    },
+    CloneFinished {
+        url: String,
+        commit: String,
+    },
+    /// The scaffold stopped with an error while working on `step`
+    StepFailed {
+        step: String,
+        error: String,
+    },
+    Completed {
+        path: PathBuf,
+    },
+}
+
+pub type EventHook = Arc<dyn Fn(&ScaffoldEvent) + Send + Sync>;
+
+/// Callbacks registered for [`ScaffoldEvent`]s
+#[derive(Clone, Default)]
+pub struct EventHooks(Vec<EventHook>);
+
+impl EventHooks {
+    pub fn push(&mut self, hook: EventHook) {
+        self.0.push(hook);
+    }
+}
+
+impl fmt::Debug for EventHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EventHooks({})", self.0.len())
+    }
+}
+
+/// Forwards to a reporter, sending events to the hooks as well. It remembers the step the
+/// scaffold is on so a failure can name it, and collects what goes into the `CreateReport`.
+pub struct Observed<'a> {
+    reporter: &'a dyn ProgressReporter,
+    hooks: &'a EventHooks,
+    step: Mutex<(String, Instant)>,
+    steps: Mutex<Vec<StepRecord>>,
+    warnings: Mutex<Vec<String>>,
+    template: Mutex<Option<TemplateReport>>,
+}
+
+impl<'a> Observed<'a> {
+    pub fn new(reporter: &'a dyn ProgressReporter, hooks: &'a EventHooks) -> Self {
+        Self {
+            reporter,
+            hooks,
+            step: Mutex::new(("Checking the project settings".to_string(), Instant::now())),
+            steps: Mutex::new(Vec::new()),
+            warnings: Mutex::new(Vec::new()),
+            template: Mutex::new(None),
+        }
+    }
+
+    pub fn step_name(&self) -> String {
+        self.step.lock().map(|s| s.0.clone()).unwrap_or_default()
+    }
+
+    /// Closes the current step and adds the step timings, warnings and template to `report`
+    pub fn fill_report(&self, report: &mut CreateReport) {
+        self.close_step(String::new());
+        if let Ok(mut steps) = self.steps.lock() {
+            report.steps = std::mem::take(&mut *steps);
+        }
+        if let Ok(mut warnings) = self.warnings.lock() {
+            report.warnings = std::mem::take(&mut *warnings);
+        }
+        if let Ok(mut template) = self.template.lock() {
+            report.template = template.take();
+        }
+    }
+
+    fn close_step(&self, next: String) {
+        let Ok(mut step) = self.step.lock() else {
+            return;
+        };
+        let (name, started) = std::mem::replace(&mut *step, (next, Instant::now()));
+        if let Ok(mut steps) = self.steps.lock() {
+            steps.push(StepRecord {
+                name,
+                duration_ms: elapsed_ms(started),
+            });
+        }
+    }
+}
+
+impl ProgressReporter for Observed<'_> {
+    fn start(&self, total_steps: u64) {
+        self.reporter.start(total_steps);
+    }
+
+    fn message(&self, message: &str) {
+        debug!("{}", message);
+        self.close_step(message.to_string());
+        self.reporter.message(message);
+    }
+
+    fn step(&self) {
+        self.reporter.step();
+    }
+
+    fn warn(&self, message: &str) {
+        if let Ok(mut warnings) = self.warnings.lock() {
+            warnings.push(message.to_string());
+        }
+        self.reporter.warn(message);
+    }
+
+    fn finish(&self, message: &str) {
+        self.reporter.finish(message);
+    }
+
+    fn event(&self, event: &ScaffoldEvent) {
+        debug!(?event, "Scaffold event");
+        if let Ok(mut template) = self.template.lock() {
+            match event {
+                ScaffoldEvent::TemplateResolved { name, url } if template.is_none() => {
+                    *template = Some(TemplateReport {
+                        name: name.clone(),
+                        url: url.clone(),
+                        commit: None,
+                    });
+                }
+                ScaffoldEvent::CloneFinished { commit, .. } => {
+                    if let Some(template) = template.as_mut().filter(|t| t.commit.is_none()) {
+                        template.commit = Some(commit.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.reporter.event(event);
+        for hook in &self.hooks.0 {
+            hook(event);
+        }
+    }
+
+    fn human_output(&self) -> bool {
+        self.reporter.human_output()
+    }
+}
+
+/// Reports nothing
+pub struct SilentReporter;
+
+impl ProgressReporter for SilentReporter {
+    fn start(&self, _total_steps: u64) {}
+    fn message(&self, _message: &str) {}
+    fn step(&self) {}
+    fn warn(&self, _message: &str) {}
+    fn finish(&self, _message: &str) {}
+
+    fn human_output(&self) -> bool {
+        false
+    }
+}
+
+/// Writes one JSON object per event, e.g. `{"event":"step","position":2,"total":5}`
+pub struct JsonLinesReporter {
+    out: Mutex<Box<dyn Write + Send>>,
+    position: AtomicU64,
+    total: AtomicU64,
+}
+
+impl JsonLinesReporter {
+    pub fn new(out: impl Write + Send + 'static) -> Self {
+        Self {
+            out: Mutex::new(Box::new(out)),
+            position: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+        }
+    }
+
+    pub fn stdout() -> Self {
+        Self::new(std::io::stdout())
+    }
+
+    fn emit(&self, event: serde_json::Value) {
+        if let Ok(mut out) = self.out.lock() {
+            let _ = writeln!(out, "{}", event);
+            let _ = out.flush();
+        }
+    }
+}
+
+impl ProgressReporter for JsonLinesReporter {
+    fn start(&self, total_steps: u64) {
+        self.total.store(total_steps, Ordering::Relaxed);
+        self.emit(serde_json::json!({ "event": "start", "total": total_steps }));
+    }
+
+    fn message(&self, message: &str) {
+        self.emit(serde_json::json!({ "event": "message", "message": message }));
+    }
+
+    fn step(&self) {
+        let position = self.position.fetch_add(1, Ordering::Relaxed) + 1;
+        self.emit(serde_json::json!({
+            "event": "step",
+            "position": position,
+            "total": self.total.load(Ordering::Relaxed),
+        }));
+    }
+
+    fn warn(&self, message: &str) {
+        self.emit(serde_json::json!({ "event": "warning", "message": message }));
+    }
+
+    fn finish(&self, message: &str) {
+        self.emit(serde_json::json!({ "event": "finish", "message": message }));
+    }
+
+    fn event(&self, event: &ScaffoldEvent) {
+        if let Ok(event) = serde_json::to_value(event) {
+            self.emit(event);
+        }
+    }
+
+    fn human_output(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,82 @@
+//! Proxy settings from `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` (or their lowercase forms),
+//! applied the same way to hexstack's own HTTP requests and to the git clones it runs.
+
+use crate::runner::Invocation;
+use tracing::warn;
+
+/// The proxies configured in the environment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProxySettings {
+    /// Proxy for `http://` URLs
+    pub http: Option<String>,
+    /// Proxy for `https://` URLs
+    pub https: Option<String>,
+    /// Comma-separated hosts and domains that are reached directly
+    pub no_proxy: Option<String>,
+}
+
+impl ProxySettings {
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Reads the settings through `var`, preferring the uppercase name of each variable
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let set = |name: &str| {
+            var(name)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let lookup = |name: &str| set(name).or_else(|| set(&name.to_lowercase()));
+        Self {
+            http: lookup("HTTP_PROXY"),
+            https: lookup("HTTPS_PROXY"),
+            no_proxy: lookup("NO_PROXY"),
+        }
+    }
+
+    /// Routes the client's requests through the proxies, except for the `no_proxy` hosts.
+    /// Proxy URLs reqwest can't parse are skipped with a warning.
+    pub fn apply(&self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        let mut builder = builder.no_proxy();
+        let no_proxy = self
+            .no_proxy
+            .as_deref()
+            .and_then(reqwest::NoProxy::from_string);
+        let proxies = [
+            self.http
+                .as_deref()
+                .map(|url| (url, reqwest::Proxy::http(url))),
+            self.https
+                .as_deref()
+                .map(|url| (url, reqwest::Proxy::https(url))),
+        ];
+        for (url, proxy) in proxies.into_iter().flatten() {
+            match proxy {
+                Ok(proxy) => builder = builder.proxy(proxy.no_proxy(no_proxy.clone())),
+                Err(err) => warn!("Ignoring the proxy {}: {}", url, err),
+            }
+        }
+        builder
+    }
+
+    /// Passes the proxies to a git command. git's curl only reads the lowercase `http_proxy`,
+    /// so the settings are handed over under the lowercase names.
+    pub fn apply_to_git(&self, invocation: Invocation) -> Invocation {
+        let vars = [
+            ("http_proxy", &self.http),
+            ("https_proxy", &self.https),
+            ("no_proxy", &self.no_proxy),
+        ];
+        vars.into_iter()
+            .fold(invocation, |invocation, (name, value)| match value {
+                Some(value) => invocation.env(name, value),
+                None => invocation,
+            })
+    }
+}
+
+/// A `reqwest` client builder that goes through the proxies from the environment
+pub fn client_builder() -> reqwest::ClientBuilder {
+    ProxySettings::from_env().apply(reqwest::Client::builder())
+}
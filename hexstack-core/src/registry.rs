@@ -0,0 +1,300 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+use crate::config::GlobalConfig;
+use crate::error::HexstackError;
+use crate::setup::ProjectTemplate;
+
+/// Index published alongside the official templates. Overridable with `HEXSTACK_REGISTRY_URL`.
+pub const DEFAULT_REGISTRY_URL: &str =
+    "https://raw.githubusercontent.com/Guru901/hexstack-templates/main/index.json";
+
+pub const REGISTRY_URL_ENV: &str = "HEXSTACK_REGISTRY_URL";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegistryIndex {
+    #[serde(default)]
+    pub templates: Vec<RegistryEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RegistryEntry {
+    pub key: String,
+    pub name: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frontend: Option<String>,
+    /// Subdirectory of `url` holding the template, for repositories hosting several templates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdir: Option<String>,
+    /// Expected sha256 of the template tree; downloads that don't match are rejected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// Approximate size of the template download in bytes, checked against the free disk space
+    /// before cloning
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Oldest hexstack release that knows how to scaffold this template
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_version: Option<String>,
+    /// Tauri desktop app wrapping the frontend
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub desktop: bool,
+}
+
+impl RegistryIndex {
+    /// Parses an index in either JSON or TOML form
+    pub fn parse(body: &str) -> Result<Self> {
+        match serde_json::from_str(body) {
+            Ok(index) => Ok(index),
+            Err(json_err) => toml::from_str(body).map_err(|toml_err| {
+                anyhow::anyhow!(
+                    "Template index is neither valid JSON ({}) nor valid TOML ({})",
+                    json_err,
+                    toml_err
+                )
+            }),
+        }
+    }
+
+    /// Returns the templates that can be used by the given hexstack version, keyed like the
+    /// built-in catalog so entries with a matching key replace the built-in ones
+    pub fn compatible_templates(
+        &self,
+        current: &semver::Version,
+    ) -> HashMap<String, ProjectTemplate> {
+        self.templates
+            .iter()
+            .filter(|entry| match &entry.min_version {
+                Some(min) => semver::Version::parse(min).is_ok_and(|min| &min <= current),
+                None => true,
+            })
+            .map(|entry| {
+                (
+                    entry.key.clone(),
+                    ProjectTemplate {
+                        name: entry.name.clone(),
+                        github_url: match &entry.subdir {
+                            Some(subdir) => format!("{}#{}", entry.url, subdir),
+                            None => entry.url.clone(),
+                        },
+                        components: entry.components.iter().map(|c| c.to_lowercase()).collect(),
+                        frontend: entry.frontend.as_ref().map(|f| f.to_lowercase()),
+                        sha256: entry.sha256.clone(),
+                        size: entry.size,
+                        desktop: entry.desktop,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+pub fn registry_url() -> String {
+    std::env::var(REGISTRY_URL_ENV)
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_REGISTRY_URL.to_string())
+}
+
+/// How long a fetched index is used before the registry is asked again
+pub const INDEX_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// The last fetch of a registry index, persisted between runs so `hexstack new` doesn't hit
+/// the network every time
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CachedIndex {
+    /// Seconds since the Unix epoch when the registry was last asked
+    pub checked_at: u64,
+    /// The index's ETag, so an unchanged index isn't downloaded again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// The last index the registry returned, kept across failed fetches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+impl CachedIndex {
+    /// `<cache dir>/registry/<hash of url>.toml`
+    pub fn path(url: &str) -> Result<PathBuf> {
+        let hash = format!("{:x}", Sha256::digest(url.as_bytes()));
+        Ok(GlobalConfig::cache_dir()?
+            .join("registry")
+            .join(format!("{}.toml", &hash[..16])))
+    }
+
+    /// Reads the cached index; a missing or unreadable cache counts as never fetched
+    pub fn load_from(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create '{}'", parent.display()))?;
+        }
+        let contents = toml::to_string(self).context("Failed to serialize the registry index")?;
+        fs::write(path, contents).with_context(|| format!("Failed to write '{}'", path.display()))
+    }
+
+    /// Whether the fetch at `checked_at` is younger than `ttl` at `now`
+    pub fn is_fresh(&self, now: u64, ttl: Duration) -> bool {
+        now.saturating_sub(self.checked_at) < ttl.as_secs()
+    }
+}
+
+/// The index at `url`, from the cache while it is younger than [`INDEX_CACHE_TTL`]
+pub async fn fetch_index(url: &str) -> Result<RegistryIndex> {
+    match CachedIndex::path(url) {
+        Ok(path) => fetch_index_cached(url, &path, INDEX_CACHE_TTL).await,
+        Err(_) => match fetch_body(url, None).await? {
+            Some((body, _)) => RegistryIndex::parse(&body),
+            None => anyhow::bail!("Template index {} returned no content", url),
+        },
+    }
+}
+
+/// The index at `url`, cached in `path`. Every attempt is recorded, including failed ones, so
+/// an unreachable registry costs one request per `ttl` instead of one per run, and the last
+/// index it returned is used in the meantime.
+pub async fn fetch_index_cached(url: &str, path: &Path, ttl: Duration) -> Result<RegistryIndex> {
+    let cached = CachedIndex::load_from(path);
+    let now = unix_now();
+    if cached.is_fresh(now, ttl) {
+        debug!("Using the template index cached in {}", path.display());
+        return match &cached.body {
+            Some(body) => RegistryIndex::parse(body),
+            None => anyhow::bail!("Template index {} was unavailable at the last check", url),
+        };
+    }
+
+    let result = fetch_body(url, cached.etag.as_deref()).await;
+    let entry = match &result {
+        Ok(Some((body, etag))) => CachedIndex {
+            checked_at: now,
+            etag: etag.clone(),
+            body: Some(body.clone()),
+        },
+        // Unchanged since the last fetch, or unreachable: keep what we had
+        Ok(None) | Err(_) => CachedIndex {
+            checked_at: now,
+            ..cached
+        },
+    };
+    if let Err(err) = entry.save_to(path) {
+        debug!("Couldn't cache the template index: {:#}", err);
+    }
+    match (result, &entry.body) {
+        (Err(err), None) => Err(err),
+        (Err(err), Some(body)) => {
+            debug!("Using the last template index from {} ({:#})", url, err);
+            RegistryIndex::parse(body)
+        }
+        (Ok(_), Some(body)) => RegistryIndex::parse(body),
+        (Ok(_), None) => anyhow::bail!("Template index {} returned no content", url),
+    }
+}
+
+/// The index body with its ETag, or `None` when it is unchanged since `etag`
+async fn fetch_body(url: &str, etag: Option<&str>) -> Result<Option<(String, Option<String>)>> {
+    let client = crate::proxy::client_builder()
+        .timeout(std::time::Duration::from_secs(3))
+        .user_agent(format!(
+            "hexstack/{} (+https://github.com/guru901/hexstack)",
+            env!("CARGO_PKG_VERSION")
+        ))
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build HTTP client: {}", e))?;
+
+    let mut request = client.get(url);
+    if let Some(etag) = etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    let response = request.send().await.map_err(|e| {
+        HexstackError::NetworkError(format!("Failed to fetch template index: {}", e))
+    })?;
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(HexstackError::NetworkError(format!(
+            "Template index request failed with status: {}",
+            response.status()
+        ))
+        .into());
+    }
+
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = response.text().await.map_err(|e| {
+        HexstackError::NetworkError(format!("Failed to read template index: {}", e))
+    })?;
+    Ok(Some((body, etag)))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Fetches the official catalog plus any registries from the global config, returning `None`
+/// when none of them are reachable so callers can keep using the built-in templates. Only the
+/// registries someone added are worth a warning; the official one being away is routine.
+pub async fn load_remote_templates(
+    config: &GlobalConfig,
+) -> Option<HashMap<String, ProjectTemplate>> {
+    let current = current_version();
+
+    let official = registry_url();
+    let mut urls = vec![official.clone()];
+    urls.extend(config.registries.iter().cloned());
+
+    let mut templates = HashMap::new();
+    let mut reached_any = false;
+    for url in &urls {
+        match fetch_index(url).await {
+            Ok(index) => {
+                reached_any = true;
+                templates.extend(index.compatible_templates(&current));
+            }
+            Err(e) if *url == official => debug!("Template registry {url} unavailable ({e})"),
+            Err(e) => warn!("Template registry {url} unavailable ({e})"),
+        }
+    }
+
+    if !reached_any {
+        debug!("Using built-in templates");
+        return None;
+    }
+
+    Some(templates)
+}
+
+/// Templates registered individually with `hexstack template add` or `--template-url`
+pub fn custom_templates(entries: &[RegistryEntry]) -> HashMap<String, ProjectTemplate> {
+    RegistryIndex {
+        templates: entries.to_vec(),
+    }
+    .compatible_templates(&current_version())
+}
+
+fn current_version() -> semver::Version {
+    semver::Version::parse(env!("CARGO_PKG_VERSION")).expect("crate version is valid semver")
+}
@@ -0,0 +1,71 @@
+//! Publishing a new project to GitHub through the `gh` CLI: the repository is created, added
+//! as `origin` and the initial commit pushed to it.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::runner::{CommandRunner, Invocation};
+
+/// How to get the GitHub CLI when it isn't on the PATH
+pub const GH_INSTALL: &str = "see https://cli.github.com, then run `gh auth login`";
+
+/// Who can see the created repository
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    #[default]
+    Private,
+    Public,
+}
+
+impl Visibility {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "private" => Some(Visibility::Private),
+            "public" => Some(Visibility::Public),
+            _ => None,
+        }
+    }
+
+    pub fn flag(self) -> &'static str {
+        match self {
+            Visibility::Private => "--private",
+            Visibility::Public => "--public",
+        }
+    }
+}
+
+/// Creates the GitHub repository `name` for the committed project in `dir` and pushes it,
+/// returning the repository's URL as `gh` prints it
+pub async fn create_github_repo(
+    runner: &dyn CommandRunner,
+    dir: &Path,
+    name: &str,
+    visibility: Visibility,
+) -> Result<String> {
+    let output = match runner
+        .run(
+            &Invocation::new("gh")
+                .args(["repo", "create", name, visibility.flag()])
+                .args(["--source", ".", "--remote", "origin", "--push"])
+                .current_dir(dir),
+        )
+        .await
+    {
+        Ok(output) => output,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            anyhow::bail!("gh isn't installed; {}", GH_INSTALL)
+        }
+        Err(err) => return Err(err).context("Failed to execute gh repo create"),
+    };
+
+    if !output.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "`gh repo create` failed (is `gh auth login` done?): {}",
+            stderr.trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
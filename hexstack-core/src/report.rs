@@ -0,0 +1,126 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::manifest::SKIPPED_DIRS;
+use crate::runner::{CommandRunner, Invocation, RunFuture};
+
+/// What a scaffold did, returned by `ProjectSetupBuilder::create` and printed last by
+/// `hexstack new --json`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CreateReport {
+    pub project_path: PathBuf,
+    /// The project template, or the first service's with `--services`
+    pub template: Option<TemplateReport>,
+    /// Every file of the new project relative to its directory, without build output,
+    /// dependencies and git metadata
+    pub files_written: Vec<PathBuf>,
+    pub commands: Vec<CommandRecord>,
+    /// How long each progress step took, in the order they ran
+    pub steps: Vec<StepRecord>,
+    pub warnings: Vec<String>,
+    pub duration_ms: u64,
+}
+
+/// What a scaffold is going to do, worked out by `ProjectSetupBuilder::preview` before
+/// anything is fetched or written
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Preview {
+    pub project_path: PathBuf,
+    /// The templates to clone: the project's, then those of the other frontend apps, or one
+    /// per service with `--services`. Empty when no template matches.
+    pub templates: Vec<String>,
+    /// The commands the scaffold runs, in order
+    pub commands: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateReport {
+    pub name: String,
+    pub url: String,
+    pub commit: Option<String>,
+}
+
+/// A git, cargo or docker command the scaffold ran
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommandRecord {
+    pub command: String,
+    pub success: bool,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StepRecord {
+    pub name: String,
+    pub duration_ms: u64,
+}
+
+/// Passes commands on to another runner, noting each one with how long it took
+#[derive(Debug)]
+pub struct RecordingRunner {
+    inner: Arc<dyn CommandRunner>,
+    commands: Mutex<Vec<CommandRecord>>,
+}
+
+impl RecordingRunner {
+    pub fn new(inner: Arc<dyn CommandRunner>) -> Self {
+        Self {
+            inner,
+            commands: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn commands(&self) -> Vec<CommandRecord> {
+        self.commands.lock().map(|c| c.clone()).unwrap_or_default()
+    }
+}
+
+impl CommandRunner for RecordingRunner {
+    fn run<'a>(&'a self, invocation: &'a Invocation) -> RunFuture<'a> {
+        Box::pin(async move {
+            let started = Instant::now();
+            let output = self.inner.run(invocation).await;
+            if let Ok(mut commands) = self.commands.lock() {
+                commands.push(CommandRecord {
+                    command: invocation.to_string(),
+                    success: output.as_ref().is_ok_and(|o| o.success()),
+                    duration_ms: elapsed_ms(started),
+                });
+            }
+            output
+        })
+    }
+}
+
+pub fn elapsed_ms(started: Instant) -> u64 {
+    started.elapsed().as_millis().try_into().unwrap_or(u64::MAX)
+}
+
+/// The files below `dir`, relative to it and sorted, skipping build output and git metadata
+pub fn list_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files);
+    files.sort();
+    files
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            let skipped = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| SKIPPED_DIRS.contains(&n));
+            if !skipped {
+                collect_files(root, &path, files);
+            }
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            files.push(relative.to_path_buf());
+        }
+    }
+}
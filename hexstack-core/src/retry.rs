@@ -0,0 +1,117 @@
+use std::future::Future;
+use std::time::Duration;
+use tracing::warn;
+
+/// How often a network operation is attempted and how long to wait in between. The wait doubles
+/// after every attempt: 500ms, then 1s with the defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first one
+    pub attempts: u32,
+    /// Wait before the second attempt
+    pub initial_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            initial_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Tries once and never waits
+    pub const fn none() -> Self {
+        Self {
+            attempts: 1,
+            initial_delay: Duration::ZERO,
+        }
+    }
+
+    /// The wait after the `attempt`th failure, counting from 1
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.initial_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+    }
+
+    /// Runs `operation` until `should_retry` rejects its outcome or the attempts run out, and
+    /// returns the last outcome. `what` names the operation in the retry warnings.
+    pub async fn run<T, F, Fut>(
+        &self,
+        what: &str,
+        mut operation: F,
+        should_retry: impl Fn(&T) -> bool,
+    ) -> T
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let mut attempt = 1;
+        loop {
+            let outcome = operation().await;
+            if attempt >= self.attempts || !should_retry(&outcome) {
+                return outcome;
+            }
+            let delay = self.delay(attempt);
+            warn!(
+                "{} failed, retrying in {:.1}s (attempt {} of {})",
+                what,
+                delay.as_secs_f32(),
+                attempt + 1,
+                self.attempts
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Whether git's stderr describes a network hiccup worth retrying, rather than a missing
+/// repository, bad credentials or an unknown revision
+pub fn is_transient_git_error(stderr: &str) -> bool {
+    const TRANSIENT: &[&str] = &[
+        "could not resolve host",
+        "connection timed out",
+        "operation timed out",
+        "connection reset",
+        "connection refused",
+        "network is unreachable",
+        "network unreachable",
+        "temporary failure",
+        "early eof",
+        "rpc failed",
+        "the remote end hung up unexpectedly",
+        "gnutls_handshake",
+        "ssl_read",
+        "http 429",
+        "error: 429",
+        "error: 500",
+        "error: 502",
+        "error: 503",
+        "error: 504",
+    ];
+    let stderr = stderr.to_lowercase();
+    TRANSIENT.iter().any(|pattern| stderr.contains(pattern))
+}
+
+/// Whether an HTTP status is worth retrying: timeouts, rate limits and server errors
+pub fn is_transient_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::REQUEST_TIMEOUT
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status.is_server_error()
+}
+
+/// Whether a request failed before getting an answer: timeouts and connection errors
+pub fn is_transient_request(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_connect() || err.is_request()
+}
+
+/// [`RetryPolicy::run`]'s `should_retry` for HTTP requests
+pub fn should_retry_response(outcome: &Result<reqwest::Response, reqwest::Error>) -> bool {
+    match outcome {
+        Ok(response) => is_transient_status(response.status()),
+        Err(err) => is_transient_request(err),
+    }
+}
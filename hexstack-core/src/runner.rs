@@ -0,0 +1,148 @@
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use tracing::{debug, trace};
+
+/// A program to run with its arguments, working directory and environment
+#[derive(Debug, Clone, Default)]
+pub struct Invocation {
+    pub program: String,
+    pub args: Vec<OsString>,
+    pub dir: Option<PathBuf>,
+    pub envs: Vec<(OsString, OsString)>,
+    /// Let the program write to the terminal instead of capturing its output
+    pub inherit_output: bool,
+}
+
+impl Invocation {
+    pub fn new(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn arg(mut self, arg: impl AsRef<OsStr>) -> Self {
+        self.args.push(arg.as_ref().to_os_string());
+        self
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.args
+            .extend(args.into_iter().map(|a| a.as_ref().to_os_string()));
+        self
+    }
+
+    pub fn current_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn env(mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> Self {
+        self.envs
+            .push((key.as_ref().to_os_string(), value.as_ref().to_os_string()));
+        self
+    }
+
+    pub fn inherit_output(mut self) -> Self {
+        self.inherit_output = true;
+        self
+    }
+
+    /// The arguments as text, for matching in mocks and for messages
+    pub fn arg_strings(&self) -> Vec<String> {
+        self.args
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+}
+
+impl fmt::Display for Invocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.program)?;
+        for arg in &self.args {
+            write!(f, " {}", arg.to_string_lossy())?;
+        }
+        Ok(())
+    }
+}
+
+/// What a finished program exited with and wrote. Output is empty when it was inherited.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandOutput {
+    /// The exit code, or `None` when the program was killed by a signal
+    pub code: Option<i32>,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+impl CommandOutput {
+    pub fn success(&self) -> bool {
+        self.code == Some(0)
+    }
+
+    /// Describes how the program exited, e.g. `exit status: 1`
+    pub fn status(&self) -> String {
+        match self.code {
+            Some(code) => format!("exit status: {}", code),
+            None => "a signal".to_string(),
+        }
+    }
+}
+
+pub type RunFuture<'a> = Pin<Box<dyn Future<Output = io::Result<CommandOutput>> + Send + 'a>>;
+
+/// Runs the git, cargo, docker and npm commands hexstack needs, so tests and embedding tools
+/// can swap in their own. A program that isn't installed fails with
+/// [`io::ErrorKind::NotFound`].
+pub trait CommandRunner: Send + Sync + fmt::Debug {
+    fn run<'a>(&'a self, invocation: &'a Invocation) -> RunFuture<'a>;
+}
+
+/// Runs commands as child processes, killed when the scaffold is cancelled
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run<'a>(&'a self, invocation: &'a Invocation) -> RunFuture<'a> {
+        Box::pin(async move {
+            debug!("Running `{}`", invocation);
+            let mut command = tokio::process::Command::new(&invocation.program);
+            command
+                .args(&invocation.args)
+                .envs(invocation.envs.iter().map(|(k, v)| (k, v)))
+                .kill_on_drop(true);
+            if let Some(dir) = &invocation.dir {
+                command.current_dir(dir);
+            }
+
+            if invocation.inherit_output {
+                let status = command.status().await?;
+                return Ok(CommandOutput {
+                    code: status.code(),
+                    ..CommandOutput::default()
+                });
+            }
+            let output = command.output().await?;
+            trace!(
+                "`{}` exited with {:?}: {}",
+                invocation,
+                output.status.code(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            Ok(CommandOutput {
+                code: output.status.code(),
+                stdout: output.stdout,
+                stderr: output.stderr,
+            })
+        })
+    }
+}
@@ -0,0 +1,370 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::extras::{ComposeService, Database, GeneratedFile, WriteMode, render_compose};
+use crate::setup::{ADDON_COMPONENTS, COMPONENTS};
+
+/// Directory the services of a `--services` project are generated into
+pub const SERVICES_DIR: &str = "services";
+
+/// Crate every service depends on for the types they exchange
+pub const SHARED_DIR: &str = "shared";
+
+/// Port each service listens on inside its container
+const SERVICE_PORT: u16 = 3000;
+
+/// A service generated into `services/<name>` by `hexstack new --services`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Service {
+    pub name: String,
+    pub components: Vec<String>,
+    /// Template commit the service was copied from; unset for services without a framework
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+impl Service {
+    pub fn has(&self, component: &str) -> bool {
+        self.components.iter().any(|c| c == component)
+    }
+
+    /// Whether the service is built from a template, i.e. has Ripress, Wynd or Lume
+    pub fn has_framework(&self) -> bool {
+        self.components
+            .iter()
+            .any(|c| COMPONENTS.contains(&c.as_str()))
+    }
+
+    /// Whether the service answers HTTP or WebSocket requests on [`SERVICE_PORT`]
+    pub fn serves(&self) -> bool {
+        self.has("ripress") || self.has("wynd")
+    }
+
+    /// The binary its container runs: the jobs worker for services without a framework
+    pub fn binary(&self) -> &str {
+        if !self.has_framework() && self.has("jobs") {
+            "worker"
+        } else {
+            &self.name
+        }
+    }
+
+    pub fn dir(&self) -> PathBuf {
+        Path::new(SERVICES_DIR).join(&self.name)
+    }
+}
+
+/// Parses `--services` values like `api,ws-gateway,worker` or `api=ripress+lume,worker`
+pub fn parse_services(value: &str) -> Result<Vec<Service>> {
+    let mut services: Vec<Service> = Vec::new();
+    for item in value.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+        let (name, components): (String, Vec<String>) = match item.split_once('=') {
+            Some((name, components)) => (
+                name.trim().to_string(),
+                components
+                    .split('+')
+                    .map(|c| c.trim().to_lowercase())
+                    .collect(),
+            ),
+            None => (
+                item.to_string(),
+                infer_components(item)
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect(),
+            ),
+        };
+
+        let valid_name = name.starts_with(|c: char| c.is_ascii_lowercase())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !valid_name {
+            anyhow::bail!(
+                "Invalid service name '{}'. Use lowercase letters, digits and hyphens, starting with a letter",
+                name
+            );
+        }
+        if name == SHARED_DIR || services.iter().any(|s| s.name == name) {
+            anyhow::bail!("Service name '{}' is used twice", name);
+        }
+        for component in &components {
+            if !COMPONENTS.contains(&component.as_str())
+                && !ADDON_COMPONENTS.contains(&component.as_str())
+            {
+                anyhow::bail!(
+                    "Invalid component '{}' for service '{}'. Valid values: {}",
+                    component,
+                    name,
+                    COMPONENTS
+                        .iter()
+                        .chain(&ADDON_COMPONENTS)
+                        .copied()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+        }
+        if components.iter().any(|c| c == "graphql") && !components.iter().any(|c| c == "ripress") {
+            anyhow::bail!(
+                "Service '{}': GraphQL is served by Ripress; add ripress",
+                name
+            );
+        }
+
+        services.push(Service {
+            name,
+            components,
+            commit: None,
+        });
+    }
+
+    if services.is_empty() {
+        anyhow::bail!("--services needs at least one service name");
+    }
+    if !services.iter().any(Service::has_framework) {
+        anyhow::bail!("At least one service needs Ripress, Wynd or Lume");
+    }
+    Ok(services)
+}
+
+/// Components for a service given by name alone, going by the words of its name: WebSocket
+/// gateways get Wynd, workers a Redis-backed job queue, and everything else a Ripress API
+pub fn infer_components(name: &str) -> &'static [&'static str] {
+    let has_word = |words: &[&str]| name.split('-').any(|word| words.contains(&word));
+    if has_word(&["ws", "gateway", "socket", "websocket", "realtime"]) {
+        &["wynd"]
+    } else if has_word(&["worker", "jobs"]) {
+        &["redis", "jobs"]
+    } else {
+        &["ripress"]
+    }
+}
+
+/// Every component used by at least one of the services
+pub fn all_components(services: &[Service]) -> Vec<String> {
+    let mut components: Vec<String> = Vec::new();
+    for component in services.iter().flat_map(|s| &s.components) {
+        if !components.contains(component) {
+            components.push(component.clone());
+        }
+    }
+    components
+}
+
+/// Crate for a service without a framework. With the jobs component the worker binary is the
+/// whole service, otherwise it gets a `main.rs` the add-ons are wired into.
+pub fn bare_service_files(service: &Service) -> Vec<GeneratedFile> {
+    let dir = service.dir();
+    let default_run = if service.has("jobs") {
+        "default-run = \"worker\"\n"
+    } else {
+        ""
+    };
+    let mut files = vec![GeneratedFile {
+        path: dir.join("Cargo.toml"),
+        contents: format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n{}\n[dependencies]\ntokio = {{ version = \"1\", features = [\"full\"] }}\n",
+            service.name, default_run
+        ),
+        mode: WriteMode::Create,
+    }];
+    if !service.has("jobs") {
+        files.push(GeneratedFile {
+            path: dir.join("src/main.rs"),
+            contents: format!(
+                "#[tokio::main]\nasync fn main() {{\n    println!(\"{} is running\");\n}}\n",
+                service.name
+            ),
+            mode: WriteMode::Create,
+        });
+    }
+    files
+}
+
+/// What the services share: the `shared` crate each of them depends on, the root workspace,
+/// a Dockerfile per service and a docker-compose.yml running them with their infrastructure
+pub fn shared_files(
+    project_name: &str,
+    services: &[Service],
+    database: Option<Database>,
+) -> Vec<GeneratedFile> {
+    let mut files = vec![
+        GeneratedFile {
+            path: Path::new(SHARED_DIR).join("Cargo.toml"),
+            contents: "[package]\nname = \"shared\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\nserde = { version = \"1\", features = [\"derive\"] }\n".to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: Path::new(SHARED_DIR).join("src/lib.rs"),
+            contents: SHARED_LIB.to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: PathBuf::from("Cargo.toml"),
+            contents: services
+                .iter()
+                .map(|s| s.dir().display().to_string())
+                .chain([SHARED_DIR.to_string()])
+                .collect::<Vec<_>>()
+                .join("\n"),
+            mode: WriteMode::Workspace,
+        },
+        GeneratedFile {
+            path: PathBuf::from(".gitignore"),
+            contents: "target\n.env\n".to_string(),
+            mode: WriteMode::AppendLines,
+        },
+        // The images are built from the root so they can see the shared crate
+        GeneratedFile {
+            path: PathBuf::from(".dockerignore"),
+            contents: "target\n.env\n".to_string(),
+            mode: WriteMode::Create,
+        },
+        GeneratedFile {
+            path: PathBuf::from("docker-compose.yml"),
+            contents: compose_file(project_name, services, database),
+            mode: WriteMode::Create,
+        },
+    ];
+
+    for service in services {
+        files.push(GeneratedFile {
+            path: service.dir().join("Cargo.toml"),
+            contents: format!("shared = {{ path = \"../../{}\" }}\n", SHARED_DIR),
+            mode: WriteMode::Dependencies {
+                table: "dependencies",
+            },
+        });
+        files.push(GeneratedFile {
+            path: service.dir().join("Dockerfile"),
+            contents: service_dockerfile(service),
+            mode: WriteMode::Create,
+        });
+    }
+    files
+}
+
+/// The services, each published on its own host port from 3000 up, plus Redis and the
+/// database when a service uses them
+pub fn compose_file(
+    project_name: &str,
+    services: &[Service],
+    database: Option<Database>,
+) -> String {
+    let db_name = project_name.replace(['-', '.'], "_");
+    let database = database.filter(|_| services.iter().any(|s| s.has("lume")));
+    let mut compose: Vec<ComposeService> = Vec::new();
+    compose.extend(database.and_then(|d| d.compose_service(&db_name)));
+    if services.iter().any(|s| s.has("redis")) {
+        compose.push(ComposeService {
+            name: "redis".to_string(),
+            image: Some("redis:7-alpine".to_string()),
+            build: None,
+            dockerfile: None,
+            environment: Vec::new(),
+            ports: vec!["6379:6379".to_string()],
+            volumes: Vec::new(),
+            depends_on: Vec::new(),
+            command: None,
+            entrypoint: Vec::new(),
+        });
+    }
+
+    let mut host_port = SERVICE_PORT;
+    for service in services {
+        let mut environment = Vec::new();
+        let mut depends_on = Vec::new();
+        if let Some(database) = database
+            && service.has("lume")
+        {
+            environment.push((
+                "DATABASE_URL".to_string(),
+                database.url_on_host(&db_name, "db"),
+            ));
+            if database.compose_service(&db_name).is_some() {
+                depends_on.push("db".to_string());
+            }
+        }
+        if service.has("redis") {
+            environment.push(("REDIS_URL".to_string(), "redis://redis:6379".to_string()));
+            depends_on.push("redis".to_string());
+        }
+        let mut ports = Vec::new();
+        if service.serves() {
+            ports.push(format!("{}:{}", host_port, SERVICE_PORT));
+            host_port += 1;
+        }
+        compose.push(ComposeService {
+            name: service.name.clone(),
+            image: None,
+            build: Some(".".to_string()),
+            dockerfile: Some(service.dir().join("Dockerfile").display().to_string()),
+            environment,
+            ports,
+            volumes: Vec::new(),
+            depends_on,
+            command: None,
+            entrypoint: Vec::new(),
+        });
+    }
+
+    render_compose(&compose)
+}
+
+/// Multi-stage build of one workspace member, run from the project root
+fn service_dockerfile(service: &Service) -> String {
+    let protoc = if service.has("grpc") {
+        "RUN apt-get update && apt-get install -y --no-install-recommends protobuf-compiler\n"
+    } else {
+        ""
+    };
+    let expose = if service.serves() {
+        format!("EXPOSE {}\n", SERVICE_PORT)
+    } else {
+        String::new()
+    };
+    let binary = service.binary();
+
+    format!(
+        r#"# Built from the project root: docker build -f {dir}/Dockerfile .
+FROM rust:1 AS build
+{protoc}WORKDIR /app
+COPY . .
+RUN cargo build --release -p {name}
+
+FROM debian:bookworm-slim
+RUN apt-get update \
+    && apt-get install -y --no-install-recommends ca-certificates \
+    && rm -rf /var/lib/apt/lists/*
+WORKDIR /app
+COPY --from=build /app/target/release/{binary} /usr/local/bin/{binary}
+{expose}CMD ["{binary}"]
+"#,
+        dir = service.dir().display(),
+        name = service.name,
+    )
+}
+
+const SHARED_LIB: &str = r#"//! Types shared by every service. Each service depends on this crate, so messages passed
+//! between them are defined once.
+
+use serde::{Deserialize, Serialize};
+
+/// Health report any service can answer with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceStatus {
+    pub service: String,
+    pub healthy: bool,
+}
+
+impl ServiceStatus {
+    pub fn healthy(service: &str) -> Self {
+        Self {
+            service: service.to_string(),
+            healthy: true,
+        }
+    }
+}
+"#;
@@ -0,0 +1,2257 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::archive;
+use crate::error::HexstackError;
+use crate::examples::Example;
+use crate::extras::{self, EXTRA_REQUIREMENTS, Extras, ProjectLayout, Styling, tailwind_target};
+use crate::hosts::Host;
+use crate::i18n;
+use crate::integrity;
+use crate::lockfile::{LOCK_FILE, LockFile, LockedTemplate};
+use crate::manifest::{self, TemplateManifest};
+use crate::package_manager::PackageManager;
+use crate::preflight;
+use crate::progress::{EventHooks, Observed, ProgressReporter, ScaffoldEvent, Step};
+use crate::proxy::ProxySettings;
+use crate::remote::{self, Visibility};
+use crate::report::{self, CreateReport, Preview, RecordingRunner};
+use crate::retry::{RetryPolicy, is_transient_git_error};
+use crate::runner::{CommandOutput, CommandRunner, Invocation, SystemRunner};
+use crate::services::{self, Service};
+use crate::t;
+use crate::validation;
+use crate::workspace::{Workspace, copy_dir};
+
+/// Backend components hexstack can scaffold
+pub const COMPONENTS: [&str; 3] = ["ripress", "wynd", "lume"];
+
+/// Components generated on top of the template rather than picking it; they need one of
+/// [`COMPONENTS`] as the backend they're added to
+pub const ADDON_COMPONENTS: [&str; 5] = ["redis", "jobs", "graphql", "grpc", "scheduler"];
+
+/// Frontends with official templates
+pub const FRONTENDS: [&str; 11] = [
+    "react",
+    "svelte",
+    "solid",
+    "sveltekit",
+    "nextjs",
+    "astro",
+    "htmx",
+    "leptos",
+    "yew",
+    "dioxus",
+    "vanilla",
+];
+
+/// Components that can serve a frontend; frontend templates need at least one of them
+const FRONTEND_HOSTS: [&str; 2] = ["ripress", "wynd"];
+
+/// Branch new repositories start on when neither hexstack nor git is configured otherwise
+pub const DEFAULT_BRANCH: &str = "main";
+
+/// Frontends built with npm
+pub const NODE_FRONTENDS: [&str; 7] = [
+    "react",
+    "svelte",
+    "solid",
+    "sveltekit",
+    "nextjs",
+    "astro",
+    "vanilla",
+];
+
+/// Server-rendered frontends are templates and assets inside the Ripress app itself
+const SERVER_RENDERED_FRONTENDS: [&str; 1] = ["htmx"];
+
+/// Directory holding the frontend apps of a project with more than one frontend
+pub const APPS_DIR: &str = "apps";
+
+/// Seconds to wait for a freshly started database container to accept connections
+const DATABASE_READY_ATTEMPTS: u32 = 60;
+
+/// A frontend generated into `apps/<name>` when a project has more than one
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FrontendApp {
+    pub name: String,
+    pub frontend: String,
+    /// Template commit the app was copied from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ComponentConfig {
+    pub description: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProjectTemplate {
+    pub name: String,
+    /// Repository URL, optionally followed by `#subdir` when the template lives in a subdirectory
+    pub github_url: String,
+    pub components: Vec<String>,
+    pub frontend: Option<String>,
+    /// Expected checksum of the template tree, see [`crate::integrity::tree_checksum`]
+    pub sha256: Option<String>,
+    /// Approximate download size in bytes, from the registry; see [`crate::preflight::required_space`]
+    pub size: Option<u64>,
+    /// The frontend is wrapped in a Tauri desktop shell
+    pub desktop: bool,
+}
+
+impl ProjectTemplate {
+    /// Splits the URL into the repository to clone and the subdirectory to materialize
+    pub fn source(&self) -> (&str, Option<&str>) {
+        match self.github_url.split_once('#') {
+            Some((repo, subdir)) if !subdir.trim_matches('/').is_empty() => {
+                (repo, Some(subdir.trim_matches('/')))
+            }
+            Some((repo, _)) => (repo, None),
+            None => (self.github_url.as_str(), None),
+        }
+    }
+}
+
+pub struct ProjectSetup {
+    pub name: String,
+    pub selected_components: Vec<String>,
+    pub selected_frontend: Option<String>,
+    /// Catalog key chosen explicitly (e.g. a custom template), bypassing component matching
+    pub template_key: Option<String>,
+    /// Template commit to check out instead of the latest one, set when reproducing a lock file
+    pub pinned_commit: Option<String>,
+    /// Continue when a template doesn't match its published checksum
+    pub allow_unverified: bool,
+    /// Wrap the frontend in a Tauri desktop app
+    pub desktop: bool,
+    /// Frontend apps laid out under `apps/`, set when more than one frontend is selected. The
+    /// first one comes from the project template and matches `selected_frontend`.
+    pub apps: Vec<FrontendApp>,
+    /// Database and other additions generated on top of the template
+    pub extras: Extras,
+    /// Example app replacing the template's main.rs
+    pub example: Option<Example>,
+    /// Start the database's docker-compose service and load its schema once the project exists
+    pub start_database: bool,
+    /// Services generated under `services/` instead of a single backend
+    pub services: Vec<Service>,
+    /// Directory the project directory is created in; the current directory when empty
+    pub target_dir: PathBuf,
+    /// Leaves the project directory of a failed scaffold on disk instead of removing it
+    pub keep_partial: bool,
+    /// Aborts the scaffold when cancelled, see [`ProjectSetup::build`]
+    pub cancel: CancellationToken,
+    /// Runs git, cargo and docker for the scaffold
+    pub runner: Arc<dyn CommandRunner>,
+    /// How often a clone that failed on a network error is attempted
+    pub retry: RetryPolicy,
+    /// Download the template archive when git isn't installed or cloning keeps failing
+    pub archive_fallback: bool,
+    /// Run the package manager's install in Node frontends, so their dev server starts right away
+    pub install_frontend: bool,
+    /// Package manager for Node frontends; detected from each frontend's lock file when unset
+    pub package_manager: Option<PackageManager>,
+    /// Stage everything and commit it once the project is complete
+    pub initial_commit: bool,
+    /// Branch `git init` starts on; git's `init.defaultBranch` or `main` when unset
+    pub default_branch: Option<String>,
+    /// Give the project a repository of its own; off to keep it in an enclosing repository
+    pub reinit_git: bool,
+    /// Create a GitHub repository with `gh` and push the initial commit to it
+    pub create_remote: Option<Visibility>,
+    /// Called with every [`ScaffoldEvent`] besides the reporter
+    pub hooks: EventHooks,
+    config: HashMap<String, ComponentConfig>,
+    templates: HashMap<String, ProjectTemplate>,
+}
+
+impl ProjectSetup {
+    pub async fn new(
+        name: String,
+        selected_components: Vec<String>,
+        selected_frontend: Option<String>,
+    ) -> Self {
+        // Normalize component names to lowercase for case-insensitive matching
+        let normalized_components: Vec<String> = selected_components
+            .into_iter()
+            .map(|comp| comp.to_lowercase())
+            .collect();
+
+        Self {
+            name,
+            selected_frontend,
+            selected_components: normalized_components,
+            template_key: None,
+            pinned_commit: None,
+            allow_unverified: false,
+            desktop: false,
+            apps: Vec::new(),
+            example: None,
+            start_database: false,
+            services: Vec::new(),
+            target_dir: PathBuf::new(),
+            keep_partial: false,
+            cancel: CancellationToken::new(),
+            runner: Arc::new(SystemRunner),
+            retry: RetryPolicy::default(),
+            archive_fallback: true,
+            install_frontend: true,
+            package_manager: None,
+            initial_commit: false,
+            default_branch: None,
+            reinit_git: true,
+            create_remote: None,
+            hooks: EventHooks::default(),
+            extras: Extras::default(),
+            config: Self::load_component_config(),
+            templates: Self::load_templates().await,
+        }
+    }
+
+    /// Where the project is generated: `target_dir/name`
+    pub fn project_path(&self) -> PathBuf {
+        self.target_dir.join(&self.name)
+    }
+
+    /// The project directory, which every generated file is written into
+    pub fn workspace(&self) -> Workspace {
+        Workspace::new(self.project_path())
+    }
+
+    /// Directory the project directory is created in
+    fn parent_dir(&self) -> &Path {
+        if self.target_dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            &self.target_dir
+        }
+    }
+
+    /// The components of [`ProjectSetup::load_component_config`], frameworks before add-ons in
+    /// the order they are offered, and any others after them by key
+    pub fn component_choices() -> Vec<(String, ComponentConfig)> {
+        let offered: Vec<&str> = COMPONENTS
+            .iter()
+            .chain(&ADDON_COMPONENTS)
+            .copied()
+            .collect();
+        let mut choices: Vec<(String, ComponentConfig)> =
+            Self::load_component_config().into_iter().collect();
+        choices.sort_by_cached_key(|(key, _)| {
+            (
+                offered
+                    .iter()
+                    .position(|offered| offered == key)
+                    .unwrap_or(offered.len()),
+                key.clone(),
+            )
+        });
+        choices
+    }
+
+    pub fn load_component_config() -> HashMap<String, ComponentConfig> {
+        HashMap::from([
+            (
+                "ripress".to_string(),
+                ComponentConfig {
+                    description: "An HTTP Framework with best in class developer experience"
+                        .to_string(),
+                },
+            ),
+            (
+                "wynd".to_string(),
+                ComponentConfig {
+                    description: "An Event Driven WebSocket library".to_string(),
+                },
+            ),
+            (
+                "lume".to_string(),
+                ComponentConfig {
+                    description: "A simple and intuitive Query Builder inspired by Drizzle"
+                        .to_string(),
+                },
+            ),
+            (
+                "redis".to_string(),
+                ComponentConfig {
+                    description: "A Redis client with caching helpers".to_string(),
+                },
+            ),
+            (
+                "jobs".to_string(),
+                ComponentConfig {
+                    description: "Background jobs run by a worker binary".to_string(),
+                },
+            ),
+            (
+                "graphql".to_string(),
+                ComponentConfig {
+                    description: "A GraphQL API with GraphiQL and typed frontend clients"
+                        .to_string(),
+                },
+            ),
+            (
+                "grpc".to_string(),
+                ComponentConfig {
+                    description: "A tonic gRPC service running beside the HTTP server".to_string(),
+                },
+            ),
+            (
+                "scheduler".to_string(),
+                ComponentConfig {
+                    description: "Recurring tasks on cron schedules".to_string(),
+                },
+            ),
+        ])
+    }
+
+    pub async fn load_templates() -> HashMap<String, ProjectTemplate> {
+        Self::template_matrix().into_iter().collect()
+    }
+
+    /// Every official template: each combination of components, alone and with each frontend,
+    /// plus a Tauri desktop variant of every client-side frontend template. Larger component
+    /// sets come first, which is the order `determine_template` falls back in.
+    pub fn template_matrix() -> Vec<(String, ProjectTemplate)> {
+        let mut component_sets: Vec<Vec<&str>> = (1..1u32 << COMPONENTS.len())
+            .map(|mask| {
+                COMPONENTS
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| mask & (1 << i) != 0)
+                    .map(|(_, component)| *component)
+                    .collect()
+            })
+            .collect();
+        component_sets.sort_by_key(|set| std::cmp::Reverse(set.len()));
+
+        let frontends = std::iter::once(None).chain(FRONTENDS.iter().map(|f| Some(*f)));
+
+        let mut templates = Vec::new();
+        for frontend in frontends {
+            for set in &component_sets {
+                if let Some(frontend) = frontend
+                    && !set.iter().any(|c| frontend_hosts(frontend).contains(c))
+                {
+                    continue;
+                }
+
+                let names: Vec<String> = set.iter().map(|c| display_name(c)).collect();
+                let (key, repo, name) = match frontend {
+                    None if set.len() == 1 => (
+                        set[0].to_string(),
+                        format!("{}-only", set[0]),
+                        format!("{} Basic", names[0]),
+                    ),
+                    None => (set.join("_"), set.join("-"), names.join(" + ")),
+                    Some(frontend) => {
+                        let key = format!("{}-{}", set.join("-"), frontend);
+                        let name = format!("{} + {}", names.join(" + "), display_name(frontend));
+                        (key.clone(), key, name)
+                    }
+                };
+
+                let template = ProjectTemplate {
+                    name,
+                    github_url: format!("https://github.com/Guru901/{}", repo),
+                    components: set.iter().map(|c| c.to_string()).collect(),
+                    frontend: frontend.map(str::to_string),
+                    sha256: None,
+                    size: None,
+                    desktop: false,
+                };
+
+                if frontend.is_some_and(|f| !is_server_rendered(f)) {
+                    templates.push((
+                        format!("{}-tauri", key),
+                        ProjectTemplate {
+                            name: format!("{} + Tauri", template.name),
+                            github_url: format!("{}-tauri", template.github_url),
+                            desktop: true,
+                            ..template.clone()
+                        },
+                    ));
+                }
+                templates.push((key, template));
+            }
+        }
+
+        templates
+    }
+
+    pub fn determine_template(&self) -> Option<&ProjectTemplate> {
+        if let Some(key) = &self.template_key {
+            return self.templates.get(key);
+        }
+
+        self.template_for_frontend(self.selected_frontend.as_deref())
+    }
+
+    /// Finds the template for the selected components paired with `selected_frontend`
+    fn template_for_frontend(&self, selected_frontend: Option<&str>) -> Option<&ProjectTemplate> {
+        self.template_for(&self.selected_components, selected_frontend)
+    }
+
+    /// Finds the template for `components` paired with `selected_frontend`
+    fn template_for(
+        &self,
+        components: &[String],
+        selected_frontend: Option<&str>,
+    ) -> Option<&ProjectTemplate> {
+        match_template(&self.templates, components, selected_frontend, self.desktop)
+    }
+
+    /// Adds templates from an external catalog, replacing built-in entries with the same key
+    pub fn extend_templates(&mut self, templates: HashMap<String, ProjectTemplate>) {
+        self.templates.extend(templates);
+    }
+
+    /// Scaffolds the project. When `cancel` fires mid-way, the running git or cargo process is
+    /// killed, the partial project directory removed and a `Cancelled` error returned. When a
+    /// step fails, the project directory is removed too unless `keep_partial` is set, so running
+    /// hexstack again doesn't stop at the existing directory.
+    pub async fn build(mut self, reporter: &dyn ProgressReporter) -> Result<CreateReport> {
+        let started = std::time::Instant::now();
+        let cancel = self.cancel.clone();
+        let project_path = self.project_path();
+        let existed = project_path.exists();
+        let keep_partial = self.keep_partial;
+        let hooks = self.hooks.clone();
+        let reporter = Observed::new(reporter, &hooks);
+        let runner = Arc::new(RecordingRunner::new(self.runner.clone()));
+        self.runner = runner.clone();
+
+        tokio::select! {
+            biased;
+            _ = cancel.cancelled() => {
+                // Dropping the scaffold killed its child processes and removed its temporary
+                // checkouts, so only the project directory is left
+                if !existed && project_path.exists() {
+                    let _ = fs::remove_dir_all(&project_path);
+                }
+                reporter.finish(&format!("🛑 {}", t!("progress-cancelled")));
+                Err(HexstackError::Cancelled.into())
+            }
+            result = self.scaffold(&reporter) => {
+                if let Err(err) = result {
+                    reporter.event(&ScaffoldEvent::StepFailed {
+                        step: reporter.step_name(),
+                        error: format!("{:#}", err),
+                    });
+                    if !existed && project_path.exists() {
+                        roll_back(&project_path, keep_partial);
+                    }
+                    return Err(err);
+                }
+                reporter.event(&ScaffoldEvent::Completed {
+                    path: project_path.clone(),
+                });
+
+                let mut create_report = CreateReport {
+                    files_written: report::list_files(&project_path),
+                    project_path,
+                    commands: runner.commands(),
+                    duration_ms: report::elapsed_ms(started),
+                    ..CreateReport::default()
+                };
+                reporter.fill_report(&mut create_report);
+                Ok(create_report)
+            }
+        }
+    }
+
+    async fn scaffold(self, reporter: &dyn ProgressReporter) -> Result<()> {
+        // Validate project name and check for existing directory
+        self.validate_project_name()?;
+        self.check_directory_conflict()?;
+
+        if !self.services.is_empty() {
+            return self.build_services(reporter).await;
+        }
+
+        // Validate that Lume cannot be used alone with frontends
+        self.validate_lume_frontend_usage()?;
+        self.validate_frontend_host()?;
+        self.validate_desktop_target()?;
+        self.validate_frontend_apps()?;
+        self.validate_styling()?;
+        self.validate_addon_components()?;
+
+        let plan = self.plan();
+        reporter.start(plan.len() as u64);
+
+        enter(reporter, Step::Resolve);
+        let template = self.determine_template();
+        reporter.step();
+
+        let project_path = self.project_path();
+        let mut lock = None;
+        let mut repository = false;
+        if let Some(template) = template {
+            enter(reporter, Step::Fetch);
+            reporter.message(&format!(
+                "📝 {}",
+                t!(
+                    "progress-generating-main",
+                    template = template.name.as_str()
+                )
+            ));
+
+            let commit = self.clone_template(template, reporter).await?;
+
+            let checksum = integrity::tree_checksum(&project_path)?;
+            if let Some(expected) = &template.sha256
+                && !integrity::matches(expected, &checksum)
+            {
+                if !self.allow_unverified {
+                    let _ = fs::remove_dir_all(&project_path);
+                    anyhow::bail!(
+                        "Template '{}' failed integrity verification\n\nExpected sha256: {}\nActual sha256:   {}\n\nThe template may have been tampered with or updated since it was published.\nRe-run with --allow-unverified to use it anyway.",
+                        template.name,
+                        expected,
+                        checksum
+                    );
+                }
+                reporter.warn(&t!("warn-unverified", template = template.name.as_str()));
+            }
+            reporter.step();
+
+            let apps = self.add_frontend_apps(reporter).await?;
+
+            enter(reporter, Step::PostProcess);
+            // Templates that ship a manifest may use `{{placeholder}}` values
+            let template_manifest = TemplateManifest::load(&project_path)?;
+            let cleanup = template_manifest
+                .as_ref()
+                .map(|m| m.cleanup.clone())
+                .unwrap_or_default();
+            for removed in manifest::strip_metadata(&self.workspace(), &cleanup, &self.name)? {
+                debug!("Removed the template's {}", removed.display());
+            }
+            if template_manifest.is_some() {
+                reporter.message(&format!("🧩 {}", t!("progress-placeholders")));
+                manifest::apply_placeholders(&self.workspace(), &self.name, &self.extras)?;
+            }
+            // The template's package name would otherwise end up in `cargo run` and the binary
+            if let Some(old) = manifest::rename_backend_package(&self.workspace(), &self.name)? {
+                debug!("Renamed package {} to {}", old, self.name);
+            }
+
+            let layout = ProjectLayout {
+                name: &self.name,
+                backend_dir: if project_path.join("backend").is_dir() {
+                    PathBuf::from("backend")
+                } else {
+                    PathBuf::new()
+                },
+                frontends: self
+                    .frontend_dirs()
+                    .into_iter()
+                    .filter_map(|(dir, frontend)| {
+                        let dir = dir.strip_prefix(&project_path).ok()?.to_path_buf();
+                        Some((dir, frontend))
+                    })
+                    .collect(),
+                server_rendered: self
+                    .selected_frontend
+                    .as_deref()
+                    .is_some_and(is_server_rendered),
+                components: &self.selected_components,
+            };
+            let mut generated = self
+                .example
+                .map(|example| example.files(&layout))
+                .unwrap_or_default();
+            generated.extend(extras::generated_files(&layout, &self.extras));
+            if self.extras.toolchain {
+                match template_manifest.map(|m| m.toolchain) {
+                    Some(toolchain) if !toolchain.is_empty() => {
+                        generated.extend(toolchain.files(&layout))
+                    }
+                    _ => reporter.warn(&t!("warn-no-toolchain", template = template.name.as_str())),
+                }
+            }
+            for skipped in extras::write_files(&self.workspace(), &generated)? {
+                reporter.warn(&t!("warn-kept-file", path = skipped.display().to_string()));
+            }
+            reporter.step();
+
+            // Clean up git history and reinitialize
+            enter(reporter, Step::GitInit);
+            if self.reinit_git {
+                reporter.message(&format!("🌱 {}", t!("progress-git-init")));
+            }
+            repository = self.cleanup_and_reinit_git(reporter).await?;
+            if repository && self.extras.git_hooks {
+                run_git(
+                    self.runner.as_ref(),
+                    &project_path,
+                    &["config", "core.hooksPath", extras::HOOKS_DIR],
+                )
+                .await?;
+            }
+            reporter.step();
+
+            let mut lock_file = self.lock_file(template, commit, checksum);
+            lock_file.apps = apps;
+            lock = Some(lock_file);
+        } else {
+            reporter.message(&format!("⚠️  {}", t!("progress-no-template")));
+        }
+
+        // Reproduced scaffolds keep the template's Cargo.lock so they match byte for byte
+        if self.pinned_commit.is_none() {
+            enter(reporter, Step::Install);
+            reporter.message(&format!("🔄 {}", t!("progress-cargo-update")));
+
+            // Check if "backend" directory exists inside the project directory
+            let backend_path = project_path.join("backend");
+            // A workspace shares one Cargo.lock at the root
+            let cargo_update_dir = if backend_path.is_dir() && !self.extras.workspace {
+                &backend_path
+            } else {
+                &project_path
+            };
+
+            self.cargo_update(cargo_update_dir).await?;
+            reporter.step();
+        }
+
+        for dir in self.node_frontend_dirs() {
+            enter(reporter, Step::Install);
+            if dir.join("package.json").is_file() {
+                let manager = PackageManager::resolve(&dir, self.package_manager);
+                reporter.message(&format!(
+                    "📦 {}",
+                    t!(
+                        "progress-node-install",
+                        dir = dir
+                            .strip_prefix(&project_path)
+                            .unwrap_or(&dir)
+                            .display()
+                            .to_string(),
+                        manager = manager.to_string()
+                    )
+                ));
+                // The project is usable without node_modules, so a failed install only warns
+                if let Err(err) = self.install_node_dependencies(&dir, manager).await {
+                    reporter.warn(&format!("{:#}", err));
+                }
+            }
+            reporter.step();
+        }
+
+        enter(reporter, Step::Finalize);
+        if let Some(lock) = &lock {
+            self.workspace().write(LOCK_FILE, lock.to_toml()?)?;
+        }
+        let template_name = lock.as_ref().map_or("", |lock| lock.template.name.as_str());
+        self.commit_and_publish(repository, template_name, reporter)
+            .await;
+        reporter.step();
+        reporter.finish(&format!("✅ {}", t!("progress-complete")));
+
+        self.provision_database().await;
+        if reporter.human_output() {
+            self.print_next_steps();
+        }
+        Ok(())
+    }
+
+    /// Generates every service under `services/` from the template for its components, plus the
+    /// shared crate, the root workspace and a docker-compose.yml running them together
+    async fn build_services(self, reporter: &dyn ProgressReporter) -> Result<()> {
+        reporter.start(self.plan().len() as u64);
+        let project_path = self.project_path();
+        fs::create_dir_all(&project_path)
+            .with_context(|| format!("Failed to create '{}'", project_path.display()))?;
+        let workspace = self.workspace();
+        enter(reporter, Step::GitInit);
+        let repository = self.cleanup_and_reinit_git(reporter).await?;
+        reporter.step();
+
+        let mut services = Vec::new();
+        let mut generated = Vec::new();
+        let mut locked = None;
+        for service in &self.services {
+            enter(reporter, Step::Fetch);
+            reporter.message(&format!(
+                "🧩 {}",
+                t!("progress-service", service = service.name.as_str())
+            ));
+            let mut service = service.clone();
+            let destination = project_path.join(service.dir());
+
+            if service.has_framework() {
+                let template = self
+                    .template_for(&service.components, None)
+                    .filter(|t| t.frontend.is_none())
+                    .ok_or_else(|| {
+                        HexstackError::TemplateNotFound(format!(
+                            "No template provides {} for the {} service",
+                            service.components.join(", "),
+                            service.name
+                        ))
+                    })?;
+                let (_checkout, template_root, commit) = self
+                    .checkout_template(template, service.commit.as_deref(), reporter)
+                    .await?;
+                let source = if template_root.join("backend").is_dir() {
+                    template_root.join("backend")
+                } else {
+                    template_root.clone()
+                };
+                copy_dir(&source, &destination)?;
+                let template_manifest = TemplateManifest::load(&template_root)?;
+                let cleanup = template_manifest
+                    .as_ref()
+                    .map(|m| m.cleanup.clone())
+                    .unwrap_or_default();
+                manifest::strip_metadata(&Workspace::new(&destination), &cleanup, &service.name)?;
+                if template_manifest.is_some() {
+                    manifest::apply_placeholders(
+                        &Workspace::new(&destination),
+                        &self.name,
+                        &self.extras,
+                    )?;
+                }
+
+                let manifest_path = service.dir().join("Cargo.toml");
+                let cargo_toml = workspace
+                    .read_to_string(&manifest_path)
+                    .with_context(|| format!("Template '{}' has no Cargo.toml", template.name))?;
+                workspace.write(
+                    &manifest_path,
+                    manifest::rename_package(&cargo_toml, &service.name),
+                )?;
+
+                if locked.is_none() {
+                    let checksum = integrity::tree_checksum(&template_root)?;
+                    locked = Some((template, commit.clone(), checksum));
+                }
+                service.commit = Some(commit);
+            } else {
+                generated.extend(services::bare_service_files(&service));
+            }
+
+            let extras = Extras {
+                database: self.extras.database.filter(|_| service.has("lume")),
+                ..Extras::default()
+            };
+            let layout = ProjectLayout {
+                name: &self.name,
+                backend_dir: service.dir(),
+                frontends: Vec::new(),
+                server_rendered: false,
+                components: &service.components,
+            };
+            // The root docker-compose.yml covers every service
+            generated.extend(
+                extras::generated_files(&layout, &extras)
+                    .into_iter()
+                    .filter(|file| file.path != Path::new("docker-compose.yml")),
+            );
+            services.push(service);
+            reporter.step();
+        }
+
+        enter(reporter, Step::PostProcess);
+        generated.extend(services::shared_files(
+            &self.name,
+            &services,
+            self.extras.database,
+        ));
+        extras::write_files(&workspace, &generated)?;
+
+        let Some((template, commit, checksum)) = locked else {
+            anyhow::bail!("❌ {}", t!("invalid-services"));
+        };
+        let mut lock = self.lock_file(template, commit, checksum);
+        lock.services = services;
+        reporter.step();
+
+        if self.pinned_commit.is_none() {
+            enter(reporter, Step::Install);
+            reporter.message(&format!("🔄 {}", t!("progress-cargo-update")));
+            self.cargo_update(&project_path).await?;
+            reporter.step();
+        }
+
+        enter(reporter, Step::Finalize);
+        workspace.write(LOCK_FILE, lock.to_toml()?)?;
+        self.commit_and_publish(repository, &lock.template.name, reporter)
+            .await;
+        reporter.step();
+        reporter.finish(&format!("✅ {}", t!("progress-complete")));
+
+        if reporter.human_output() {
+            self.print_services_next_steps();
+        }
+        Ok(())
+    }
+
+    async fn cargo_update(&self, dir: &Path) -> Result<()> {
+        let output = self
+            .runner
+            .run(&Invocation::new("cargo").arg("update").current_dir(dir))
+            .await
+            .with_context(|| t!("cargo-update-not-run"))?;
+
+        if !output.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!(t!(
+                "cargo-update-failed",
+                dir = dir.display().to_string(),
+                error = stderr.trim()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Frontend directories dependencies are installed in, when [`ProjectSetup::install_frontend`]
+    /// is on
+    fn node_frontend_dirs(&self) -> Vec<PathBuf> {
+        if !self.install_frontend {
+            return Vec::new();
+        }
+        self.frontend_dirs()
+            .into_iter()
+            .filter(|(_, frontend)| NODE_FRONTENDS.contains(frontend))
+            .map(|(dir, _)| dir)
+            .collect()
+    }
+
+    async fn install_node_dependencies(&self, dir: &Path, manager: PackageManager) -> Result<()> {
+        let install = manager.install_command();
+        let output = match self
+            .runner
+            .run(
+                &Invocation::new(manager.binary())
+                    .arg("install")
+                    .current_dir(dir),
+            )
+            .await
+        {
+            Ok(output) => output,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => anyhow::bail!(
+                "{} isn't installed, so the frontend dependencies weren't installed. Get it with: {}",
+                manager,
+                manager.install_instructions()
+            ),
+            Err(err) => return Err(err).with_context(|| format!("Failed to execute {}", install)),
+        };
+
+        if !output.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!(
+                "Failed to run '{}' in '{}', run it yourself before starting the dev server: {}",
+                install,
+                dir.display(),
+                stderr.trim()
+            );
+        }
+        Ok(())
+    }
+
+    /// Starts the database in docker when asked to, warning instead of failing since the project
+    /// itself is ready
+    async fn provision_database(&self) {
+        if !self.start_database {
+            return;
+        }
+        if let Err(err) = self.start_database_service().await {
+            warn!(
+                "⚠️  {}",
+                t!("database-failed", error = format!("{:#}", err))
+            );
+            warn!("   {}", t!("database-start-yourself"));
+        }
+    }
+
+    /// Runs `docker compose up -d db`, waits until the engine accepts connections and loads the
+    /// initial schema with the template's sqlx migrations or the lume seed binary
+    async fn start_database_service(&self) -> Result<()> {
+        let Some(database) = self.extras.database else {
+            return Ok(());
+        };
+        let db_name = self.name.replace(['-', '.'], "_");
+        let Some(check) = database.ready_check(&db_name) else {
+            return Ok(());
+        };
+        let project_path = self.project_path();
+
+        info!(
+            "\n🐳 {}",
+            t!("database-starting", database = database.display_name())
+        );
+        let output = self
+            .runner
+            .run(
+                &Invocation::new("docker")
+                    .args(["compose", "up", "-d", "db"])
+                    .current_dir(&project_path)
+                    .inherit_output(),
+            )
+            .await
+            .context("Failed to run docker compose")?;
+        if !output.success() {
+            anyhow::bail!(t!(
+                "database-compose-failed",
+                status = output.status().to_string()
+            ));
+        }
+
+        info!("⏳ {}", t!("database-waiting"));
+        let mut attempts = 0;
+        loop {
+            let ready = self
+                .runner
+                .run(
+                    &Invocation::new("docker")
+                        .args(["compose", "exec", "-T", "db"])
+                        .args(&check)
+                        .current_dir(&project_path),
+                )
+                .await
+                .context("Failed to run docker compose exec")?
+                .success();
+            if ready {
+                break;
+            }
+            attempts += 1;
+            if attempts == DATABASE_READY_ATTEMPTS {
+                anyhow::bail!(t!("database-not-ready", seconds = attempts));
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+
+        let backend = if project_path.join("backend").is_dir() {
+            project_path.join("backend")
+        } else {
+            project_path
+        };
+        // `cargo run` and the seed binary read DATABASE_URL from .env
+        let env = backend.join(".env");
+        if !env.exists() && backend.join(".env.example").is_file() {
+            fs::copy(backend.join(".env.example"), &env)
+                .context("Failed to create .env from .env.example")?;
+        }
+
+        let line = if backend.join("migrations").is_dir() && on_path("sqlx") {
+            "sqlx migrate run"
+        } else if backend.join("src/bin/seed.rs").is_file() {
+            "cargo run --bin seed"
+        } else {
+            info!("✅ {}", t!("database-up"));
+            return Ok(());
+        };
+        info!("📦 {}", t!("database-running", command = line));
+        let mut parts = line.split_whitespace();
+        let output = self
+            .runner
+            .run(
+                &Invocation::new(parts.next().unwrap_or_default())
+                    .args(parts)
+                    .current_dir(&backend)
+                    .inherit_output(),
+            )
+            .await
+            .with_context(|| format!("Failed to run `{}`", line))?;
+        if !output.success() {
+            anyhow::bail!(t!(
+                "database-command-failed",
+                command = line,
+                status = output.status().to_string()
+            ));
+        }
+        info!("✅ {}", t!("database-ready"));
+        Ok(())
+    }
+
+    /// The steps [`ProjectSetup::build`] takes with these settings, in order. The progress bar
+    /// is as long as the plan and advances once per step.
+    pub fn plan(&self) -> Vec<Step> {
+        let mut plan = Vec::new();
+        if !self.services.is_empty() {
+            plan.push(Step::GitInit);
+            plan.extend(std::iter::repeat_n(Step::Fetch, self.services.len()));
+            plan.push(Step::PostProcess);
+        } else {
+            plan.push(Step::Resolve);
+            if self.determine_template().is_some() {
+                // The first frontend app comes with the template, the others are fetched
+                plan.extend(std::iter::repeat_n(Step::Fetch, self.apps.len().max(1)));
+                plan.extend([Step::PostProcess, Step::GitInit]);
+            }
+        }
+        // Reproduced scaffolds keep the template's Cargo.lock
+        if self.pinned_commit.is_none() {
+            plan.push(Step::Install);
+        }
+        plan.extend(std::iter::repeat_n(
+            Step::Install,
+            self.node_frontend_dirs().len(),
+        ));
+        plan.push(Step::Finalize);
+        plan
+    }
+
+    /// What [`ProjectSetup::build`] is going to do with these settings, for a summary to confirm
+    /// before anything is fetched or written. The package manager of a frontend without a
+    /// preferred one is only known once its template is cloned, so npm is assumed.
+    pub async fn preview(&self) -> Preview {
+        let mut templates = Vec::new();
+        let mut commands = Vec::new();
+        let clone = |template: &ProjectTemplate| format!("git clone {}", template.source().0);
+        let git_init = format!("git init -b {}", self.default_branch().await);
+
+        if !self.services.is_empty() {
+            if self.reinit_git {
+                commands.push(git_init);
+            }
+            for service in self.services.iter().filter(|s| s.has_framework()) {
+                if let Some(template) = self
+                    .template_for(&service.components, None)
+                    .filter(|t| t.frontend.is_none())
+                {
+                    templates.push(template.name.clone());
+                    commands.push(clone(template));
+                }
+            }
+        } else if let Some(template) = self.determine_template() {
+            templates.push(template.name.clone());
+            commands.push(clone(template));
+            for app in self.apps.iter().skip(1) {
+                if let Some(template) = self
+                    .template_for_frontend(Some(&app.frontend))
+                    .filter(|t| t.frontend.as_deref() == Some(app.frontend.as_str()))
+                {
+                    templates.push(template.name.clone());
+                    commands.push(clone(template));
+                }
+            }
+            if self.reinit_git {
+                commands.push(git_init);
+            }
+        }
+
+        if self.pinned_commit.is_none() {
+            commands.push("cargo update".to_string());
+        }
+        let project_path = self.project_path();
+        for dir in self.node_frontend_dirs() {
+            commands.push(format!(
+                "{}   # in {}",
+                self.package_manager.unwrap_or_default().install_command(),
+                dir.strip_prefix(&project_path).unwrap_or(&dir).display()
+            ));
+        }
+        if self.reinit_git && (self.initial_commit || self.create_remote.is_some()) {
+            commands.push("git add --all && git commit".to_string());
+            if let Some(visibility) = self.create_remote {
+                commands.push(format!(
+                    "gh repo create {} {} --source . --remote origin --push",
+                    self.name,
+                    visibility.flag()
+                ));
+            }
+        }
+        if self.start_database {
+            commands.push("docker compose up -d db".to_string());
+        }
+
+        Preview {
+            project_path,
+            templates,
+            commands,
+        }
+    }
+
+    fn print_next_steps(&self) {
+        info!("\n🎉 {}", t!("created", name = self.name.as_str()));
+        info!("\n{}", t!("next-steps"));
+        info!("  cd {}", self.project_path().display());
+        info!("  cargo run");
+
+        if !self.selected_components.is_empty() {
+            info!("\n{}", t!("components-added"));
+            for component in &self.selected_components {
+                if let Some(config) = self.config.get(component) {
+                    let description = i18n::lookup(&format!("component-{}", component))
+                        .unwrap_or_else(|| config.description.clone());
+                    info!("  • {} - {}", component, description);
+                }
+            }
+        }
+
+        if let Some(example) = self.example {
+            info!("\n{}", t!("example", description = example.description()));
+            info!("  {}", t!("example-open"));
+        }
+
+        if let Some(database) = self.extras.database {
+            info!("\n{}", t!("database", database = database.display_name()));
+            info!("  {}", t!("database-env"));
+            if database.compose_service(&self.name).is_some()
+                && !self.extras.docker
+                && !self.start_database
+            {
+                info!("  docker compose up -d db");
+            }
+            if self.selected_components.iter().any(|c| c == "lume") {
+                info!("  hexstack db seed   # {}", t!("database-seed"));
+            }
+        }
+
+        if (self.extras.tracing || self.extras.otel)
+            && self.selected_components.iter().any(|c| c == "ripress")
+        {
+            info!("\n{}", t!("tracing-middleware"));
+            info!("  app.use_pre_middleware(\"/\", telemetry::trace_requests);");
+        }
+
+        if self.selected_components.iter().any(|c| c == "graphql") {
+            info!("\n{}", t!("graphql-routes"));
+            info!("  graphql::register(&mut app);");
+            info!("  {}", t!("graphql-graphiql"));
+        }
+
+        if self.extras.openapi {
+            info!("\n{}", t!("openapi-routes"));
+            info!("  openapi::register(&mut app);");
+            info!("{}", t!("openapi-client"));
+            info!("  hexstack generate api-client");
+        }
+
+        if self.extras.rate_limit {
+            info!("\n{}", t!("rate-limit-middleware"));
+            if self.selected_components.iter().any(|c| c == "redis") {
+                info!(
+                    "  rate_limit::register(&mut app, rate_limit::RateLimiter::connect().await.unwrap());"
+                );
+            } else {
+                info!("  rate_limit::register(&mut app, rate_limit::RateLimiter::from_env());");
+            }
+            info!("{}", t!("rate-limit-tune"));
+        }
+
+        if self.extras.sessions {
+            let store = if self.extras.database.is_some() {
+                "session::DatabaseStore::connect().await.unwrap()"
+            } else if self.selected_components.iter().any(|c| c == "redis") {
+                "session::RedisStore::connect().await.unwrap()"
+            } else {
+                "session::MemoryStore::default()"
+            };
+            info!("\n{}", t!("sessions-manager"));
+            info!("  let sessions = session::Sessions::new({});", store);
+            info!("  sessions.require_login(&mut app, \"/api/account\");");
+        }
+
+        if self.extras.otel {
+            info!("\n{}", t!("otel-jaeger"));
+            if !self.extras.docker {
+                info!("  docker compose up -d jaeger");
+            }
+            info!("{}", t!("otel-shutdown"));
+        }
+
+        if self.extras.admin {
+            info!("\n{}", t!("admin-api"));
+            info!("  admin::register(&mut app, admin::Users::default());");
+            info!("{}", t!("admin-client"));
+        }
+
+        if self.extras.uploads {
+            info!("\n{}", t!("uploads-route"));
+            info!("  uploads::register(&mut app, storage::Storage::from_env().unwrap());");
+            if !self.extras.docker {
+                info!("{}", t!("uploads-minio"));
+                info!("  docker compose up -d minio minio-setup");
+            }
+        }
+
+        if self.extras.shared_types {
+            info!("\n{}", t!("shared-types"));
+            info!("  hexstack generate types");
+        }
+
+        if self.selected_components.iter().any(|c| c == "grpc") {
+            info!("\n{}", t!("grpc-server"));
+            info!("  tokio::spawn(grpc::serve());");
+        }
+
+        if self.selected_components.iter().any(|c| c == "scheduler") {
+            info!("\n{}", t!("scheduler-start"));
+            info!("  let _scheduler = scheduler::start().await.unwrap();");
+        }
+
+        if self.selected_components.iter().any(|c| c == "jobs") {
+            info!("\n{}", t!("jobs-worker"));
+            info!("  cargo run --bin worker");
+        }
+
+        if self.extras.email {
+            info!("\n{}", t!("email-mailhog"));
+            if !self.extras.docker {
+                info!("  docker compose up -d mailhog");
+            }
+        }
+
+        if self.extras.tests {
+            info!("\n{}", t!("tests-run"));
+        }
+
+        if self.extras.e2e {
+            info!("\n{}", t!("e2e-run"));
+            info!("  ./scripts/e2e.sh");
+        }
+
+        if self.extras.devcontainer {
+            info!("\n{}", t!("devcontainer-open"));
+            info!("  {}", t!("devcontainer-debug"));
+        }
+
+        if self.extras.nix {
+            info!("\n{}", t!("nix-shell"));
+            info!("  nix develop   # {}", t!("nix-direnv"));
+        }
+
+        if self.extras.git_hooks {
+            info!("\n{}", t!("git-hooks"));
+            info!("  {}", t!("git-hooks-install"));
+        }
+
+        if self.extras.docker {
+            info!("\n{}", t!("docker-stack"));
+            info!("  docker compose up --build");
+        }
+
+        if self.extras.k8s {
+            info!("\n{}", t!("k8s-deploy"));
+            info!("  docker compose build");
+            info!(
+                "  helm install {} {}   # {}",
+                self.name,
+                extras::HELM_CHART_DIR,
+                t!("k8s-values")
+            );
+        }
+
+        if self.desktop {
+            info!("\n{}", t!("desktop-dev"));
+        } else {
+            for (frontend_dir, frontend) in self.frontend_dirs() {
+                info!("\n{}", t!("frontend", frontend = display_name(frontend)));
+
+                if let Some(steps) = self.dev_steps(&frontend_dir, frontend)
+                    && frontend_dir.is_dir()
+                {
+                    info!("{}", t!("frontend-dev-server"));
+                    if let Some((binary, install)) = frontend_tool(frontend)
+                        && !on_path(binary)
+                    {
+                        info!("  {}", install);
+                    }
+                    info!("  cd {}", frontend_dir.display());
+                    for step in steps {
+                        info!("  {}", step);
+                    }
+                }
+            }
+            if self.apps.len() > 1 {
+                info!("\n{}", t!("run-everything"));
+            }
+        }
+
+        if let Some(template) = self.determine_template() {
+            info!(
+                "\n{}",
+                t!("template-used", template = template.name.as_str())
+            );
+        }
+    }
+
+    fn print_services_next_steps(&self) {
+        info!("\n🎉 {}", t!("created", name = self.name.as_str()));
+        info!("\n{}", t!("next-steps"));
+        info!("  cd {}", self.project_path().display());
+        info!("  docker compose up --build");
+
+        info!("\n{}", t!("services"));
+        let mut host_port = 3000;
+        for service in &self.services {
+            let components = service
+                .components
+                .iter()
+                .map(|c| display_name(c))
+                .collect::<Vec<_>>()
+                .join(", ");
+            if service.serves() {
+                info!(
+                    "  • {} ({}) - http://localhost:{}",
+                    service.name, components, host_port
+                );
+                host_port += 1;
+            } else {
+                info!("  • {} ({})", service.name, components);
+            }
+        }
+        info!("\n{}", t!("services-run-one"));
+        info!("{}", t!("services-shared", dir = services::SHARED_DIR));
+    }
+
+    /// Validates that Lume cannot be used alone with frontends
+    /// Lume must be used with at least one other component (Ripress or Wynd) when a frontend is selected
+    pub fn validate_lume_frontend_usage(&self) -> Result<()> {
+        // Check if a frontend is selected
+        if self.selected_frontend.is_some() {
+            // Check if Lume is selected
+            let has_lume = self.selected_components.contains(&"lume".to_string());
+
+            if has_lume {
+                // Check if Lume is used alone (without Ripress or Wynd)
+                let has_ripress = self.selected_components.contains(&"ripress".to_string());
+                let has_wynd = self.selected_components.contains(&"wynd".to_string());
+
+                if !has_ripress && !has_wynd {
+                    anyhow::bail!("❌ {}", t!("invalid-lume-alone"));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ensures add-on components have a backend component to be added to
+    pub fn validate_addon_components(&self) -> Result<()> {
+        let (addons, backend): (Vec<&String>, Vec<&String>) = self
+            .selected_components
+            .iter()
+            .partition(|c| ADDON_COMPONENTS.contains(&c.as_str()));
+        if let Some(addon) = addons.first()
+            && backend.is_empty()
+            && self.template_key.is_none()
+        {
+            anyhow::bail!("❌ {}", t!("invalid-addon", addon = display_name(addon)));
+        }
+        if self.selected_components.iter().any(|c| c == "graphql")
+            && !self.selected_components.iter().any(|c| c == "ripress")
+            && self.template_key.is_none()
+        {
+            anyhow::bail!("❌ {}", t!("invalid-graphql"));
+        }
+        if self.template_key.is_none() {
+            let enabled = self.extras.enabled();
+            for (extra, component) in EXTRA_REQUIREMENTS {
+                if enabled.contains(&extra)
+                    && !self.selected_components.iter().any(|c| c == component)
+                {
+                    anyhow::bail!(
+                        "❌ {}",
+                        t!(
+                            "invalid-extra-requirement",
+                            extra = extra,
+                            name = display_name(component),
+                            component = component
+                        )
+                    );
+                }
+            }
+        }
+        if self.extras.k8s && !self.extras.docker {
+            anyhow::bail!("❌ {}", t!("invalid-k8s"));
+        }
+        Ok(())
+    }
+
+    /// Validates that a component able to serve the selected frontend was chosen
+    pub fn validate_frontend_host(&self) -> Result<()> {
+        let Some(frontend) = &self.selected_frontend else {
+            return Ok(());
+        };
+        if !FRONTENDS.contains(&frontend.as_str()) {
+            return Ok(());
+        }
+
+        let hosts = frontend_hosts(frontend);
+        if !self
+            .selected_components
+            .iter()
+            .any(|c| hosts.contains(&c.as_str()))
+        {
+            let names: Vec<String> = hosts.iter().map(|h| display_name(h)).collect();
+            anyhow::bail!(
+                "❌ {}",
+                t!(
+                    "invalid-frontend-host",
+                    frontend = display_name(frontend),
+                    hosts = names.join(&format!(" {} ", t!("or")))
+                )
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Validates that a desktop app wraps a client-side frontend
+    pub fn validate_desktop_target(&self) -> Result<()> {
+        if !self.desktop {
+            return Ok(());
+        }
+
+        match self.selected_frontend.as_deref() {
+            None => anyhow::bail!("❌ {}", t!("invalid-desktop-frontend")),
+            Some(frontend) if is_server_rendered(frontend) => anyhow::bail!(
+                "❌ {}",
+                t!(
+                    "invalid-desktop-server-rendered",
+                    frontend = display_name(frontend)
+                )
+            ),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Ensures Tailwind is only requested for a frontend it can be added to
+    pub fn validate_styling(&self) -> Result<()> {
+        if self.extras.styling == Styling::Tailwind
+            && !self
+                .frontend_dirs()
+                .iter()
+                .any(|(_, frontend)| tailwind_target(frontend).is_some())
+        {
+            anyhow::bail!("❌ {}", t!("invalid-tailwind"));
+        }
+        Ok(())
+    }
+
+    /// Validates the frontend apps of a project with more than one frontend
+    pub fn validate_frontend_apps(&self) -> Result<()> {
+        if self.apps.len() < 2 {
+            return Ok(());
+        }
+
+        if self.desktop {
+            anyhow::bail!("❌ {}", t!("invalid-apps-desktop"));
+        }
+
+        for (i, app) in self.apps.iter().enumerate() {
+            if app.name.is_empty()
+                || !app
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                anyhow::bail!("❌ {}", t!("invalid-app-name", name = app.name.as_str()));
+            }
+            if self.apps[..i].iter().any(|other| other.name == app.name) {
+                anyhow::bail!(
+                    "❌ {}",
+                    t!("invalid-app-duplicate", name = app.name.as_str())
+                );
+            }
+            if !FRONTENDS.contains(&app.frontend.as_str()) {
+                anyhow::bail!(
+                    "❌ {}",
+                    t!("invalid-app-frontend", frontend = app.frontend.as_str())
+                );
+            }
+            if is_server_rendered(&app.frontend) {
+                anyhow::bail!(
+                    "❌ {}",
+                    t!(
+                        "invalid-app-server-rendered",
+                        frontend = display_name(&app.frontend)
+                    )
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Commands starting the dev server of the frontend in `dir`. Node frontends use their
+    /// package manager and skip the install once `node_modules` exists.
+    fn dev_steps(&self, dir: &Path, frontend: &str) -> Option<Vec<String>> {
+        if !NODE_FRONTENDS.contains(&frontend) {
+            return frontend_dev_steps(frontend)
+                .map(|steps| steps.iter().map(|step| step.to_string()).collect());
+        }
+        let [install, dev] = PackageManager::resolve(dir, self.package_manager).dev_steps();
+        Some(if dir.join("node_modules").is_dir() {
+            vec![dev]
+        } else {
+            vec![install, dev]
+        })
+    }
+
+    /// Directories of the project's frontends, paired with the frontend they hold
+    pub fn frontend_dirs(&self) -> Vec<(PathBuf, &str)> {
+        let project_path = self.project_path();
+        if self.apps.len() > 1 {
+            return self
+                .apps
+                .iter()
+                .map(|app| {
+                    (
+                        project_path.join(APPS_DIR).join(&app.name),
+                        app.frontend.as_str(),
+                    )
+                })
+                .collect();
+        }
+
+        self.selected_frontend
+            .iter()
+            .map(|frontend| (project_path.join("frontend"), frontend.as_str()))
+            .collect()
+    }
+
+    /// Moves the template's `frontend` directory to `apps/<name>` and copies the `frontend`
+    /// directory of every other app's template next to it, returning the apps with their commits
+    async fn add_frontend_apps(&self, reporter: &dyn ProgressReporter) -> Result<Vec<FrontendApp>> {
+        if self.apps.len() < 2 {
+            return Ok(Vec::new());
+        }
+
+        let project_path = self.project_path();
+        let apps_dir = project_path.join(APPS_DIR);
+        let primary = project_path.join("frontend");
+        if !primary.is_dir() {
+            anyhow::bail!(
+                "The project template has no frontend/ directory to move into {}/",
+                APPS_DIR
+            );
+        }
+        fs::create_dir_all(&apps_dir)
+            .with_context(|| format!("Failed to create '{}'", apps_dir.display()))?;
+        fs::rename(&primary, apps_dir.join(&self.apps[0].name))
+            .context("Failed to move the frontend into place")?;
+
+        let mut apps = vec![self.apps[0].clone()];
+        for app in &self.apps[1..] {
+            enter(reporter, Step::Fetch);
+            reporter.message(&format!(
+                "🧩 {}",
+                t!(
+                    "progress-app",
+                    app = app.name.as_str(),
+                    frontend = display_name(&app.frontend)
+                )
+            ));
+
+            let template = self
+                .template_for_frontend(Some(&app.frontend))
+                .filter(|t| t.frontend.as_deref() == Some(app.frontend.as_str()))
+                .ok_or_else(|| {
+                    HexstackError::TemplateNotFound(format!(
+                        "No template pairs the selected components with {}",
+                        display_name(&app.frontend)
+                    ))
+                })?;
+            let (_checkout, template_root, commit) = self
+                .checkout_template(template, app.commit.as_deref(), reporter)
+                .await?;
+
+            let source = template_root.join("frontend");
+            if !source.is_dir() {
+                anyhow::bail!("Template '{}' has no frontend/ directory", template.name);
+            }
+            let destination = apps_dir.join(&app.name);
+            copy_dir(&source, &destination)?;
+            if TemplateManifest::load(&template_root)?.is_some() {
+                manifest::apply_placeholders(
+                    &Workspace::new(&destination),
+                    &self.name,
+                    &self.extras,
+                )?;
+            }
+
+            apps.push(FrontendApp {
+                commit: Some(commit),
+                ..app.clone()
+            });
+            reporter.step();
+        }
+
+        Ok(apps)
+    }
+
+    /// Clones `template` into a temporary directory at `commit`, or its latest commit when unset,
+    /// and verifies its checksum. Returns the checkout, the template root inside it and the commit.
+    async fn checkout_template(
+        &self,
+        template: &ProjectTemplate,
+        commit: Option<&str>,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(tempfile::TempDir, PathBuf, String)> {
+        let (repo_url, subdir) = template.source();
+        self.check_disk_space(template)?;
+        clone_started(reporter, template);
+
+        let checkout_parent =
+            tempfile::tempdir().context("Failed to create a temporary directory")?;
+        let checkout = checkout_parent.path().join("template");
+        let mut clone = Invocation::new("git").arg("clone");
+        if commit.is_none() {
+            clone = clone.args(["--depth", "1"]);
+        }
+        let clone = clone.arg(repo_url).arg(&checkout);
+        let commit = match self
+            .clone_or_download(&clone, repo_url, commit, &checkout, reporter)
+            .await?
+        {
+            Some(commit) => commit,
+            None => {
+                if let Some(commit) = commit {
+                    run_git(
+                        self.runner.as_ref(),
+                        &checkout,
+                        &["checkout", "--quiet", "--detach", commit],
+                    )
+                    .await?;
+                }
+                run_git(self.runner.as_ref(), &checkout, &["rev-parse", "HEAD"]).await?
+            }
+        };
+        reporter.event(&ScaffoldEvent::CloneFinished {
+            url: repo_url.to_string(),
+            commit: commit.clone(),
+        });
+
+        let template_root = match subdir {
+            Some(subdir) => checkout.join(subdir),
+            None => checkout.clone(),
+        };
+        if let Some(expected) = &template.sha256
+            && !integrity::matches(expected, &integrity::tree_checksum(&template_root)?)
+            && !self.allow_unverified
+        {
+            anyhow::bail!(
+                "Template '{}' failed integrity verification. Re-run with --allow-unverified to use it anyway.",
+                template.name
+            );
+        }
+
+        Ok((checkout_parent, template_root, commit))
+    }
+
+    /// Runs `clone` into `dest` and returns `None` once git has cloned the repository. When git
+    /// isn't installed, or cloning keeps failing on network errors, the forge's archive of `rev`
+    /// (the default branch when unset) is unpacked into `dest` instead and its commit returned.
+    async fn clone_or_download(
+        &self,
+        clone: &Invocation,
+        repo_url: &str,
+        rev: Option<&str>,
+        dest: &Path,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<Option<String>> {
+        let host = Host::from_url(repo_url);
+        let (reason, err): (String, anyhow::Error) = match clone_with_retry(
+            self.runner.as_ref(),
+            self.retry,
+            clone,
+            dest,
+        )
+        .await
+        {
+            Ok(output) if output.success() => return Ok(None),
+            Ok(output) => {
+                let err = HexstackError::git_failed(&["clone", repo_url], &output.stderr);
+                if !is_transient_git_error(&String::from_utf8_lossy(&output.stderr)) {
+                    return Err(err.into());
+                }
+                (t!("archive-clone-failing"), err.into())
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                let install = match host {
+                    Some(host) => format!(
+                        "https://git-scm.com/downloads, or download the template manually from {}",
+                        host.archive_url(repo_url, rev.unwrap_or("HEAD"))
+                    ),
+                    None => "https://git-scm.com/downloads".to_string(),
+                };
+                (
+                    t!("archive-git-missing"),
+                    HexstackError::tool_missing("git", &install).into(),
+                )
+            }
+            Err(err) => return Err(err).context("Failed to execute git clone command"),
+        };
+        let Some(host) = host.filter(|_| self.archive_fallback) else {
+            return Err(err);
+        };
+
+        let rev = rev.unwrap_or("HEAD");
+        let url = host.archive_url(repo_url, rev);
+        reporter.warn(&t!(
+            "warn-archive-fallback",
+            reason = reason,
+            url = url.as_str()
+        ));
+        if dest.exists() {
+            let _ = fs::remove_dir_all(dest);
+        }
+        let extracted = match archive::download(&url, self.retry).await {
+            Ok(tarball) => archive::extract_tarball(&tarball, dest),
+            Err(download_err) => Err(download_err),
+        };
+        match extracted {
+            Ok(commit) => Ok(Some(commit.unwrap_or_else(|| rev.to_string()))),
+            Err(download_err) => {
+                if dest.exists() {
+                    let _ = fs::remove_dir_all(dest);
+                }
+                reporter.warn(&t!(
+                    "warn-download-failed",
+                    error = format!("{:#}", download_err)
+                ));
+                Err(err)
+            }
+        }
+    }
+
+    /// Validates the project name for common issues
+    pub fn validate_project_name(&self) -> Result<()> {
+        validation::validate_project_name(&self.name)?;
+        Ok(())
+    }
+
+    /// Checks if a directory with the same name already exists
+    pub fn check_directory_conflict(&self) -> Result<()> {
+        let project_path = self.project_path();
+
+        if project_path.exists() {
+            return Err(HexstackError::DirectoryExists {
+                file: !project_path.is_dir(),
+                path: project_path,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Fails before cloning when the filesystem the project goes on can't fit `template`, going by
+    /// its registry `size`. Built-in templates and registry entries without one only need
+    /// [`preflight::MIN_FREE_BYTES`]; a filesystem whose free space can't be read isn't checked.
+    fn check_disk_space(&self, template: &ProjectTemplate) -> Result<()> {
+        let dir = self.parent_dir();
+        let available = match preflight::available_space(dir) {
+            Ok(available) => available,
+            Err(err) => {
+                debug!("Not checking the free space in {}: {}", dir.display(), err);
+                return Ok(());
+            }
+        };
+        preflight::check_space(dir, template.size, available)?;
+        Ok(())
+    }
+
+    /// Clones the template into the project directory and returns the commit it was cloned at.
+    /// Templates living in a subdirectory are cloned next to it first so only that subdirectory
+    /// ends up in the project
+    async fn clone_template(
+        &self,
+        template: &ProjectTemplate,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<String> {
+        let (repo_url, subdir) = template.source();
+        self.check_disk_space(template)?;
+        clone_started(reporter, template);
+
+        let checkout_parent = match subdir {
+            Some(_) => Some(
+                tempfile::Builder::new()
+                    .prefix(".hexstack-")
+                    .tempdir_in(self.parent_dir())
+                    .context("Failed to create a temporary directory for the template")?,
+            ),
+            None => None,
+        };
+        let checkout_path = match &checkout_parent {
+            Some(parent) => parent.path().join("template"),
+            None => self.project_path(),
+        };
+
+        let mut clone = Invocation::new("git").arg("clone");
+        // Pinned commits may be anywhere in the history, so they need a full clone
+        if subdir.is_some() && self.pinned_commit.is_none() {
+            clone = clone.args(["--depth", "1"]);
+        }
+        let clone = clone.arg(repo_url).arg(&checkout_path);
+        let pinned_commit = self.pinned_commit.as_deref();
+        let commit = match self
+            .clone_or_download(&clone, repo_url, pinned_commit, &checkout_path, reporter)
+            .await?
+        {
+            Some(commit) => commit,
+            None => {
+                if let Some(commit) = pinned_commit {
+                    run_git(
+                        self.runner.as_ref(),
+                        &checkout_path,
+                        &["checkout", "--quiet", "--detach", commit],
+                    )
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Locked template commit {} is not available in {}",
+                            commit, repo_url
+                        )
+                    })?;
+                }
+                run_git(self.runner.as_ref(), &checkout_path, &["rev-parse", "HEAD"]).await?
+            }
+        };
+        reporter.event(&ScaffoldEvent::CloneFinished {
+            url: repo_url.to_string(),
+            commit: commit.clone(),
+        });
+
+        if let Some(subdir) = subdir {
+            if Path::new(subdir)
+                .components()
+                .any(|c| !matches!(c, std::path::Component::Normal(_)))
+            {
+                anyhow::bail!("Invalid template subdirectory '{}'", subdir);
+            }
+
+            let source = checkout_path.join(subdir);
+            if !source.is_dir() {
+                anyhow::bail!(
+                    "Template repository {} has no '{}' directory",
+                    repo_url,
+                    subdir
+                );
+            }
+
+            fs::rename(&source, self.project_path()).with_context(|| {
+                format!(
+                    "Failed to move template subdirectory '{}' into place",
+                    subdir
+                )
+            })?;
+        }
+
+        Ok(commit)
+    }
+
+    /// Describes the scaffold so it can be reproduced with `hexstack new --locked`
+    pub fn lock_file(
+        &self,
+        template: &ProjectTemplate,
+        commit: String,
+        checksum: String,
+    ) -> LockFile {
+        let (url, subdir) = template.source();
+        LockFile {
+            hexstack_version: env!("CARGO_PKG_VERSION").to_string(),
+            project_name: self.name.clone(),
+            template: LockedTemplate {
+                name: template.name.clone(),
+                url: url.to_string(),
+                subdir: subdir.map(str::to_string),
+                commit,
+                sha256: Some(checksum),
+                components: self.selected_components.clone(),
+                frontend: self.selected_frontend.clone(),
+                desktop: template.desktop,
+            },
+            apps: Vec::new(),
+            services: Vec::new(),
+            extras: self.extras.clone(),
+            deploy: None,
+        }
+    }
+
+    /// Creates the initial commit when [`ProjectSetup::initial_commit`] or
+    /// [`ProjectSetup::create_remote`] asks for it, then creates the GitHub repository and pushes
+    /// the commit for the latter. Failures only warn since the project itself is complete.
+    async fn commit_and_publish(
+        &self,
+        repository: bool,
+        template: &str,
+        reporter: &dyn ProgressReporter,
+    ) {
+        if !repository {
+            if self.create_remote.is_some() {
+                reporter.warn(&t!("warn-no-repository"));
+            }
+            return;
+        }
+        if !self.initial_commit && self.create_remote.is_none() {
+            return;
+        }
+        if !self.commit_project(template, reporter).await {
+            return;
+        }
+        let Some(visibility) = self.create_remote else {
+            return;
+        };
+
+        reporter.message(&format!("🚀 {}", t!("progress-github-repo")));
+        match remote::create_github_repo(
+            self.runner.as_ref(),
+            &self.project_path(),
+            &self.name,
+            visibility,
+        )
+        .await
+        {
+            Ok(url) => info!("🚀 {}", t!("pushed", url = url)),
+            Err(err) => reporter.warn(&t!("warn-github-failed", error = format!("{:#}", err))),
+        }
+    }
+
+    /// Stages the whole project and commits it with [`initial_commit_message`], returning
+    /// whether that worked. A failed commit, e.g. because git has no user configured, warns.
+    async fn commit_project(&self, template: &str, reporter: &dyn ProgressReporter) -> bool {
+        reporter.message(&format!("📌 {}", t!("progress-initial-commit")));
+        let project_path = self.project_path();
+        let message = initial_commit_message(template);
+        let committed = async {
+            run_git(self.runner.as_ref(), &project_path, &["add", "--all"]).await?;
+            // The git-hooks extra would lint a project whose dependencies aren't built yet
+            run_git(
+                self.runner.as_ref(),
+                &project_path,
+                &["commit", "--quiet", "--no-verify", "-m", &message],
+            )
+            .await
+        }
+        .await;
+        if let Err(err) = committed {
+            reporter.warn(&t!("warn-commit-failed", error = format!("{:#}", err)));
+            return false;
+        }
+        true
+    }
+
+    /// Cleans up git history and reinitializes the repository. Returns `false` when the project
+    /// has no repository of its own: with [`ProjectSetup::reinit_git`] off, or when git isn't
+    /// installed.
+    async fn cleanup_and_reinit_git(&self, reporter: &dyn ProgressReporter) -> Result<bool> {
+        let project_path = self.project_path();
+
+        // Remove .git directory
+        self.workspace()
+            .remove_dir_all(".git")
+            .context("Failed to remove .git directory from cloned template")?;
+
+        let enclosing = enclosing_repository(&project_path);
+        if !self.reinit_git {
+            match enclosing {
+                Some(repository) => debug!(
+                    "Leaving the project to the repository at {}",
+                    repository.display()
+                ),
+                None => reporter.warn(&t!("warn-not-versioned")),
+            }
+            return Ok(false);
+        }
+        if let Some(repository) = enclosing {
+            reporter.warn(&t!(
+                "warn-nested-repository",
+                path = repository.display().to_string()
+            ));
+        }
+
+        // Initialize new git repository
+        let branch = self.default_branch().await;
+        let output = match self
+            .runner
+            .run(
+                &Invocation::new("git")
+                    .args(["init", "-b", &branch])
+                    .current_dir(&project_path),
+            )
+            .await
+        {
+            Ok(output) => output,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                warn!("{}", t!("warn-no-git"));
+                return Ok(false);
+            }
+            Err(err) => return Err(err).context("Failed to execute git init"),
+        };
+
+        if !output.success() {
+            return Err(HexstackError::git_failed(&["init", "-b", &branch], &output.stderr).into());
+        }
+
+        Ok(true)
+    }
+
+    /// Branch the new repository starts on: [`ProjectSetup::default_branch`], otherwise git's
+    /// `init.defaultBranch`, otherwise [`DEFAULT_BRANCH`]
+    async fn default_branch(&self) -> String {
+        if let Some(branch) = &self.default_branch {
+            return branch.clone();
+        }
+        // The project directory may not exist yet, and git sees the same settings from its parent
+        let configured = run_git(
+            self.runner.as_ref(),
+            self.parent_dir(),
+            &["config", "--get", "init.defaultBranch"],
+        )
+        .await;
+        match configured {
+            Ok(branch) if !branch.is_empty() => branch,
+            _ => DEFAULT_BRANCH.to_string(),
+        }
+    }
+}
+
+/// The root of the git repository `dir` would be nested in, looking at the directories above it.
+/// A `.git` file counts too, as worktrees and submodules have one.
+pub fn enclosing_repository(dir: &Path) -> Option<PathBuf> {
+    let dir = std::path::absolute(dir).ok()?;
+    dir.ancestors()
+        .skip(1)
+        .find(|ancestor| ancestor.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Finds the template in `templates` for `components` paired with `selected_frontend`: an exact
+/// match when there is one, otherwise the largest official combination the components cover
+pub fn match_template<'a>(
+    templates: &'a HashMap<String, ProjectTemplate>,
+    components: &[String],
+    selected_frontend: Option<&str>,
+    desktop: bool,
+) -> Option<&'a ProjectTemplate> {
+    let components_set: std::collections::HashSet<&str> = components
+        .iter()
+        .map(|s| s.as_str())
+        .filter(|c| !ADDON_COMPONENTS.contains(c))
+        .collect();
+
+    // An exact match on components and frontend wins, which also covers combinations
+    // published through a registry that the priority lists below do not know about
+    let mut exact_matches: Vec<(&String, &ProjectTemplate)> = templates
+        .iter()
+        .filter(|(_, template)| {
+            !template.components.is_empty()
+                && template.frontend.as_deref() == selected_frontend
+                && template.desktop == desktop
+                && template.components.len() == components_set.len()
+                && template
+                    .components
+                    .iter()
+                    .all(|comp| components_set.contains(comp.as_str()))
+        })
+        .collect();
+    exact_matches.sort_by(|a, b| a.0.cmp(b.0));
+
+    if let Some((_, template)) = exact_matches.first() {
+        return Some(template);
+    }
+
+    // Otherwise fall back through the official templates for the frontend, largest first.
+    // Unknown frontends fall back to the backend-only templates.
+    let frontend = selected_frontend.filter(|f| FRONTENDS.contains(f));
+    let template_priorities: Vec<(String, Vec<String>)> = ProjectSetup::template_matrix()
+        .into_iter()
+        .filter(|(_, template)| {
+            template.frontend.as_deref() == frontend && template.desktop == desktop
+        })
+        .map(|(key, template)| (key, template.components))
+        .collect();
+
+    for (template_key, required_components) in &template_priorities {
+        if required_components
+            .iter()
+            .all(|comp| components_set.contains(comp.as_str()))
+        {
+            // For multi-component templates, ensure we have ONLY those components
+            if required_components.len() > 1 {
+                if components_set.len() == required_components.len()
+                    && let Some(template) = templates.get(template_key)
+                {
+                    return Some(template);
+                }
+            } else {
+                // For single component templates, allow additional components
+                if let Some(template) = templates.get(template_key) {
+                    return Some(template);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Removes the directory a failed scaffold created, or says where it was left with `keep`
+fn roll_back(project_path: &Path, keep: bool) {
+    if keep {
+        warn!(
+            "{}",
+            t!("partial-kept", path = project_path.display().to_string())
+        );
+        return;
+    }
+    match fs::remove_dir_all(project_path) {
+        Ok(()) => info!(
+            "{}",
+            t!("partial-removed", path = project_path.display().to_string())
+        ),
+        Err(err) => warn!(
+            "{}",
+            t!(
+                "partial-not-removed",
+                path = project_path.display().to_string(),
+                error = err.to_string()
+            )
+        ),
+    }
+}
+
+/// Tells the reporter the scaffold moved on to `step`
+fn enter(reporter: &dyn ProgressReporter, step: Step) {
+    reporter.event(&ScaffoldEvent::StepStarted { step });
+}
+
+/// Runs the `git clone` in `clone`, trying again under `policy` while git fails on a network
+/// error. A failed attempt's partial checkout at `dest` is removed so the next one starts clean.
+/// The proxies from the environment are passed on to git, and on Windows `core.longpaths` so
+/// deep template trees check out past the 260 character path limit.
+pub async fn clone_with_retry(
+    runner: &dyn CommandRunner,
+    policy: RetryPolicy,
+    clone: &Invocation,
+    dest: &Path,
+) -> std::io::Result<CommandOutput> {
+    let mut clone = ProxySettings::from_env().apply_to_git(clone.clone());
+    if cfg!(windows) {
+        clone = clone
+            .env("GIT_CONFIG_COUNT", "1")
+            .env("GIT_CONFIG_KEY_0", "core.longpaths")
+            .env("GIT_CONFIG_VALUE_0", "true");
+    }
+    let clone = &clone;
+    policy
+        .run(
+            "git clone",
+            || async move {
+                if dest.exists() {
+                    let _ = fs::remove_dir_all(dest);
+                }
+                runner.run(clone).await
+            },
+            |outcome| {
+                matches!(outcome, Ok(output) if !output.success()
+                    && is_transient_git_error(&String::from_utf8_lossy(&output.stderr)))
+            },
+        )
+        .await
+}
+
+/// Reports the template that was picked and that cloning it has begun
+fn clone_started(reporter: &dyn ProgressReporter, template: &ProjectTemplate) {
+    let (url, _) = template.source();
+    reporter.event(&ScaffoldEvent::TemplateResolved {
+        name: template.name.clone(),
+        url: template.github_url.clone(),
+    });
+    reporter.event(&ScaffoldEvent::CloneStarted {
+        url: url.to_string(),
+    });
+}
+
+/// Runs a git command inside `dir`, returning its trimmed stdout
+pub async fn run_git(runner: &dyn CommandRunner, dir: &Path, args: &[&str]) -> Result<String> {
+    let output = runner
+        .run(&Invocation::new("git").args(args).current_dir(dir))
+        .await
+        .with_context(|| format!("Failed to execute git {}", args.join(" ")))?;
+
+    if !output.success() {
+        return Err(HexstackError::git_failed(args, &output.stderr).into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Message of the commit [`ProjectSetup::initial_commit`] creates
+pub fn initial_commit_message(template: &str) -> String {
+    format!(
+        "Initial commit from hexstack ({} {})",
+        template,
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// Human readable name for a component or frontend key
+pub fn display_name(key: &str) -> String {
+    match key {
+        "sveltekit" => return "SvelteKit".to_string(),
+        "nextjs" => return "Next.js".to_string(),
+        "htmx" => return "HTMX".to_string(),
+        "vanilla" => return "Vanilla TS".to_string(),
+        "graphql" => return "GraphQL".to_string(),
+        "grpc" => return "gRPC".to_string(),
+        _ => {}
+    }
+
+    let mut chars = key.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+    }
+}
+
+/// Commands that start a frontend's dev server, run from the project's `frontend` directory
+pub fn frontend_dev_steps(frontend: &str) -> Option<&'static [&'static str]> {
+    match frontend {
+        frontend if NODE_FRONTENDS.contains(&frontend) => Some(&["npm install", "npm run dev"]),
+        "leptos" => Some(&["cargo leptos watch"]),
+        "yew" => Some(&["rustup target add wasm32-unknown-unknown", "trunk serve"]),
+        "dioxus" => Some(&["dx serve"]),
+        _ => None,
+    }
+}
+
+/// Build tool a frontend's dev flow relies on, with the command that installs it
+pub fn frontend_tool(frontend: &str) -> Option<(&'static str, &'static str)> {
+    match frontend {
+        "leptos" => Some(("cargo-leptos", "cargo install cargo-leptos --locked")),
+        "yew" => Some(("trunk", "cargo install trunk --locked")),
+        "dioxus" => Some(("dx", "cargo install dioxus-cli --locked")),
+        _ => None,
+    }
+}
+
+/// Whether an executable is available on `PATH`
+pub fn on_path(binary: &str) -> bool {
+    let Some(paths) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&paths)
+        .any(|dir| dir.join(binary).is_file() || dir.join(format!("{}.exe", binary)).is_file())
+}
+
+/// Components that can serve `frontend`
+pub fn frontend_hosts(frontend: &str) -> &'static [&'static str] {
+    if is_server_rendered(frontend) {
+        &["ripress"]
+    } else {
+        &FRONTEND_HOSTS
+    }
+}
+
+/// Whether `frontend` is rendered by the backend instead of running as a separate app
+pub fn is_server_rendered(frontend: &str) -> bool {
+    SERVER_RENDERED_FRONTENDS.contains(&frontend)
+}
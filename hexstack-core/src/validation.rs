@@ -0,0 +1,177 @@
+//! Project name rules shared by the CLI's arguments and prompts and the library API. A name
+//! has to work as a cargo package name and as a directory name on every platform.
+
+use crate::error::HexstackError;
+
+/// Longest project name accepted
+pub const MAX_NAME_LENGTH: usize = 50;
+
+/// A project name that passed [`validate_project_name`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ValidName(String);
+
+impl ValidName {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl AsRef<str> for ValidName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for ValidName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<ValidName> for String {
+    fn from(name: ValidName) -> Self {
+        name.0
+    }
+}
+
+/// Checks `name` against every project name rule, failing with
+/// [`HexstackError::InvalidName`] that suggests a fixed name when there is one
+pub fn validate_project_name(name: &str) -> Result<ValidName, HexstackError> {
+    if name.is_empty() {
+        return Err(HexstackError::InvalidName(
+            "Project name cannot be empty".to_string(),
+        ));
+    }
+    if name.len() > MAX_NAME_LENGTH {
+        return Err(HexstackError::InvalidName(format!(
+            "Project name is too long (max {} characters)",
+            MAX_NAME_LENGTH
+        )));
+    }
+    // The name ends up in Cargo.toml, and projects get checked out on Windows too
+    match crate_name_problem(name).or_else(|| windows_name_problem(name)) {
+        Some(problem) => Err(HexstackError::InvalidName(with_suggestion(problem, name))),
+        None => Ok(ValidName(name.to_string())),
+    }
+}
+
+/// Rust keywords, which cargo refuses as package names
+const RUST_KEYWORDS: [&str; 52] = [
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Names cargo refuses because they clash with the standard library or its build directories
+const CARGO_RESERVED_NAMES: [&str; 10] = [
+    "alloc",
+    "core",
+    "proc_macro",
+    "proc-macro",
+    "std",
+    "test",
+    "build",
+    "deps",
+    "examples",
+    "incremental",
+];
+
+/// Why cargo would refuse `name` as a package name: characters other than ASCII letters,
+/// digits, `-` and `_`, a leading digit or dash, or a Rust keyword or reserved name
+pub fn crate_name_problem(name: &str) -> Option<String> {
+    if let Some(c) = name
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && *c != '-' && *c != '_')
+    {
+        let found = match c {
+            ' ' => "a space".to_string(),
+            c => format!("'{}'", c),
+        };
+        return Some(format!(
+            "Project name '{}' contains {}, but package names may only use letters, numbers, `-` and `_`",
+            name, found
+        ));
+    }
+    if let Some(first_char) = name.chars().next()
+        && !first_char.is_ascii_alphabetic()
+        && first_char != '_'
+    {
+        return Some(format!(
+            "Project name must start with a letter or underscore, not '{}'",
+            first_char
+        ));
+    }
+    if RUST_KEYWORDS.contains(&name) {
+        return Some(format!(
+            "Project name '{}' is a Rust keyword and can't be a package name",
+            name
+        ));
+    }
+    if CARGO_RESERVED_NAMES.contains(&name) {
+        return Some(format!(
+            "Project name '{}' is reserved by cargo and can't be a package name",
+            name
+        ));
+    }
+    None
+}
+
+/// A name close to `name` that passes [`crate_name_problem`] and [`windows_name_problem`]:
+/// `my.app` and `my app` become `my-app`, `2d-game` becomes `app-2d-game` and `fn` becomes
+/// `fn-app`. `None` when `name` is fine as it is or nothing usable is left of it.
+pub fn suggest_crate_name(name: &str) -> Option<String> {
+    let mut fixed = String::new();
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            fixed.push(c);
+        } else if !fixed.is_empty() && !fixed.ends_with('-') {
+            fixed.push('-');
+        }
+    }
+    let mut fixed = fixed.trim_end_matches('-').to_string();
+    if fixed.is_empty() {
+        return None;
+    }
+    if fixed.starts_with(|c: char| c.is_ascii_digit()) {
+        fixed = format!("app-{}", fixed);
+    }
+    if crate_name_problem(&fixed).is_some() || windows_name_problem(&fixed).is_some() {
+        fixed.push_str("-app");
+    }
+    (fixed != name && crate_name_problem(&fixed).is_none()).then_some(fixed)
+}
+
+/// `problem` followed by the name [`suggest_crate_name`] offers instead, when there is one
+fn with_suggestion(problem: String, name: &str) -> String {
+    match suggest_crate_name(name) {
+        Some(suggestion) => format!("{}\nTry `{}` instead", problem, suggestion),
+        None => problem,
+    }
+}
+
+/// Device names Windows reserves in every directory. Windows reserves them with an extension too,
+/// but project names can't contain dots anyway
+pub const WINDOWS_RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Why `name` can't be a directory on Windows: it's a reserved device name such as `con`. Names
+/// with dots or spaces, which Windows treats specially too, already fail [`crate_name_problem`]
+pub fn windows_name_problem(name: &str) -> Option<String> {
+    WINDOWS_RESERVED_NAMES
+        .iter()
+        .find(|reserved| reserved.eq_ignore_ascii_case(name))
+        .map(|reserved| {
+            format!(
+                "Project name '{}' is reserved on Windows ({} is a device name)",
+                name, reserved
+            )
+        })
+}
@@ -0,0 +1,149 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tracing::trace;
+
+use crate::manifest::SKIPPED_DIRS;
+
+/// A project directory the scaffold's file operations are resolved against, so none of them
+/// depend on the process's current directory. Paths are relative to the root, or absolute paths
+/// inside it; anything leading out of the root is refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    root: PathBuf,
+}
+
+impl Workspace {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolves `path` against the root
+    pub fn path(&self, path: impl AsRef<Path>) -> Result<PathBuf> {
+        let path = path.as_ref();
+        let relative = if path.is_absolute() {
+            path.strip_prefix(&self.root).with_context(|| {
+                format!(
+                    "'{}' is outside the project '{}'",
+                    path.display(),
+                    self.root.display()
+                )
+            })?
+        } else {
+            path
+        };
+        if relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            anyhow::bail!(
+                "'{}' is outside the project '{}'",
+                path.display(),
+                self.root.display()
+            );
+        }
+        Ok(self.root.join(relative))
+    }
+
+    /// The file's contents, or `None` when it doesn't exist or isn't text
+    pub fn read_to_string(&self, path: impl AsRef<Path>) -> Option<String> {
+        fs::read_to_string(self.path(path).ok()?).ok()
+    }
+
+    /// Writes `contents`, creating the parent directories first
+    pub fn write(&self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<()> {
+        let path = self.path(path)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create '{}'", parent.display()))?;
+        }
+        trace!("Writing {}", path.display());
+        fs::write(&path, contents).with_context(|| format!("Failed to write '{}'", path.display()))
+    }
+
+    pub fn set_executable(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = self.path(path)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+                .with_context(|| format!("Failed to make '{}' executable", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Removes a directory and everything in it; a directory that isn't there is fine
+    pub fn remove_dir_all(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = self.path(path)?;
+        if path.exists() {
+            fs::remove_dir_all(&path)
+                .with_context(|| format!("Failed to remove '{}'", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Copies a template tree, skipping build output, dependencies and git metadata. On Windows the
+/// copy goes through [`long_path`]s, so deep trees aren't cut off at 260 characters.
+pub fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    copy_tree(&long_path(from), &long_path(to))
+}
+
+fn copy_tree(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to).with_context(|| format!("Failed to create '{}'", to.display()))?;
+
+    for entry in
+        fs::read_dir(from).with_context(|| format!("Failed to read '{}'", from.display()))?
+    {
+        let entry = entry?;
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+
+        if path.is_dir() {
+            let skipped = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|n| SKIPPED_DIRS.contains(&n));
+            if !skipped {
+                copy_tree(&path, &target)?;
+            }
+        } else {
+            fs::copy(&path, &target)
+                .with_context(|| format!("Failed to copy '{}'", path.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// `path` in the `\\?\` form that lifts Windows' 260 character limit. Other platforms have no
+/// such limit, so they get `path` back as it is.
+pub fn long_path(path: &Path) -> PathBuf {
+    if !cfg!(windows) {
+        return path.to_path_buf();
+    }
+    // Verbatim paths skip `..` and `/` handling, so they have to be absolute and normalized first
+    match std::path::absolute(path) {
+        Ok(absolute) => match absolute.to_str() {
+            Some(absolute) => PathBuf::from(verbatim_path(absolute)),
+            None => absolute,
+        },
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Prefixes an absolute Windows path with `\\?\`, or a `\\server\share` path with
+/// `\\?\UNC\`
+pub fn verbatim_path(absolute: &str) -> String {
+    if absolute.starts_with(r"\\?\") {
+        absolute.to_string()
+    } else if let Some(share) = absolute.strip_prefix(r"\\") {
+        format!(r"\\?\UNC\{}", share)
+    } else {
+        format!(r"\\?\{}", absolute)
+    }
+}
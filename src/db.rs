@@ -0,0 +1,50 @@
+use anyhow::{Context, Result};
+
+use crate::dev::{backend_dir, load_lock};
+use crate::output::say;
+use hexstack_core::runner::{CommandRunner, Invocation, SystemRunner};
+
+const USAGE: &str = "Usage: hexstack db seed";
+
+/// Runs one of the project's database tasks
+pub async fn run(args: &[String]) -> Result<()> {
+    match args.first().map(String::as_str) {
+        Some("seed") => seed(&args[1..]).await,
+        Some(other) => anyhow::bail!("Unknown database command: {}\n\n{}", other, USAGE),
+        None => anyhow::bail!("{}", USAGE),
+    }
+}
+
+/// Loads the fixtures in the backend's `seeds/` directory through its `seed` binary
+async fn seed(args: &[String]) -> Result<()> {
+    if let Some(arg) = args.first() {
+        anyhow::bail!("Unknown argument: {}\n\n{}", arg, USAGE);
+    }
+
+    load_lock()?;
+    let backend = backend_dir();
+    if !backend.join("src/bin/seed.rs").is_file() {
+        anyhow::bail!(
+            "No seed binary found in {}. Create the project with lume and a database.",
+            backend.join("src/bin").display()
+        );
+    }
+
+    say!(
+        "🌱 Seeding the database from {}...",
+        backend.join("seeds").display()
+    );
+    let output = SystemRunner
+        .run(
+            &Invocation::new("cargo")
+                .args(["run", "--bin", "seed"])
+                .current_dir(backend)
+                .inherit_output(),
+        )
+        .await
+        .context("Failed to run cargo run")?;
+    if !output.success() {
+        anyhow::bail!("Seeding the database failed with {}", output.status());
+    }
+    Ok(())
+}
@@ -0,0 +1,237 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::dev::{backend_dir, frontend_dirs, load_lock, require_tool};
+use crate::output::{say, say_err};
+use hexstack_core::lockfile::{DeploySettings, LOCK_FILE, LockFile, Provider};
+use hexstack_core::runner::{CommandRunner, Invocation, SystemRunner};
+use hexstack_core::setup::run_git;
+
+const USAGE: &str = "Usage: hexstack deploy [docker|fly|shuttle] [--registry <registry>] [--app <name>] [--tag <tag>]";
+
+/// Port the backend listens on, which Fly.io routes traffic to
+const BACKEND_PORT: u16 = 3000;
+
+/// Flags passed to `hexstack deploy`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DeployArgs {
+    pub provider: Option<Provider>,
+    pub registry: Option<String>,
+    pub app: Option<String>,
+    pub tag: Option<String>,
+}
+
+pub fn parse_args(args: &[String]) -> Result<DeployArgs> {
+    let mut parsed = DeployArgs::default();
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1).cloned();
+        match args[i].as_str() {
+            "--registry" => parsed.registry = Some(required(value, "--registry")?),
+            "--app" => parsed.app = Some(required(value, "--app")?),
+            "--tag" => parsed.tag = Some(required(value, "--tag")?),
+            arg if !arg.starts_with('-') && parsed.provider.is_none() => {
+                parsed.provider = Some(Provider::parse(arg).with_context(|| {
+                    format!(
+                        "Unknown provider '{}'. Valid values: {}",
+                        arg,
+                        Provider::ALL.map(Provider::key).join(", ")
+                    )
+                })?);
+                i += 1;
+                continue;
+            }
+            arg => anyhow::bail!("Unknown argument: {}\n\n{}", arg, USAGE),
+        }
+        i += 2;
+    }
+    Ok(parsed)
+}
+
+fn required(value: Option<String>, flag: &str) -> Result<String> {
+    value.with_context(|| format!("{} requires a value\n\n{}", flag, USAGE))
+}
+
+/// Combines the flags with the settings saved by an earlier deploy; flags win
+pub fn resolve_settings(
+    args: &DeployArgs,
+    saved: Option<&DeploySettings>,
+) -> Result<DeploySettings> {
+    let Some(provider) = args.provider.or(saved.map(|s| s.provider)) else {
+        anyhow::bail!("No deploy provider configured yet\n\n{}", USAGE);
+    };
+    // Settings saved for another provider don't carry over
+    let saved = saved.filter(|s| s.provider == provider);
+    let settings = DeploySettings {
+        provider,
+        registry: args
+            .registry
+            .clone()
+            .or_else(|| saved.and_then(|s| s.registry.clone())),
+        app: args
+            .app
+            .clone()
+            .or_else(|| saved.and_then(|s| s.app.clone())),
+    };
+    if provider == Provider::Docker && settings.registry.is_none() {
+        anyhow::bail!(
+            "Deploying with docker needs a registry to push to, e.g. --registry ghcr.io/me"
+        );
+    }
+    Ok(settings)
+}
+
+/// Builds the project and ships it with the configured provider
+pub async fn run(args: &[String]) -> Result<()> {
+    let args = parse_args(args)?;
+    let mut lock = load_lock()?;
+    let settings = resolve_settings(&args, lock.deploy.as_ref())?;
+    if lock.deploy.as_ref() != Some(&settings) {
+        lock.deploy = Some(settings.clone());
+        lock.save(Path::new(LOCK_FILE))?;
+        say!("💾 Saved the deploy settings to {}", LOCK_FILE);
+    }
+
+    let (binary, install) = settings.provider.cli();
+    require_tool((binary, install))?;
+    let app = settings
+        .app
+        .clone()
+        .unwrap_or_else(|| lock.project_name.clone());
+
+    match settings.provider {
+        Provider::Docker => {
+            let tag = match args.tag {
+                Some(tag) => tag,
+                None => run_git(
+                    &SystemRunner,
+                    Path::new("."),
+                    &["rev-parse", "--short", "HEAD"],
+                )
+                .await
+                .unwrap_or_else(|_| "latest".to_string()),
+            };
+            let registry = settings.registry.unwrap_or_default();
+            push_images(&lock, registry.trim_end_matches('/'), &app, &tag).await
+        }
+        Provider::Fly => deploy_fly(&app).await,
+        Provider::Shuttle => deploy_shuttle().await,
+    }
+}
+
+/// Builds the backend's and frontends' images and pushes them as `<registry>/<app>-<name>:<tag>`
+async fn push_images(lock: &LockFile, registry: &str, app: &str, tag: &str) -> Result<()> {
+    let backend = backend_dir();
+    require_dockerfile(backend)?;
+    let mut images = vec![(backend.to_path_buf(), "backend".to_string())];
+    for (dir, _) in frontend_dirs(lock) {
+        if dir.join("Dockerfile").is_file() {
+            let name = dir
+                .file_name()
+                .map_or("frontend".into(), |n| n.to_string_lossy().into_owned());
+            images.push((dir, name));
+        }
+    }
+
+    for (dir, name) in images {
+        let image = format!("{}/{}-{}:{}", registry, app, name, tag);
+        say!("🐳 Building {}...", image);
+        step("docker", &["build", "-t", &image, "."], &dir).await?;
+        say!("📤 Pushing {}...", image);
+        step("docker", &["push", &image], &dir).await?;
+    }
+    say!("✅ Pushed the images to {}", registry);
+    Ok(())
+}
+
+/// Deploys the backend to Fly.io, writing a `fly.toml` and creating the app on the first run
+async fn deploy_fly(app: &str) -> Result<()> {
+    let backend = backend_dir();
+    require_dockerfile(backend)?;
+    let config = backend.join("fly.toml");
+    if !config.exists() {
+        fs::write(&config, fly_config(app))
+            .with_context(|| format!("Failed to write '{}'", config.display()))?;
+        say!("✏️  Wrote {}", config.display());
+        // Fails when the app already exists, which is fine
+        if let Err(err) = step("fly", &["apps", "create", app], backend).await {
+            say_err!("⚠️  {:#}", err);
+        }
+    }
+
+    say!("🚀 Deploying {} to Fly.io...", app);
+    step("fly", &["deploy", "--remote-only"], backend).await?;
+    say!("✅ Deployed to https://{}.fly.dev", app);
+    Ok(())
+}
+
+/// `fly.toml` building the backend's Dockerfile and routing HTTPS traffic to it
+pub fn fly_config(app: &str) -> String {
+    format!(
+        r#"app = "{}"
+
+[build]
+  dockerfile = "Dockerfile"
+
+[http_service]
+  internal_port = {}
+  force_https = true
+  auto_stop_machines = "stop"
+  auto_start_machines = true
+  min_machines_running = 0
+"#,
+        app, BACKEND_PORT
+    )
+}
+
+/// Deploys a backend whose entry point is `#[shuttle_runtime::main]` to Shuttle
+async fn deploy_shuttle() -> Result<()> {
+    let backend = backend_dir();
+    let manifest = fs::read_to_string(backend.join("Cargo.toml"))
+        .with_context(|| format!("Failed to read {}", backend.join("Cargo.toml").display()))?;
+    if !manifest.contains("shuttle-runtime") {
+        anyhow::bail!(
+            "Shuttle runs backends built on shuttle-runtime, which {} doesn't depend on.\nSee https://docs.shuttle.dev to port the entry point, or deploy with docker or fly.",
+            backend.join("Cargo.toml").display()
+        );
+    }
+
+    say!("🔨 Building the backend...");
+    step("cargo", &["build", "--release"], backend).await?;
+    say!("🚀 Deploying to Shuttle...");
+    step("shuttle", &["deploy"], backend).await?;
+    say!("✅ Deployed to Shuttle");
+    Ok(())
+}
+
+fn require_dockerfile(dir: &Path) -> Result<()> {
+    if !dir.join("Dockerfile").is_file() {
+        anyhow::bail!(
+            "No Dockerfile found in {}. Create the project with the docker extra or add one.",
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
+async fn step(program: &str, args: &[&str], dir: &Path) -> Result<()> {
+    let output = SystemRunner
+        .run(
+            &Invocation::new(program)
+                .args(args)
+                .current_dir(dir)
+                .inherit_output(),
+        )
+        .await
+        .with_context(|| format!("Failed to run {}", program))?;
+    if !output.success() {
+        anyhow::bail!(
+            "`{} {}` failed with {}",
+            program,
+            args.join(" "),
+            output.status()
+        );
+    }
+    Ok(())
+}
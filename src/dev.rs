@@ -0,0 +1,236 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tokio::process::{Child, Command};
+use tokio::task::JoinSet;
+
+use crate::output::say;
+use hexstack_core::config::GlobalConfig;
+use hexstack_core::error::HexstackError;
+use hexstack_core::lockfile::{LOCK_FILE, LockFile};
+use hexstack_core::package_manager::PackageManager;
+use hexstack_core::runner::{CommandRunner, Invocation, SystemRunner};
+use hexstack_core::setup::{
+    APPS_DIR, NODE_FRONTENDS, display_name, frontend_dev_steps, frontend_tool, on_path,
+};
+
+const USAGE: &str = "Usage: hexstack dev";
+const TEST_USAGE: &str = "Usage: hexstack test";
+
+/// Tauri's cargo subcommand and how to install it
+const TAURI_CLI: (&str, &str) = ("cargo-tauri", "cargo install tauri-cli --locked");
+
+/// Runs a generated project in development: the backend plus a dev server per frontend app, or
+/// the Tauri dev shell for desktop apps
+pub async fn run(args: &[String]) -> Result<()> {
+    if let Some(arg) = args.first() {
+        anyhow::bail!("Unknown argument: {}\n\n{}", arg, USAGE);
+    }
+
+    let lock = load_lock()?;
+
+    if lock.template.desktop {
+        require_tool(TAURI_CLI)?;
+        say!("🖥️  Starting the Tauri dev shell...");
+        let output = SystemRunner
+            .run(
+                &Invocation::new("cargo")
+                    .args(["tauri", "dev"])
+                    .inherit_output(),
+            )
+            .await
+            .context("Failed to run cargo tauri dev")?;
+        if !output.success() {
+            anyhow::bail!("cargo tauri dev exited with {}", output.status());
+        }
+        return Ok(());
+    }
+
+    let backend_dir = backend_dir();
+    let frontends = frontend_dirs(&lock);
+    let configured = configured_package_manager();
+
+    // Each process is awaited in its own task; whichever exits first stops the others
+    let mut processes = JoinSet::new();
+    for (dir, frontend) in &frontends {
+        if !dir.is_dir() {
+            continue;
+        }
+        let steps: Vec<String> = if NODE_FRONTENDS.contains(frontend) {
+            let manager = PackageManager::resolve(dir, configured);
+            require_tool((manager.binary(), manager.install_instructions()))?;
+            manager.dev_steps().to_vec()
+        } else if let Some(steps) = frontend_dev_steps(frontend) {
+            steps.iter().map(|step| step.to_string()).collect()
+        } else {
+            continue;
+        };
+        if let Some(tool) = frontend_tool(frontend) {
+            require_tool(tool)?;
+        }
+
+        let (server, setup) = steps.split_last().expect("dev steps are never empty");
+        for step in setup {
+            let status = command(step, dir)
+                .status()
+                .await
+                .with_context(|| format!("Failed to run `{}`", step))?;
+            if !status.success() {
+                anyhow::bail!("`{}` exited with {}", step, status);
+            }
+        }
+
+        say!(
+            "🌐 Starting the {} dev server in {}...",
+            display_name(frontend),
+            dir.display()
+        );
+        let mut child = spawn(server, dir)?;
+        processes.spawn(async move { child.wait().await });
+    }
+
+    say!("🦀 Starting the backend...");
+    let mut backend = spawn("cargo run", backend_dir)?;
+    processes.spawn(async move { backend.wait().await });
+
+    let status = processes
+        .join_next()
+        .await
+        .expect("the backend process is always running")
+        .context("Dev process task failed")?
+        .context("Failed to wait for the dev processes")?;
+
+    if !status.success() {
+        anyhow::bail!("Dev process exited with {}", status);
+    }
+    Ok(())
+}
+
+/// Runs the backend's tests and the test script of every frontend that has one, reporting
+/// every failing suite at the end
+pub async fn test(args: &[String]) -> Result<()> {
+    if let Some(arg) = args.first() {
+        anyhow::bail!("Unknown argument: {}\n\n{}", arg, TEST_USAGE);
+    }
+
+    let lock = load_lock()?;
+    // A workspace tests every Rust crate in one run from the root
+    let workspace = lock.extras.workspace;
+    let mut suites = if workspace {
+        vec![(
+            PathBuf::from("."),
+            "cargo test --workspace".to_string(),
+            None,
+        )]
+    } else {
+        vec![(backend_dir().to_path_buf(), "cargo test".to_string(), None)]
+    };
+    let configured = configured_package_manager();
+    for (dir, _) in frontend_dirs(&lock) {
+        if has_test_script(&dir) {
+            let manager = PackageManager::resolve(&dir, configured);
+            suites.push((dir, manager.run_command("test"), Some(manager)));
+        } else if dir.join("Cargo.toml").is_file() && !workspace {
+            suites.push((dir, "cargo test".to_string(), None));
+        }
+    }
+
+    let mut failed = Vec::new();
+    for (dir, line, manager) in &suites {
+        say!("🧪 Running `{}` in {}...", line, dir.display());
+        if let Some(manager) = manager
+            && !dir.join("node_modules").is_dir()
+        {
+            let install = manager.install_command();
+            let status = command(&install, dir)
+                .status()
+                .await
+                .with_context(|| format!("Failed to run `{}`", install))?;
+            if !status.success() {
+                failed.push(dir.display().to_string());
+                continue;
+            }
+        }
+
+        let status = command(line, dir)
+            .status()
+            .await
+            .with_context(|| format!("Failed to run `{}`", line))?;
+        if !status.success() {
+            failed.push(dir.display().to_string());
+        }
+    }
+
+    if !failed.is_empty() {
+        anyhow::bail!("Tests failed in: {}", failed.join(", "));
+    }
+    say!("✅ All {} test suite(s) passed", suites.len());
+    Ok(())
+}
+
+pub fn load_lock() -> Result<LockFile> {
+    let lock_path = Path::new(LOCK_FILE);
+    if !lock_path.exists() {
+        anyhow::bail!(
+            "No {} found in the current directory.\nRun this command from the root of a project created by hexstack.",
+            LOCK_FILE
+        );
+    }
+    LockFile::load(lock_path)
+}
+
+pub fn backend_dir() -> &'static Path {
+    if Path::new("backend").is_dir() {
+        Path::new("backend")
+    } else {
+        Path::new(".")
+    }
+}
+
+/// Frontend directories of the project and the frontend in each
+pub fn frontend_dirs(lock: &LockFile) -> Vec<(PathBuf, &str)> {
+    if lock.apps.is_empty() {
+        lock.template
+            .frontend
+            .iter()
+            .map(|frontend| (PathBuf::from("frontend"), frontend.as_str()))
+            .collect()
+    } else {
+        lock.apps
+            .iter()
+            .map(|app| (Path::new(APPS_DIR).join(&app.name), app.frontend.as_str()))
+            .collect()
+    }
+}
+
+/// The package manager set in the global config, which wins over the frontends' lock files
+fn configured_package_manager() -> Option<PackageManager> {
+    GlobalConfig::load().unwrap_or_default().package_manager
+}
+
+fn has_test_script(dir: &Path) -> bool {
+    std::fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+        .is_some_and(|package| package["scripts"]["test"].is_string())
+}
+
+fn command(line: &str, dir: &Path) -> Command {
+    let mut parts = line.split_whitespace();
+    let mut command = Command::new(parts.next().unwrap_or_default());
+    command.args(parts).current_dir(dir);
+    command
+}
+
+fn spawn(line: &str, dir: &Path) -> Result<Child> {
+    command(line, dir)
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to run `{}`", line))
+}
+
+pub fn require_tool((binary, install): (&str, &str)) -> Result<()> {
+    if !on_path(binary) {
+        return Err(HexstackError::tool_missing(binary, install).into());
+    }
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::dev::{backend_dir, frontend_dirs, load_lock};
+use crate::output::say;
+use hexstack_core::error::HexstackError;
+use hexstack_core::extras::TYPES_DIR;
+use hexstack_core::lockfile::LockFile;
+use hexstack_core::openapi::{CLIENT_DIR, typescript_client};
+use hexstack_core::proxy;
+use hexstack_core::runner::{CommandRunner, Invocation, SystemRunner};
+use hexstack_core::setup::NODE_FRONTENDS;
+
+const USAGE: &str = "Usage: hexstack generate <api-client [--spec <file|url>] | types>";
+const API_CLIENT_USAGE: &str = "Usage: hexstack generate api-client [--spec <file|url>]";
+
+/// Runs one of the project's code generators
+pub async fn run(args: &[String]) -> Result<()> {
+    match args.first().map(String::as_str) {
+        Some("api-client") => generate_client(&args[1..]).await,
+        Some("types") => shared_types(&args[1..]).await,
+        Some(other) => anyhow::bail!("Unknown generator: {}\n\n{}", other, USAGE),
+        None => anyhow::bail!("{}", USAGE),
+    }
+}
+
+/// Frontend directories of the project built with npm, which the generated TypeScript goes to
+pub fn node_frontend_dirs(lock: &LockFile) -> Result<Vec<PathBuf>> {
+    let dirs: Vec<PathBuf> = frontend_dirs(lock)
+        .into_iter()
+        .filter(|(_, frontend)| NODE_FRONTENDS.contains(frontend))
+        .map(|(dir, _)| dir)
+        .collect();
+    if dirs.is_empty() {
+        anyhow::bail!("This project has no TypeScript frontend to generate code into");
+    }
+    Ok(dirs)
+}
+
+/// Exports the TypeScript definitions of the `shared` crate's types into every Node frontend
+async fn shared_types(args: &[String]) -> Result<()> {
+    if let Some(arg) = args.first() {
+        anyhow::bail!("Unknown argument: {}\n\n{}", arg, USAGE);
+    }
+
+    let lock = load_lock()?;
+    let backend = backend_dir();
+    if !backend.join("shared/Cargo.toml").is_file() {
+        anyhow::bail!(
+            "No shared crate found in {}. Create the project with the shared-types extra.",
+            backend.join("shared").display()
+        );
+    }
+
+    let root = std::env::current_dir().context("Failed to read the current directory")?;
+    for dir in node_frontend_dirs(&lock)? {
+        let out = root.join(&dir).join(TYPES_DIR);
+        // ts-rs writes a file per `#[ts(export)]` type when its export tests run
+        let output = SystemRunner
+            .run(
+                &Invocation::new("cargo")
+                    .args(["test", "-p", "shared", "export_bindings"])
+                    .env("TS_RS_EXPORT_DIR", &out)
+                    .current_dir(backend)
+                    .inherit_output(),
+            )
+            .await
+            .context("Failed to run cargo test")?;
+        if !output.success() {
+            anyhow::bail!("Exporting the shared types failed with {}", output.status());
+        }
+        say!(
+            "✏️  Wrote the shared types to {}",
+            dir.join(TYPES_DIR).display()
+        );
+    }
+    Ok(())
+}
+
+/// Regenerates the TypeScript client of every Node frontend from the backend's OpenAPI spec
+pub async fn generate_client(args: &[String]) -> Result<()> {
+    let mut spec_source = None;
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--spec" => {
+                spec_source = Some(
+                    rest.next()
+                        .context(format!(
+                            "--spec needs a file or URL\n\n{}",
+                            API_CLIENT_USAGE
+                        ))?
+                        .clone(),
+                )
+            }
+            _ => anyhow::bail!("Unknown argument: {}\n\n{}", arg, API_CLIENT_USAGE),
+        }
+    }
+
+    let lock = load_lock()?;
+    let frontends = node_frontend_dirs(&lock)?;
+
+    let spec = match spec_source {
+        Some(source) if source.starts_with("http://") || source.starts_with("https://") => {
+            proxy::client_builder()
+                .build()?
+                .get(&source)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|e| {
+                    HexstackError::NetworkError(format!("Failed to fetch {}: {}", source, e))
+                })?
+                .json()
+                .await
+                .with_context(|| format!("{} is not a JSON OpenAPI spec", source))?
+        }
+        Some(source) => read_spec(Path::new(&source))?,
+        None => {
+            let path = backend_dir().join("openapi.json");
+            if !path.is_file() {
+                anyhow::bail!(
+                    "No {} found. Run the backend in debug mode once to write it, or pass --spec <file|url>",
+                    path.display()
+                );
+            }
+            read_spec(&path)?
+        }
+    };
+
+    let files = typescript_client(&spec)?;
+    for dir in frontends {
+        let client_dir = dir.join(CLIENT_DIR);
+        fs::create_dir_all(&client_dir)
+            .with_context(|| format!("Failed to create {}", client_dir.display()))?;
+        for (name, contents) in &files {
+            let path = client_dir.join(name);
+            fs::write(&path, contents)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            say!("✏️  Wrote {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+fn read_spec(path: &Path) -> Result<Value> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("{} is not a JSON OpenAPI spec", path.display()))
+}
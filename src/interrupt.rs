@@ -0,0 +1,70 @@
+//! Ctrl-C handling. While a scaffold runs, the first Ctrl-C cancels it so the engine can kill
+//! git or cargo and remove the partial project; at any other time, or on a second Ctrl-C, the
+//! terminal is restored and hexstack exits with [`EXIT_CODE`].
+
+use hexstack_core::HexstackError;
+use std::io;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio_util::sync::CancellationToken;
+
+/// Exit code after an interrupt, 128 + SIGINT as shells report it
+pub const EXIT_CODE: i32 = 130;
+
+static TOKEN: LazyLock<CancellationToken> = LazyLock::new(CancellationToken::new);
+static CLEANING_UP: AtomicBool = AtomicBool::new(false);
+
+/// Listens for Ctrl-C for the rest of the process
+pub fn install() {
+    tokio::spawn(async {
+        while tokio::signal::ctrl_c().await.is_ok() {
+            if !CLEANING_UP.load(Ordering::SeqCst) || TOKEN.is_cancelled() {
+                exit();
+            }
+            TOKEN.cancel();
+        }
+    });
+}
+
+/// The token cancelled by the first Ctrl-C, for [`ProjectSetupBuilder::cancellation`]
+///
+/// [`ProjectSetupBuilder::cancellation`]: hexstack_core::ProjectSetupBuilder::cancellation
+pub fn token() -> CancellationToken {
+    TOKEN.clone()
+}
+
+/// Marks the scaffold as running until the guard is dropped, so Ctrl-C leaves the cleanup to
+/// it instead of exiting at once
+pub fn cleanup_guard() -> CleanupGuard {
+    CLEANING_UP.store(true, Ordering::SeqCst);
+    CleanupGuard
+}
+
+pub struct CleanupGuard;
+
+impl Drop for CleanupGuard {
+    fn drop(&mut self) {
+        CLEANING_UP.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Whether `err` comes from Ctrl-C: a cancelled scaffold, or a prompt whose read was
+/// interrupted
+pub fn is_interrupt(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        matches!(cause.downcast_ref(), Some(HexstackError::Cancelled))
+            || matches!(cause.downcast_ref(), Some(dialoguer::Error::IO(io)) if io.kind() == io::ErrorKind::Interrupted)
+            || cause
+                .downcast_ref::<io::Error>()
+                .is_some_and(|io| io.kind() == io::ErrorKind::Interrupted)
+    })
+}
+
+/// Shows the cursor a select prompt or progress bar hid, and exits with [`EXIT_CODE`]
+pub fn exit() -> ! {
+    let term = console::Term::stderr();
+    let _ = term.show_cursor();
+    let _ = term.write_line("");
+    eprintln!("Interrupted");
+    std::process::exit(EXIT_CODE);
+}
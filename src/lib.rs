@@ -1,19 +1,141 @@
-mod setup;
+mod db;
+mod deploy;
+mod dev;
+mod generate;
+mod interrupt;
+mod logging;
+mod options;
+mod output;
+mod plugins;
+mod progress;
+mod template;
+mod update;
+mod upgrade;
+mod wizard;
 
 use console::Style;
-use dialoguer::{Input, MultiSelect, Select, theme::ColorfulTheme};
-use serde_json::Value;
-use tokio::process::Command as AsyncCommand;
+use dialoguer::{Confirm, FuzzySelect, Input, Select, theme::ColorfulTheme};
 
-use crate::setup::ProjectSetup;
+use crate::output::{say, say_err};
+use crate::wizard::{Wizard, WizardAnswers, WizardStep};
 use anyhow::Result;
+use hexstack_core::config::{GlobalConfig, LastAnswers};
+use hexstack_core::extras::{OPTIONAL_EXTRAS, tailwind_target};
+use hexstack_core::lockfile::LockFile;
+use hexstack_core::setup::{
+    ADDON_COMPONENTS, COMPONENTS, FRONTENDS, NODE_FRONTENDS, display_name, is_server_rendered,
+    on_path,
+};
+use std::path::PathBuf;
+
+pub use crate::logging::{LogFormat, LogOptions};
+pub use crate::options::GlobalOptions;
+pub use crate::output::OutputOptions;
+pub use crate::progress::IndicatifReporter;
+pub use hexstack_core::t;
+pub use hexstack_core::{
+    Catalog, CatalogTemplate, CommandOutput, CommandRecord, CommandRunner, ComponentInfo,
+    CreateReport, Database, Example, Extras, FrontendApp, FrontendInfo, HexstackError, Invocation,
+    JsonLinesReporter, PackageManager, Preview, ProgressReporter, ProjectSetupBuilder,
+    RegistryEntry, RetryPolicy, RunFuture, ScaffoldEvent, Service, SilentReporter, StepRecord,
+    Styling, SystemRunner, TemplateReport, ValidName,
+};
 
 #[cfg(test)]
 mod tests;
 
-pub fn parse_new_args(args: &[String]) -> Result<(Option<&String>, Option<Vec<String>>)> {
+/// Catalog key under which a `--template-url` template is registered
+const TEMPLATE_URL_KEY: &str = "template-url";
+
+/// Why `value` isn't something `--template` accepts
+fn invalid_template_message(value: &str) -> String {
+    format!(
+        "Invalid template value '{}'. Valid values: full, ripress, wynd, lume, redis, jobs, graphql, grpc, scheduler or a comma-separated combination like ripress,redis, a key from `hexstack template list`, or an owner/repo shorthand",
+        value
+    )
+}
+
+/// Options collected from the `hexstack new` command line
+#[derive(Debug, Default)]
+pub struct NewArgs {
+    pub name: Option<String>,
+    /// Components requested through `--template`
+    pub components: Option<Vec<String>>,
+    /// Custom template key requested through `--template`, looked up in the global config when
+    /// the project is created
+    pub template: Option<String>,
+    /// Template repository passed with `--template-url`, optionally as `repo#subdir`
+    pub template_url: Option<String>,
+    /// Lock file passed with `--locked` to reproduce an earlier scaffold
+    pub locked: Option<PathBuf>,
+    /// Use templates that fail checksum verification
+    pub allow_unverified: bool,
+    /// Leave the partial project on disk when a step fails (`--keep-partial`)
+    pub keep_partial: bool,
+    /// `--desktop` or `--no-desktop`: wrap the frontend in a Tauri desktop app
+    pub desktop: Option<bool>,
+    /// Frontends requested through `--frontend`; more than one generates an `apps/` layout
+    pub frontends: Option<Vec<FrontendApp>>,
+    /// Engine passed with `--database`; `Some(None)` for `--database none`
+    pub database: Option<Option<Database>>,
+    /// Extras requested through `--extras`; empty for `--extras none`
+    pub extras: Option<Vec<String>>,
+    /// Styling passed with `--styling`
+    pub styling: Option<Styling>,
+    /// Example app passed with `--example`
+    pub example: Option<Example>,
+    /// `--start-db` or `--no-start-db`: start the database in docker once the project exists
+    pub start_database: Option<bool>,
+    /// Services passed with `--services`, generated under `services/` instead of one backend
+    pub services: Option<Vec<Service>>,
+    /// Report progress as JSON lines on stdout (`--json`)
+    pub json: bool,
+    /// Leave installing the frontend's dependencies to the user (`--no-frontend-install`)
+    pub skip_frontend_install: bool,
+    /// Package manager passed with `--package-manager`
+    pub package_manager: Option<PackageManager>,
+    /// `--initial-commit` or `--no-initial-commit`: commit the finished project
+    pub initial_commit: Option<bool>,
+    /// Branch passed with `--default-branch`
+    pub default_branch: Option<String>,
+    /// Keep the project in the enclosing repository instead of running `git init` (`--no-reinit`)
+    pub no_reinit: bool,
+    /// `--create-remote`, private unless `--public` is passed too: create a GitHub repository
+    /// and push the initial commit
+    pub create_remote: Option<hexstack_core::remote::Visibility>,
+    /// Create the project without showing the summary to confirm first (`--yes`)
+    pub yes: bool,
+}
+
+pub fn parse_new_args(args: &[String]) -> Result<NewArgs, HexstackError> {
+    Ok(parse_args(args)?)
+}
+
+fn parse_args(args: &[String]) -> Result<NewArgs> {
     let mut name = None;
     let mut templates = Vec::new();
+    let mut custom_template = None;
+    let mut template_url = None;
+    let mut locked = None;
+    let mut allow_unverified = false;
+    let mut keep_partial = false;
+    let mut desktop = None;
+    let mut frontends = None;
+    let mut database = None;
+    let mut extras = None;
+    let mut styling = None;
+    let mut example = None;
+    let mut start_database = None;
+    let mut services = None;
+    let mut json = false;
+    let mut skip_frontend_install = false;
+    let mut package_manager = None;
+    let mut initial_commit = None;
+    let mut default_branch = None;
+    let mut no_reinit = false;
+    let mut create_remote = false;
+    let mut public = false;
+    let mut yes = false;
     let mut i = 0;
     let mut errors = Vec::new();
 
@@ -28,15 +150,32 @@ pub fn parse_new_args(args: &[String]) -> Result<(Option<&String>, Option<Vec<St
                             templates.push(String::from("wynd"));
                             templates.push(String::from("lume"));
                         }
-                        "ripress" | "wynd" | "lume" => {
-                            templates.push(template_value);
+                        value
+                            if value.split(',').all(|c| {
+                                COMPONENTS.contains(&c.trim())
+                                    || ADDON_COMPONENTS.contains(&c.trim())
+                            }) =>
+                        {
+                            for component in value.split(',').map(str::trim) {
+                                if !templates.iter().any(|t| t == component) {
+                                    templates.push(component.to_string());
+                                }
+                            }
                         }
-                        _ => {
-                            errors.push(format!(
-                                "Invalid template value '{}'. Valid values: full, ripress, wynd, lume",
-                                args[i + 1]
-                            ));
+                        _ if let Some(url) =
+                            hexstack_core::hosts::expand_shorthand(&args[i + 1]) =>
+                        {
+                            if template_url.replace(url).is_some() {
+                                errors
+                                    .push("Only one template repository can be given".to_string());
+                            }
+                        }
+                        // Keys never contain commas, so this was meant as a combination
+                        value if value.contains(',') => {
+                            errors.push(invalid_template_message(&args[i + 1]))
                         }
+                        // Looked up in the global config once the command runs
+                        _ => custom_template = Some(template_value),
                     }
                     i += 2;
                 } else {
@@ -44,29 +183,211 @@ pub fn parse_new_args(args: &[String]) -> Result<(Option<&String>, Option<Vec<St
                     i += 1;
                 }
             }
-            arg if !arg.starts_with('-') && name.is_none() => {
-                // Validate project name early
-                if arg.is_empty() {
-                    errors.push("Project name cannot be empty".to_string());
-                } else if arg.len() > 50 {
-                    errors.push("Project name is too long (max 50 characters)".to_string());
+            "--template-url" => {
+                if let Some(url) = args.get(i + 1) {
+                    if template_url
+                        .replace(hexstack_core::hosts::normalize_url(url))
+                        .is_some()
+                    {
+                        errors.push("Only one template repository can be given".to_string());
+                    }
+                    i += 2;
                 } else {
-                    let invalid_chars = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
-                    if arg.chars().any(|c| invalid_chars.contains(&c)) {
-                        errors.push(format!(
-                            "Project name '{}' contains invalid characters. Valid characters: letters, numbers, hyphens, underscores, and dots",
-                            arg
-                        ));
-                    } else if let Some(first_char) = arg.chars().next() {
-                        if !first_char.is_alphabetic() && first_char != '_' {
+                    errors.push("--template-url requires a value".to_string());
+                    i += 1;
+                }
+            }
+            "--locked" => {
+                if let Some(path) = args.get(i + 1) {
+                    locked = Some(PathBuf::from(path));
+                    i += 2;
+                } else {
+                    errors.push("--locked requires a lock file path".to_string());
+                    i += 1;
+                }
+            }
+            "--allow-unverified" => {
+                allow_unverified = true;
+                i += 1;
+            }
+            "--keep-partial" => {
+                keep_partial = true;
+                i += 1;
+            }
+            "--desktop" => {
+                desktop = Some(true);
+                i += 1;
+            }
+            "--no-desktop" => {
+                desktop = Some(false);
+                i += 1;
+            }
+            "--json" => {
+                json = true;
+                i += 1;
+            }
+            "--default-branch" => match args.get(i + 1).map(|value| value.trim()) {
+                Some(value) if !value.is_empty() && !value.starts_with('-') => {
+                    default_branch = Some(value.to_string());
+                    i += 2;
+                }
+                _ => {
+                    errors.push("--default-branch requires a branch name".to_string());
+                    i += 1;
+                }
+            },
+            "--create-remote" => {
+                create_remote = true;
+                i += 1;
+            }
+            "--public" => {
+                public = true;
+                i += 1;
+            }
+            "--no-reinit" => {
+                no_reinit = true;
+                i += 1;
+            }
+            "--yes" | "-y" => {
+                yes = true;
+                i += 1;
+            }
+            "--initial-commit" => {
+                initial_commit = Some(true);
+                i += 1;
+            }
+            "--no-initial-commit" => {
+                initial_commit = Some(false);
+                i += 1;
+            }
+            "--no-frontend-install" => {
+                skip_frontend_install = true;
+                i += 1;
+            }
+            "--start-db" => {
+                start_database = Some(true);
+                i += 1;
+            }
+            "--no-start-db" => {
+                start_database = Some(false);
+                i += 1;
+            }
+            "--frontend" => {
+                if let Some(value) = args.get(i + 1) {
+                    match parse_frontend_apps(value) {
+                        Ok(apps) => frontends = Some(apps),
+                        Err(err) => errors.push(err.to_string()),
+                    }
+                    i += 2;
+                } else {
+                    errors.push("--frontend requires a value".to_string());
+                    i += 1;
+                }
+            }
+            "--services" => {
+                if let Some(value) = args.get(i + 1) {
+                    match hexstack_core::services::parse_services(value) {
+                        Ok(parsed) => services = Some(parsed),
+                        Err(err) => errors.push(err.to_string()),
+                    }
+                    i += 2;
+                } else {
+                    errors.push("--services requires a value".to_string());
+                    i += 1;
+                }
+            }
+            "--database" => {
+                if let Some(value) = args.get(i + 1) {
+                    match Database::parse(value) {
+                        Some(engine) => database = Some(Some(engine)),
+                        None if value.eq_ignore_ascii_case("none") => database = Some(None),
+                        None => errors.push(format!(
+                            "Invalid database '{}'. Valid values: postgres, sqlite, mysql, none",
+                            value
+                        )),
+                    }
+                    i += 2;
+                } else {
+                    errors.push("--database requires a value".to_string());
+                    i += 1;
+                }
+            }
+            "--package-manager" => {
+                if let Some(value) = args.get(i + 1) {
+                    match PackageManager::parse(value) {
+                        Some(manager) => package_manager = Some(manager),
+                        None => errors.push(format!(
+                            "Invalid package manager '{}'. Valid values: {}",
+                            value,
+                            hexstack_core::package_manager::PACKAGE_MANAGERS.join(", ")
+                        )),
+                    }
+                    i += 2;
+                } else {
+                    errors.push("--package-manager requires a value".to_string());
+                    i += 1;
+                }
+            }
+            "--styling" => {
+                if let Some(value) = args.get(i + 1) {
+                    match Styling::parse(value) {
+                        Some(value) => styling = Some(value),
+                        None => errors.push(format!(
+                            "Invalid styling '{}'. Valid values: css, tailwind",
+                            value
+                        )),
+                    }
+                    i += 2;
+                } else {
+                    errors.push("--styling requires a value".to_string());
+                    i += 1;
+                }
+            }
+            "--example" => {
+                if let Some(value) = args.get(i + 1) {
+                    match Example::parse(value) {
+                        Some(value) => example = Some(value),
+                        None => errors.push(format!(
+                            "Invalid example '{}'. Valid values: {}",
+                            value,
+                            Example::ALL.map(Example::key).join(", ")
+                        )),
+                    }
+                    i += 2;
+                } else {
+                    errors.push("--example requires a value".to_string());
+                    i += 1;
+                }
+            }
+            "--extras" => {
+                if let Some(value) = args.get(i + 1) {
+                    let keys: Vec<String> = value
+                        .split(',')
+                        .map(|key| key.trim().to_lowercase())
+                        .filter(|key| key != "none")
+                        .collect();
+                    for key in &keys {
+                        if !OPTIONAL_EXTRAS.iter().any(|(k, _)| k == key) {
                             errors.push(format!(
-                                "Project name must start with a letter or underscore, not '{}'",
-                                first_char
+                                "Invalid extra '{}'. Valid values: {}, none",
+                                key,
+                                OPTIONAL_EXTRAS.map(|(k, _)| k).join(", ")
                             ));
                         }
                     }
+                    extras = Some(keys);
+                    i += 2;
+                } else {
+                    errors.push("--extras requires a value".to_string());
+                    i += 1;
+                }
+            }
+            arg if !arg.starts_with('-') && name.is_none() => {
+                // Validate project name early
+                if let Err(err) = hexstack_core::validation::validate_project_name(arg) {
+                    errors.push(err.to_string());
                 }
-                name = Some(&args[i]);
+                name = Some(args[i].clone());
                 i += 1;
             }
             _ => {
@@ -80,178 +401,869 @@ pub fn parse_new_args(args: &[String]) -> Result<(Option<&String>, Option<Vec<St
         anyhow::bail!("Argument parsing errors:\n{}", errors.join("\n"));
     }
 
+    if no_reinit && (default_branch.is_some() || initial_commit == Some(true) || create_remote) {
+        anyhow::bail!(
+            "--no-reinit leaves the project to the enclosing repository; it can't be combined with --default-branch, --initial-commit or --create-remote"
+        );
+    }
+    if create_remote && initial_commit == Some(false) {
+        anyhow::bail!("--create-remote pushes the initial commit, so it needs one");
+    }
+    if public && !create_remote {
+        anyhow::bail!("--public only applies to the repository --create-remote creates");
+    }
+    let create_remote = create_remote.then_some(if public {
+        hexstack_core::remote::Visibility::Public
+    } else {
+        hexstack_core::remote::Visibility::Private
+    });
+
+    if custom_template.is_some() && !templates.is_empty() {
+        anyhow::bail!("A custom template cannot be combined with component templates");
+    }
+
+    if template_url.is_some() && (custom_template.is_some() || !templates.is_empty()) {
+        anyhow::bail!("--template-url cannot be combined with --template");
+    }
+
+    if locked.is_some()
+        && (template_url.is_some() || custom_template.is_some() || !templates.is_empty())
+    {
+        anyhow::bail!("--locked cannot be combined with --template or --template-url");
+    }
+
+    if example.is_some()
+        && (template_url.is_some() || custom_template.is_some() || locked.is_some())
+    {
+        anyhow::bail!(
+            "--example builds on the Hexstack components; use --template, not a template repository"
+        );
+    }
+
+    if services.is_some()
+        && (template_url.is_some()
+            || custom_template.is_some()
+            || !templates.is_empty()
+            || locked.is_some()
+            || frontends.is_some()
+            || example.is_some()
+            || desktop == Some(true)
+            || extras.is_some())
+    {
+        anyhow::bail!(
+            "--services picks each service's components itself and generates its own docker-compose.yml; it can't be combined with --template, --frontend, --example, --desktop, --extras or --locked"
+        );
+    }
+
+    // The example's components are added to any picked with --template
+    if let Some(example) = example {
+        for component in example.components() {
+            if !templates.iter().any(|t| t == component) {
+                templates.push(component.to_string());
+            }
+        }
+    }
+
     let templates_option = if templates.is_empty() {
         None
     } else {
         Some(templates)
     };
 
-    Ok((name, templates_option))
+    Ok(NewArgs {
+        name,
+        components: templates_option,
+        template: custom_template,
+        template_url,
+        locked,
+        allow_unverified,
+        keep_partial,
+        desktop,
+        frontends,
+        database,
+        extras,
+        styling,
+        example,
+        start_database,
+        services,
+        json,
+        skip_frontend_install,
+        package_manager,
+        initial_commit,
+        default_branch,
+        no_reinit,
+        create_remote,
+        yes,
+    })
+}
+
+/// Parses `--frontend` values like `react`, `none` or `web=react,admin=svelte`
+fn parse_frontend_apps(value: &str) -> Result<Vec<FrontendApp>> {
+    if value.eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+
+    value
+        .split(',')
+        .map(|item| {
+            let (name, frontend) = match item.split_once('=') {
+                Some((name, frontend)) => (name.trim().to_string(), frontend.trim().to_lowercase()),
+                None => (item.trim().to_lowercase(), item.trim().to_lowercase()),
+            };
+            if !FRONTENDS.contains(&frontend.as_str()) {
+                anyhow::bail!(
+                    "Invalid frontend '{}'. Valid values: {}, none",
+                    frontend,
+                    FRONTENDS.join(", ")
+                );
+            }
+            Ok(FrontendApp {
+                name,
+                frontend,
+                commit: None,
+            })
+        })
+        .collect()
+}
+
+pub async fn run_template_command(args: &[String]) -> Result<(), HexstackError> {
+    Ok(template::run(args).await?)
 }
 
-pub async fn create_project(
-    project_name: Option<&String>,
-    templates: Option<Vec<String>>,
-) -> Result<()> {
+pub async fn upgrade_template(args: &[String]) -> Result<(), HexstackError> {
+    Ok(upgrade::run(args).await?)
+}
+
+pub async fn run_dev(args: &[String]) -> Result<(), HexstackError> {
+    Ok(dev::run(args).await?)
+}
+
+pub async fn run_tests(args: &[String]) -> Result<(), HexstackError> {
+    Ok(dev::test(args).await?)
+}
+
+pub async fn run_generate(args: &[String]) -> Result<(), HexstackError> {
+    Ok(generate::run(args).await?)
+}
+
+pub async fn run_db(args: &[String]) -> Result<(), HexstackError> {
+    Ok(db::run(args).await?)
+}
+
+pub async fn run_deploy(args: &[String]) -> Result<(), HexstackError> {
+    Ok(deploy::run(args).await?)
+}
+
+/// The `hexstack-<command>` plugin executable on the PATH, if one is installed
+pub fn find_plugin(command: &str) -> Option<std::path::PathBuf> {
+    plugins::find(command)
+}
+
+/// Commands provided by the plugins installed on the PATH
+pub fn installed_plugins() -> Vec<String> {
+    plugins::installed()
+}
+
+/// Runs the plugin for `command`, passing it `args` and the project context
+pub async fn run_plugin(
+    plugin: &std::path::Path,
+    command: &str,
+    args: &[String],
+) -> Result<(), HexstackError> {
+    Ok(plugins::run(plugin, command, args).await?)
+}
+
+/// Splits `-v`, `-q`, `--log-format`, `--no-update-check`, `--no-color` and `--plain` off the
+/// front of the command line
+pub fn parse_global_args(args: &[String]) -> Result<(GlobalOptions, &[String]), HexstackError> {
+    Ok(options::parse_global_args(args)?)
+}
+
+/// Turns colors off for `--no-color`, `--plain` or `NO_COLOR`, and emoji and other Unicode
+/// symbols off for `--plain`. Call it before [`init_logging`].
+pub fn init_output(options: OutputOptions) {
+    output::init(options);
+}
+
+/// Picks the language of prompts and messages from the `language` setting or `LANG`, with
+/// translations from `<config dir>/locales` on top of the bundled ones
+pub fn init_locale() {
+    let config = GlobalConfig::load().unwrap_or_default();
+    let user_dir = GlobalConfig::dir()
+        .ok()
+        .map(|dir| dir.join(hexstack_core::i18n::LOCALES_DIR));
+    hexstack_core::i18n::init(config.language.as_deref(), user_dir.as_deref());
+}
+
+/// Shows the engine's log events on stderr at the level and in the format of `options`
+pub fn init_logging(options: LogOptions) {
+    logging::init(options);
+}
+
+/// Asks for whatever `args` leaves open and creates the project. Ctrl-C, at a prompt or while
+/// scaffolding, fails with [`HexstackError::Cancelled`].
+pub async fn create_project(args: NewArgs) -> Result<(), HexstackError> {
+    new_project(args).await.map_err(|err| {
+        if interrupt::is_interrupt(&err) {
+            HexstackError::Cancelled
+        } else {
+            err.into()
+        }
+    })
+}
+
+/// Handles Ctrl-C for the rest of the process: a running scaffold is cancelled and cleaned up,
+/// otherwise the terminal is restored and hexstack exits with [`INTERRUPTED_EXIT_CODE`]
+pub fn install_interrupt_handler() {
+    interrupt::install();
+}
+
+/// The exit code after Ctrl-C
+pub const INTERRUPTED_EXIT_CODE: i32 = interrupt::EXIT_CODE;
+
+/// Whether `err` was caused by Ctrl-C
+pub fn is_interrupt(err: &anyhow::Error) -> bool {
+    interrupt::is_interrupt(err)
+}
+
+/// Restores the terminal after Ctrl-C and exits with [`INTERRUPTED_EXIT_CODE`]
+pub fn exit_interrupted() -> ! {
+    interrupt::exit()
+}
+
+async fn new_project(args: NewArgs) -> Result<()> {
+    hexstack_core::preflight::check(&hexstack_core::preflight::BASE_TOOLS)?;
+    if args.create_remote.is_some() && !hexstack_core::setup::on_path("gh") {
+        say_err!(
+            "⚠️  gh isn't installed, so --create-remote will only commit the project locally. To publish it, {}",
+            hexstack_core::remote::GH_INSTALL
+        );
+    }
+    if !hexstack_core::setup::on_path("git") {
+        say_err!(
+            "⚠️  git isn't installed, so the template will be downloaded as an archive and the project won't be a git repository. Install it with: {}",
+            hexstack_core::preflight::install_instructions("git", std::env::consts::OS)
+        );
+    }
+
+    // With --json, stdout only carries the progress events
+    let json = args.json;
+    let announce = |line: String| {
+        if json {
+            say_err!("{}", line);
+        } else {
+            say!("{}", line);
+        }
+    };
+
+    let global_config = GlobalConfig::load().unwrap_or_else(|e| {
+        eprintln!("Ignoring global config: {e}");
+        GlobalConfig::default()
+    });
+
     let dull = Style::new().dim();
     let underline = Style::new().underlined();
     let theme = ColorfulTheme {
         prompt_style: Style::new().cyan().bold(),
-        prompt_suffix: dull.apply_to(" ›".to_string()),
+        prompt_suffix: dull.apply_to(output::symbol(" ›", " >").to_string()),
         defaults_style: underline.cyan(),
         values_style: Style::new().green(),
         active_item_style: Style::new().cyan().bold(),
         inactive_item_style: dull.clone(),
-        picked_item_prefix: Style::new().green().apply_to("✔".to_string()),
-        unpicked_item_prefix: dull.apply_to("•".to_string()),
+        picked_item_prefix: Style::new()
+            .green()
+            .apply_to(output::symbol("✔", "[x]").to_string()),
+        unpicked_item_prefix: dull.apply_to(output::symbol("•", "[ ]").to_string()),
         ..ColorfulTheme::default()
     };
+    let theme = if output::plain() {
+        ColorfulTheme {
+            success_prefix: Style::new().green().apply_to("+".to_string()),
+            success_suffix: dull.apply_to("-".to_string()),
+            error_prefix: Style::new().red().apply_to("x".to_string()),
+            active_item_prefix: Style::new().green().apply_to(">".to_string()),
+            checked_item_prefix: Style::new().green().apply_to("[x]".to_string()),
+            unchecked_item_prefix: dull.apply_to("[ ]".to_string()),
+            ..theme
+        }
+    } else {
+        theme
+    };
+
+    let lock = args.locked.as_deref().map(LockFile::load).transpose()?;
 
-    let project_name: String = match project_name {
-        Some(name) => name.clone(),
-        None => {
-            let project_name: String = Input::with_theme(&theme)
-                .with_prompt("What should the name of your project be")
-                .default("my-app".into())
-                .interact_text()?;
-            project_name
+    // The summary is only shown to someone who can answer it
+    let confirm = !json && !args.yes && console::user_attended();
+    let mut previous = None;
+    let (builder, wizard) = loop {
+        let answers = ask(
+            &args,
+            lock.as_ref(),
+            &global_config,
+            &theme,
+            &announce,
+            previous.as_ref(),
+        )?;
+        let builder = project_builder(&args, lock.as_ref(), answers.clone());
+        if !confirm {
+            break (builder, answers.wizard);
+        }
+
+        let preview = builder.preview().await?;
+        println!();
+        for line in summary_lines(&answers, &preview) {
+            say!("{}", line);
+        }
+        println!();
+        let choice = Select::with_theme(&theme)
+            .with_prompt(t!("prompt-create"))
+            .items([t!("create-yes"), t!("create-go-back"), t!("create-cancel")])
+            .default(0)
+            .interact()?;
+        match choice {
+            0 => break (builder, answers.wizard),
+            1 => previous = Some(answers),
+            _ => {
+                println!("{}", t!("cancelled"));
+                return Ok(());
+            }
         }
     };
+    let builder = builder.cancellation(interrupt::token());
+    let _cleanup = interrupt::cleanup_guard();
+    if json {
+        let report = builder.create_with(&JsonLinesReporter::stdout()).await?;
+        println!(
+            "{}",
+            serde_json::json!({ "event": "report", "report": report })
+        );
+    } else {
+        builder.create_with(&IndicatifReporter::new()).await?;
+    }
+    if wizard != WizardAnswers::default() {
+        remember_answers(&wizard);
+    }
+
+    Ok(())
+}
 
-    println!("📦 Creating project `{}`", project_name);
+/// What `hexstack new` collected from the command line, the lock file and the prompts
+#[derive(Debug, Clone)]
+struct Answers {
+    project_name: String,
+    custom_template: Option<RegistryEntry>,
+    components: Vec<String>,
+    frontend: Option<String>,
+    apps: Vec<FrontendApp>,
+    desktop: bool,
+    extras: Extras,
+    services: Vec<Service>,
+    start_database: bool,
+    package_manager: Option<PackageManager>,
+    /// What the wizard was answered with, remembered for the next run
+    wizard: WizardAnswers,
+}
 
-    let component_options = &["ripress", "wynd", "lume"];
+/// Prompts for whatever `args` and `lock` leave open. Going back from the summary asks again,
+/// starting from the `previous` answers.
+fn ask(
+    args: &NewArgs,
+    lock: Option<&LockFile>,
+    global_config: &GlobalConfig,
+    theme: &ColorfulTheme,
+    announce: &dyn Fn(String),
+    previous: Option<&Answers>,
+) -> Result<Answers> {
+    // Templates registered with `hexstack template add` are offered ahead of the component picker
+    let custom_template = match args.template.clone() {
+        _ if lock.is_some() => lock.map(|lock| RegistryEntry {
+            key: TEMPLATE_URL_KEY.to_string(),
+            name: lock.template.name.clone(),
+            url: lock.template_url(),
+            components: lock.template.components.clone(),
+            frontend: lock.template.frontend.clone(),
+            subdir: None,
+            sha256: lock.template.sha256.clone(),
+            size: None,
+            min_version: None,
+            desktop: lock.template.desktop,
+        }),
+        _ if args.template_url.is_some() => args.template_url.clone().map(|url| RegistryEntry {
+            key: TEMPLATE_URL_KEY.to_string(),
+            name: url.clone(),
+            url,
+            components: Vec::new(),
+            frontend: None,
+            subdir: None,
+            sha256: None,
+            size: None,
+            min_version: None,
+            desktop: args.desktop == Some(true),
+        }),
+        Some(key) => match global_config.find_template(&key) {
+            Some(template) => Some(template.clone()),
+            None => anyhow::bail!(invalid_template_message(&key)),
+        },
+        None if args.components.is_none() && !global_config.templates.is_empty() => {
+            let mut template_options = vec![t!("template-hexstack-stack")];
+            template_options.extend(
+                global_config
+                    .templates
+                    .iter()
+                    .map(|t| format!("{} ({})", t.name, t.key)),
+            );
 
-    let selected_components = match templates {
-        Some(templates) => templates,
-        None => {
-            let selections = MultiSelect::with_theme(&theme)
-                .with_prompt("Select the components you want (space to select, enter to confirm)")
-                .items(component_options.iter().map(|f| capitalize(f)))
+            let selection = FuzzySelect::with_theme(theme)
+                .with_prompt(t!("prompt-template"))
+                .items(&template_options)
+                .default(0)
                 .interact()?;
 
-            let selected_components: Vec<String> = selections
-                .into_iter()
-                .map(|i| component_options[i].to_string())
-                .collect();
-
-            selected_components
+            selection
+                .checked_sub(1)
+                .map(|i| global_config.templates[i].clone())
         }
+        None => None,
     };
 
-    let frontend_options = vec!["react", "svelte", "none"];
+    let mut apps = lock.map(|l| l.apps.clone()).unwrap_or_default();
+    let services = match (lock, args.services.clone()) {
+        (Some(lock), _) => lock.services.clone(),
+        (None, services) => services.unwrap_or_default(),
+    };
+    // The name, components, frontends and extras the flags leave open are asked in the wizard
+    let choosing_stack = lock.is_none() && services.is_empty() && custom_template.is_none();
+    let choosing_frontends = choosing_stack && args.frontends.is_none() && args.example.is_none();
+    let node_frontend = args
+        .frontends
+        .iter()
+        .flatten()
+        .map(|app| app.frontend.as_str())
+        .chain(custom_template.as_ref().and_then(|t| t.frontend.as_deref()))
+        .any(|frontend| NODE_FRONTENDS.contains(&frontend));
+    let steps: Vec<WizardStep> = [
+        (WizardStep::Name, args.name.is_none() && lock.is_none()),
+        (
+            WizardStep::Components,
+            choosing_stack && args.components.is_none(),
+        ),
+        (WizardStep::Frontends, choosing_frontends),
+        (
+            WizardStep::PackageManager,
+            lock.is_none()
+                && args.package_manager.is_none()
+                && global_config.package_manager.is_none()
+                && (choosing_frontends || node_frontend),
+        ),
+        (
+            WizardStep::Extras,
+            lock.is_none() && services.is_empty() && args.extras.is_none(),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(step, open)| open.then_some(step))
+    .collect();
+    let wizard = if steps.is_empty() {
+        WizardAnswers::default()
+    } else {
+        // Going back starts from the answers just given, a new run from the last run's
+        let defaults = match previous {
+            Some(p) => WizardAnswers {
+                name: Some(p.project_name.clone()),
+                components: Some(p.components.clone()),
+                frontends: Some(match p.apps.as_slice() {
+                    [] => p.frontend.iter().cloned().collect(),
+                    apps => apps.iter().map(|app| app.frontend.clone()).collect(),
+                }),
+                package_manager: p.wizard.package_manager.clone(),
+                extras: Some(p.extras.enabled().into_iter().map(String::from).collect()),
+            },
+            None => {
+                let last = global_config.last_answers.clone().unwrap_or_default();
+                WizardAnswers {
+                    components: Some(last.components),
+                    frontends: Some(last.frontends),
+                    package_manager: last.package_manager.map(|m| m.to_string()),
+                    ..WizardAnswers::default()
+                }
+            }
+        };
+        wizard::run(Wizard::new(steps, &defaults))?
+    };
 
-    let selection = Select::with_theme(&theme)
-        .with_prompt("Select the frontend you want")
-        .items(frontend_options.clone().into_iter().map(|f| capitalize(f)))
-        .interact()?;
+    let project_name = match (&args.name, lock, &wizard.name) {
+        (Some(name), _, _) => name.clone(),
+        (None, Some(lock), _) => lock.project_name.clone(),
+        (None, None, name) => name.clone().unwrap_or_default(),
+    };
 
-    let selected_frontend = frontend_options[selection];
+    announce(format!(
+        "📦 {}",
+        t!("creating", name = project_name.as_str())
+    ));
 
-    if selected_frontend == "none" {
-        println!("🚧 Creating project `{}` without frontend", project_name);
+    let (selected_components, selected_frontend, desktop) = if !services.is_empty() {
+        announce(format!(
+            "🚧 {}",
+            t!(
+                "creating-services",
+                name = project_name.as_str(),
+                count = services.len()
+            )
+        ));
+        (
+            hexstack_core::services::all_components(&services),
+            None,
+            false,
+        )
+    } else if let Some(template) = &custom_template {
+        announce(format!(
+            "🚧 {}",
+            t!(
+                "creating-from-template",
+                name = project_name.as_str(),
+                template = template.name.as_str()
+            )
+        ));
+        (
+            template.components.clone(),
+            template.frontend.clone(),
+            template.desktop,
+        )
     } else {
-        println!("🚧 Creating project `{}` with frontend", project_name);
-    }
+        let selected_components = args
+            .components
+            .clone()
+            .or_else(|| wizard.components.clone())
+            .unwrap_or_default();
+
+        let frontend_apps = match args.frontends.clone() {
+            Some(apps) => apps,
+            // Examples serve their own page, so they don't ask for a frontend
+            None if args.example.is_some() => Vec::new(),
+            None => {
+                let frontends = wizard.frontends.clone().unwrap_or_default();
+                let multiple = frontends.len() > 1;
+                let mut selected = Vec::new();
+                for frontend in frontends {
+                    let name = if multiple {
+                        let previous_name = previous
+                            .and_then(|p| p.apps.iter().find(|app| app.frontend == frontend))
+                            .map_or_else(|| frontend.clone(), |app| app.name.clone());
+                        Input::with_theme(theme)
+                            .with_prompt(t!("prompt-app-name", frontend = display_name(&frontend)))
+                            .default(previous_name)
+                            .interact_text()?
+                    } else {
+                        frontend.clone()
+                    };
+                    selected.push(FrontendApp {
+                        name,
+                        frontend,
+                        commit: None,
+                    });
+                }
+                selected
+            }
+        };
 
-    let selected_frontend = match selected_frontend {
-        "react" => Some(String::from("react")),
-        "svelte" => Some(String::from("svelte")),
-        "none" => None,
-        _ => {
-            eprintln!("Error: Invalid frontend");
-            None
+        let selected_frontend = frontend_apps.first().map(|app| app.frontend.clone());
+        hexstack_core::preflight::check(&hexstack_core::preflight::frontend_tools(
+            frontend_apps.iter().map(|app| app.frontend.as_str()),
+        ))?;
+        match frontend_apps.len() {
+            0 => announce(format!(
+                "🚧 {}",
+                t!("creating-without-frontend", name = project_name.as_str())
+            )),
+            1 => announce(format!(
+                "🚧 {}",
+                t!("creating-with-frontend", name = project_name.as_str())
+            )),
+            count => announce(format!(
+                "🚧 {}",
+                t!(
+                    "creating-with-frontend-apps",
+                    name = project_name.as_str(),
+                    count = count
+                )
+            )),
+        }
+        if frontend_apps.len() > 1 {
+            apps = frontend_apps;
         }
-    };
 
-    let project_setup =
-        ProjectSetup::new(project_name, selected_components, selected_frontend).await;
-    project_setup.build().await?;
+        // Only asked when someone is there to answer; scripts get a web app unless --desktop
+        let unattended = args.json || args.yes || !console::user_attended();
+        let desktop = match (&selected_frontend, args.desktop) {
+            (_, Some(desktop)) => desktop,
+            (Some(frontend), None)
+                if !unattended && apps.is_empty() && !is_server_rendered(frontend) =>
+            {
+                Confirm::with_theme(theme)
+                    .with_prompt(t!("prompt-desktop"))
+                    .default(false)
+                    .interact()?
+            }
+            _ => false,
+        };
 
-    Ok(())
-}
+        (selected_components, selected_frontend, desktop)
+    };
 
-fn capitalize(word: &str) -> String {
-    let mut chars = word.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-    }
-}
+    let database = match (lock, args.database) {
+        (Some(lock), _) => lock.extras.database,
+        (None, Some(database)) => database,
+        (None, None) if selected_components.iter().any(|c| c == "lume") => {
+            let mut options: Vec<String> = Database::ALL
+                .iter()
+                .map(|d| d.display_name().to_string())
+                .collect();
+            options.push(t!("database-none"));
 
-pub async fn update_if_needed() -> Result<()> {
-    let version = env!("CARGO_PKG_VERSION");
-    let latest_version = get_latest_version().await?;
+            let selection = Select::with_theme(theme)
+                .with_prompt(t!("prompt-database"))
+                .items(&options)
+                .default(0)
+                .interact()?;
+            Database::ALL.get(selection).copied()
+        }
+        (None, None) => None,
+    };
 
-    // Update only if the remote version is strictly greater than the current one.
-    let current = semver::Version::parse(version)
-        .map_err(|e| anyhow::anyhow!("Invalid current version '{}': {}", version, e))?;
-    let latest: semver::Version = semver::Version::parse(&latest_version)
-        .map_err(|e| anyhow::anyhow!("Invalid latest version '{}': {}", latest_version, e))?;
+    let tailwind_frontend = if apps.is_empty() {
+        selected_frontend.as_deref().and_then(tailwind_target)
+    } else {
+        apps.iter().find_map(|app| tailwind_target(&app.frontend))
+    };
+    let styling = match (lock, args.styling) {
+        (Some(lock), _) => lock.extras.styling,
+        (None, Some(styling)) => styling,
+        (None, None) if tailwind_frontend.is_some() => {
+            let selection = Select::with_theme(theme)
+                .with_prompt(t!("prompt-styling"))
+                .items([t!("styling-css"), t!("styling-tailwind")])
+                .default(0)
+                .interact()?;
+            [Styling::Css, Styling::Tailwind][selection]
+        }
+        (None, None) => Styling::Css,
+    };
 
-    if latest > current {
-        println!(
-            "A new version of hexstack is available ({} → {})",
-            version, latest_version
-        );
-        println!("Updating...");
+    let mut extras = Extras {
+        database,
+        styling,
+        ..Extras::default()
+    };
+    let extra_keys = match (lock, args.extras.clone()) {
+        (Some(lock), _) => {
+            extras = lock.extras.clone();
+            Vec::new()
+        }
+        (None, Some(keys)) => keys,
+        // The services get their own docker-compose.yml and workspace instead
+        (None, None) if !services.is_empty() => Vec::new(),
+        (None, None) => wizard.extras.clone().unwrap_or_default(),
+    };
+    for key in extra_keys {
+        extras.enable(&key);
+    }
+
+    let db_name = project_name.replace(['-', '.'], "_");
+    let start_database = match (
+        database
+            .filter(|_| services.is_empty())
+            .and_then(|d| d.ready_check(&db_name)),
+        args.start_database,
+    ) {
+        (None, _) => false,
+        (Some(_), Some(start)) => start,
+        (Some(_), None) if on_path("docker") => Confirm::with_theme(theme)
+            .with_prompt(t!("prompt-start-database"))
+            .default(true)
+            .interact()?,
+        (Some(_), None) => false,
+    };
 
-        let output = AsyncCommand::new("cargo")
-            .args(["install", "hexstack", "--force", "--locked"])
-            .output()
-            .await?;
+    Ok(Answers {
+        project_name,
+        custom_template,
+        components: selected_components,
+        frontend: selected_frontend,
+        apps,
+        desktop,
+        extras,
+        services,
+        start_database,
+        package_manager: wizard
+            .package_manager
+            .as_deref()
+            .and_then(PackageManager::parse),
+        wizard,
+    })
+}
 
-        if !output.status.success() {
-            let error = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow::anyhow!("Failed to update: {}", error));
+/// Saves what the wizard was answered with as the defaults of the next run, unless
+/// `remember_answers = false`. Questions the flags answered keep their earlier defaults.
+fn remember_answers(answers: &WizardAnswers) {
+    let result = GlobalConfig::load().and_then(|mut config| {
+        if config.remember_answers == Some(false) {
+            return Ok(());
         }
+        let last = config.last_answers.take().unwrap_or_default();
+        config.last_answers = Some(updated_last_answers(last, answers));
+        config.save()
+    });
+    if let Err(err) = result {
+        eprintln!("Could not save the answers for next time: {:#}", err);
+    }
+}
 
-        println!("Updated hexstack to the latest version!");
-        println!("Please restart hexstack to use the new version.");
+/// `last` with the questions the wizard asked replaced by its answers
+fn updated_last_answers(mut last: LastAnswers, answers: &WizardAnswers) -> LastAnswers {
+    if let Some(components) = &answers.components {
+        last.components = components.clone();
+    }
+    if let Some(frontends) = &answers.frontends {
+        last.frontends = frontends.clone();
+    }
+    if let Some(package_manager) = &answers.package_manager {
+        last.package_manager = PackageManager::parse(package_manager);
+    }
+    last
+}
 
-        std::process::exit(0);
+fn project_builder(
+    args: &NewArgs,
+    lock: Option<&LockFile>,
+    answers: Answers,
+) -> ProjectSetupBuilder {
+    let mut builder = ProjectSetupBuilder::new(answers.project_name);
+    if let Some(template) = answers.custom_template {
+        builder = builder.template(template);
     }
-    Ok(())
+    if let Some(lock) = lock {
+        builder = builder.pinned_commit(lock.template.commit.clone());
+    }
+    if let Some(example) = args.example {
+        builder = builder.example(example);
+    }
+    builder = if !answers.apps.is_empty() {
+        builder.frontend_apps(answers.apps)
+    } else if let Some(frontend) = answers.frontend {
+        builder.frontend(frontend)
+    } else {
+        builder
+    };
+    if let Some(commit) = args.initial_commit {
+        builder = builder.initial_commit(commit);
+    }
+    if let Some(branch) = &args.default_branch {
+        builder = builder.default_branch(branch.clone());
+    }
+    builder
+        .components(answers.components)
+        .allow_unverified(args.allow_unverified)
+        .keep_partial(args.keep_partial)
+        .desktop(answers.desktop)
+        .extras(answers.extras)
+        .services(answers.services)
+        .start_database(answers.start_database)
+        .install_frontend(!args.skip_frontend_install)
+        .package_manager(args.package_manager.or(answers.package_manager))
+        .reinit_git(!args.no_reinit)
+        .create_remote(args.create_remote)
 }
 
-async fn get_latest_version() -> Result<String> {
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(3))
-        .user_agent(format!(
-            "hexstack/{} (+https://github.com/guru901/hexstack)",
-            env!("CARGO_PKG_VERSION")
-        ))
-        .build()
-        .map_err(|e| anyhow::anyhow!("Failed to build HTTP client: {}", e))?;
-
-    let response = client
-        .get("https://crates.io/api/v1/crates/hexstack")
-        .header(reqwest::header::ACCEPT, "application/json")
-        .send()
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to fetch crate info: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(anyhow::anyhow!(
-            "API request failed with status: {}",
-            response.status()
-        ));
+/// The summary `hexstack new` asks to confirm before it starts fetching and writing. The values
+/// line up after the longest label, whichever language the labels are in.
+fn summary_lines(answers: &Answers, preview: &Preview) -> Vec<String> {
+    let none = || t!("summary-none");
+    let list = |items: Vec<String>| {
+        if items.is_empty() {
+            none()
+        } else {
+            items.join(", ")
+        }
+    };
+
+    let mut rows = vec![(t!("summary-name"), answers.project_name.clone())];
+    let template = match (&answers.custom_template, preview.templates.as_slice()) {
+        (Some(template), _) => template.name.clone(),
+        (None, []) => t!("summary-no-template"),
+        (None, templates) => templates.join(", "),
+    };
+    rows.push((t!("summary-template"), template));
+
+    if !answers.services.is_empty() {
+        let services = answers.services.iter().map(|s| s.name.clone()).collect();
+        rows.push((t!("summary-services"), list(services)));
+    }
+    let components = answers.components.iter().map(|c| display_name(c)).collect();
+    rows.push((t!("summary-components"), list(components)));
+    let frontend = if !answers.apps.is_empty() {
+        let apps: Vec<String> = answers
+            .apps
+            .iter()
+            .map(|app| format!("{} ({})", app.name, display_name(&app.frontend)))
+            .collect();
+        apps.join(", ")
+    } else {
+        answers.frontend.as_deref().map_or_else(none, display_name)
+    };
+    let frontend = if answers.desktop {
+        t!("summary-desktop", frontend = frontend)
+    } else {
+        frontend
+    };
+    rows.push((t!("summary-frontend"), frontend));
+    rows.push((
+        t!("summary-database"),
+        answers
+            .extras
+            .database
+            .map_or_else(none, |d| d.display_name().to_string()),
+    ));
+    if answers.extras.styling == Styling::Tailwind {
+        rows.push((t!("summary-styling"), t!("styling-tailwind")));
     }
+    let extras = answers
+        .extras
+        .enabled()
+        .iter()
+        .map(|e| e.to_string())
+        .collect();
+    rows.push((t!("summary-extras"), list(extras)));
+    rows.push((
+        t!("summary-path"),
+        std::path::absolute(&preview.project_path)
+            .unwrap_or_else(|_| preview.project_path.clone())
+            .display()
+            .to_string(),
+    ));
 
-    let json: Value = response
-        .json()
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to parse JSON response: {}", e))?;
-
-    let latest_version = json
-        .get("crate")
-        .and_then(|c| {
-            c.get("max_stable_version")
-                .or_else(|| c.get("max_version"))
-                .or_else(|| c.get("newest_version"))
-        })
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| anyhow::anyhow!("Invalid API response format"))?;
+    let width = rows
+        .iter()
+        .map(|(label, _)| label.chars().count() + 2)
+        .max()
+        .unwrap_or_default();
+    let mut lines = vec![t!("summary")];
+    lines.extend(
+        rows.into_iter()
+            .map(|(label, value)| format!("  {:<width$}{}", label, value)),
+    );
+    lines.push(format!("  {}", t!("summary-commands")));
+    lines.extend(
+        preview
+            .commands
+            .iter()
+            .map(|command| format!("    {}", command)),
+    );
+    lines
+}
 
-    Ok(latest_version.to_string())
+/// Installs a newer hexstack release when one is published, unless update checks are turned off
+/// with `HEXSTACK_NO_UPDATE` or `check_updates = false`
+pub async fn update_if_needed() -> Result<(), HexstackError> {
+    Ok(update::update_if_needed().await?)
 }
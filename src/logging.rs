@@ -0,0 +1,55 @@
+//! The engine reports what it does through `tracing`; the CLI decides how much of it to show and
+//! in which format, from the global options parsed in [`crate::options`].
+
+use std::io::IsTerminal;
+use tracing::level_filters::LevelFilter;
+
+use crate::output;
+
+/// How engine log lines are written to stderr
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Plain messages, with the level and target added in verbose mode
+    #[default]
+    Text,
+    /// One JSON object per event, for log collectors
+    Json,
+}
+
+/// Logging options parsed from the global flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogOptions {
+    pub level: LevelFilter,
+    pub format: LogFormat,
+}
+
+impl Default for LogOptions {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::INFO,
+            format: LogFormat::Text,
+        }
+    }
+}
+
+/// Installs the formatter for engine events on stderr, keeping stdout for command output such
+/// as `hexstack new --json`
+pub fn init(options: LogOptions) {
+    let builder = tracing_subscriber::fmt()
+        .with_max_level(options.level)
+        .with_writer(|| output::Stderr);
+    let result = match options.format {
+        LogFormat::Json => builder.json().try_init(),
+        LogFormat::Text => {
+            let verbose = options.level > LevelFilter::INFO;
+            builder
+                .without_time()
+                .with_level(verbose)
+                .with_target(verbose)
+                .with_ansi(std::io::stderr().is_terminal() && output::colors())
+                .try_init()
+        }
+    };
+    // Only fails when a subscriber is already installed, e.g. by an embedding tool
+    let _ = result;
+}
@@ -1,43 +1,117 @@
 #[tokio::main]
 async fn main() {
     let args: Vec<String> = std::env::args().collect();
+    hexstack::init_locale();
 
-    if args.len() == 1 {
-        eprintln!("Incorrect usage");
-        eprintln!("Usage: hexstack new [project-name] [--template <template>]");
-        eprintln!("\nExamples:");
-        eprintln!("  hexstack new my-app");
-        eprintln!("  hexstack new my-app --template full");
-        eprintln!("  hexstack new my-app --template ripress");
-        eprintln!("  hexstack new my-app --template wynd");
-        eprintln!("  hexstack new my-app --template lume");
+    let (options, rest) = match hexstack::parse_global_args(&args[1..]) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}", hexstack::t!("error", message = err.to_string()));
+            std::process::exit(1);
+        }
+    };
+    let Some((command, rest)) = rest.split_first() else {
+        print_usage();
         return;
-    }
-
-    let command = &args[1];
+    };
+    hexstack::init_output(options.output);
+    hexstack::init_logging(options.log);
+    hexstack::install_interrupt_handler();
 
-    if let Err(e) = hexstack::update_if_needed().await {
+    if !options.no_update_check
+        && let Err(e) = hexstack::update_if_needed().await
+    {
         eprintln!("Auto-update check failed: {e}");
         eprintln!("Continuing without updating. To update manually, run: cargo install hexstack");
     }
 
-    let result = match command.as_str() {
-        "new" => match hexstack::parse_new_args(&args[2..]) {
-            Ok((name, templates)) => hexstack::create_project(name, templates).await,
-            Err(e) => Err(e),
-        },
-        "--version" => {
-            println!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
-            Ok(())
+    if let Err(err) = run(command, rest).await {
+        if hexstack::is_interrupt(&err) {
+            hexstack::exit_interrupted();
         }
-        _ => Err(anyhow::anyhow!(
-            "Unknown command: {}\n\nAvailable commands:\n  new    Create a new project",
-            command
-        )),
-    };
-
-    if let Err(err) = result {
-        eprintln!("Error: {}", err);
+        eprintln!("{}", hexstack::t!("error", message = err.to_string()));
         std::process::exit(1);
     }
 }
+
+/// Printed when hexstack runs without a command
+fn print_usage() {
+    eprintln!("Incorrect usage");
+    eprintln!(
+        "Usage: hexstack new [project-name] [--template <template> | --template-url <url[#subdir]>] [--frontend <a[,name=b]>] [--desktop | --no-desktop] [--database <postgres|sqlite|mysql|none>] [--styling <css|tailwind>] [--example <chat|todo|blog>] [--start-db | --no-start-db] [--package-manager <npm|pnpm|yarn|bun>] [--no-frontend-install] [--initial-commit | --no-initial-commit] [--default-branch <name> | --no-reinit] [--create-remote [--public]] [--keep-partial] [--json] [--yes] [--extras <docker,ci,tracing,config,tests,e2e,email,openapi,shared-types,uploads,rate-limit,sessions,otel,admin,devcontainer,nix,git-hooks,toolchain,k8s,workspace|none>]"
+    );
+    eprintln!("       hexstack template <new|validate|list|preview|add|remove>");
+    eprintln!("       hexstack upgrade-template [--to <revision>] [--dry-run]");
+    eprintln!("       hexstack dev");
+    eprintln!("       hexstack test");
+    eprintln!("       hexstack generate <api-client [--spec <file|url>] | types>");
+    eprintln!("       hexstack db seed");
+    eprintln!(
+        "       hexstack deploy [docker|fly|shuttle] [--registry <registry>] [--app <name>] [--tag <tag>]"
+    );
+    eprintln!("       hexstack <plugin> [args...]   runs hexstack-<plugin> from the PATH");
+    eprintln!("\nGlobal options, before the command:");
+    eprintln!("  -v, -vv          show debug or trace output from the scaffolding engine");
+    eprintln!("  -q, --quiet      only show warnings and errors");
+    eprintln!("  --log-format <text|json>");
+    eprintln!(
+        "  --no-update-check  don't look for a new hexstack release (or set HEXSTACK_NO_UPDATE=1)"
+    );
+    eprintln!("  --no-color       don't color the output (or set NO_COLOR=1)");
+    eprintln!("  --plain          no colors, and ASCII instead of emoji and Unicode symbols");
+    eprintln!("\nExamples:");
+    eprintln!("  hexstack new my-app");
+    eprintln!("  hexstack new my-app --template full");
+    eprintln!("  hexstack new my-app --template ripress");
+    eprintln!("  hexstack new my-app --template wynd");
+    eprintln!("  hexstack new my-app --template lume");
+    eprintln!("  hexstack new my-app --template ripress,lume --database postgres");
+    eprintln!("  hexstack new my-app --template lume --database postgres --start-db");
+    eprintln!("  hexstack new my-app --template ripress,redis,jobs");
+    eprintln!("  hexstack new my-app --template ripress,graphql --frontend react");
+    eprintln!("  hexstack new my-app --template guru901/ripress-react");
+    eprintln!("  hexstack new my-app --template-url https://github.com/me/templates#api");
+    eprintln!("  hexstack new my-app --template ripress --frontend web=react,admin=svelte");
+    eprintln!(
+        "  hexstack new my-app --template ripress --frontend react --styling tailwind --extras docker,ci"
+    );
+    eprintln!("  hexstack new demo --example chat");
+    eprintln!("  hexstack new --locked .hexstack.lock");
+    eprintln!("  hexstack -v new my-app --template ripress");
+}
+
+async fn run(command: &str, args: &[String]) -> anyhow::Result<()> {
+    match command {
+        "new" => hexstack::create_project(hexstack::parse_new_args(args)?).await?,
+        "template" => hexstack::run_template_command(args).await?,
+        "upgrade-template" => hexstack::upgrade_template(args).await?,
+        "dev" => hexstack::run_dev(args).await?,
+        "test" => hexstack::run_tests(args).await?,
+        "generate" => hexstack::run_generate(args).await?,
+        "db" => hexstack::run_db(args).await?,
+        "deploy" => hexstack::run_deploy(args).await?,
+        "--version" => println!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+        _ => match hexstack::find_plugin(command) {
+            Some(plugin) => hexstack::run_plugin(&plugin, command, args).await?,
+            None => anyhow::bail!(
+                "Unknown command: {}\n\nAvailable commands:\n  new               Create a new project\n  template          Manage custom templates and registries\n  upgrade-template  Pull upstream template changes into this project\n  dev               Run the backend and frontend dev servers\n  test              Run the backend and frontend tests\n  generate          Generate code from the project, e.g. the OpenAPI client or shared types\n  db                Run database tasks such as loading seed data\n  deploy            Build the project and ship it to a registry, Fly.io or Shuttle{}",
+                command,
+                plugin_list()
+            ),
+        },
+    }
+    Ok(())
+}
+
+/// The installed `hexstack-<command>` plugins, listed after the built-in commands
+fn plugin_list() -> String {
+    let plugins = hexstack::installed_plugins();
+    if plugins.is_empty() {
+        return String::new();
+    }
+    let mut list = String::from("\n\nInstalled plugins:");
+    for plugin in plugins {
+        list.push_str(&format!("\n  {}", plugin));
+    }
+    list
+}
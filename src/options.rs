@@ -0,0 +1,77 @@
+//! Options that apply to every command and come before it:
+//!
+//! ```text
+//! hexstack -v new my-app                  # debug: commands run, template events
+//! hexstack -vv new my-app                 # trace: files written, command stderr
+//! hexstack -q new my-app                  # warnings and errors only
+//! hexstack --log-format json new my-app   # one JSON object per log line
+//! hexstack --no-update-check new my-app   # don't look for a new release
+//! hexstack --no-color new my-app          # no colors, also with NO_COLOR=1
+//! hexstack --plain new my-app             # no colors, emoji or Unicode symbols
+//! ```
+
+use anyhow::Result;
+use tracing::level_filters::LevelFilter;
+
+use crate::logging::{LogFormat, LogOptions};
+use crate::output::OutputOptions;
+
+/// Options given before the command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GlobalOptions {
+    pub log: LogOptions,
+    pub output: OutputOptions,
+    /// Skip the check for a new hexstack release (`--no-update-check`)
+    pub no_update_check: bool,
+}
+
+/// Splits the global options off the front of `args`, returning them with the command and its
+/// arguments
+pub fn parse_global_args(args: &[String]) -> Result<(GlobalOptions, &[String])> {
+    let mut options = GlobalOptions::default();
+    let mut verbosity = 0;
+    let mut quiet = false;
+
+    let mut i = 0;
+    while let Some(arg) = args.get(i) {
+        match arg.as_str() {
+            "-v" | "--verbose" => verbosity += 1,
+            "-vv" => verbosity += 2,
+            "-q" | "--quiet" => quiet = true,
+            "--no-update-check" => options.no_update_check = true,
+            "--no-color" => options.output.no_color = true,
+            "--plain" => options.output.plain = true,
+            "--log-format" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| anyhow::anyhow!("--log-format needs a value: text or json"))?;
+                options.log.format = parse_format(value)?;
+            }
+            _ => match arg.strip_prefix("--log-format=") {
+                Some(value) => options.log.format = parse_format(value)?,
+                None => break,
+            },
+        }
+        i += 1;
+    }
+
+    if quiet && verbosity > 0 {
+        anyhow::bail!("--quiet can't be combined with --verbose");
+    }
+    options.log.level = match (quiet, verbosity) {
+        (true, _) => LevelFilter::WARN,
+        (false, 0) => LevelFilter::INFO,
+        (false, 1) => LevelFilter::DEBUG,
+        (false, _) => LevelFilter::TRACE,
+    };
+    Ok((options, &args[i..]))
+}
+
+fn parse_format(value: &str) -> Result<LogFormat> {
+    match value {
+        "text" => Ok(LogFormat::Text),
+        "json" => Ok(LogFormat::Json),
+        _ => anyhow::bail!("Unknown log format '{}': use text or json", value),
+    }
+}
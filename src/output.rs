@@ -0,0 +1,150 @@
+//! How hexstack decorates what it prints. Colors are turned off by `--no-color` or the `NO_COLOR`
+//! convention (<https://no-color.org>); `--plain` also turns emoji, box drawing and the other
+//! Unicode symbols into ASCII, for terminals and logs that can't show them.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static COLORS: AtomicBool = AtomicBool::new(true);
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Output options parsed from the global flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OutputOptions {
+    /// `--no-color`
+    pub no_color: bool,
+    /// `--plain`: no colors and ASCII only
+    pub plain: bool,
+}
+
+/// Applies `options` and the `NO_COLOR` environment variable to everything printed afterwards
+pub fn init(options: OutputOptions) {
+    let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let colors = !(options.no_color || options.plain || no_color_env);
+    COLORS.store(colors, Ordering::Relaxed);
+    PLAIN.store(options.plain, Ordering::Relaxed);
+    if !colors {
+        console::set_colors_enabled(false);
+        console::set_colors_enabled_stderr(false);
+    }
+}
+
+/// Whether output may be colored
+pub fn colors() -> bool {
+    COLORS.load(Ordering::Relaxed)
+}
+
+/// Whether output is limited to ASCII
+pub fn plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// `unicode`, or `ascii` with `--plain`
+pub fn symbol(unicode: &'static str, ascii: &'static str) -> &'static str {
+    if plain() { ascii } else { unicode }
+}
+
+/// `line` as it should be printed: unchanged, or turned into ASCII with `--plain`
+pub fn text(line: &str) -> Cow<'_, str> {
+    if plain() && !line.is_ascii() {
+        Cow::Owned(ascii(line))
+    } else {
+        Cow::Borrowed(line)
+    }
+}
+
+/// Replaces the symbols hexstack prints with ASCII and drops the purely decorative ones, such
+/// as the emoji at the start of a status line, with the spaces after them
+pub fn ascii(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        let status = match c {
+            '✅' | '✔' | '✓' => "[ok]",
+            '⚠' => "[!]",
+            '❌' | '✘' | '✗' => "[x]",
+            _ => "",
+        };
+        if !status.is_empty() {
+            // Emoji are followed by two spaces to make up for their width; one is enough here
+            out.push_str(status);
+            skip_variation(&mut chars);
+            if chars.next_if_eq(&' ').is_some() {
+                out.push(' ');
+                while chars.next_if_eq(&' ').is_some() {}
+            }
+            continue;
+        }
+        match c {
+            '—' | '–' | '─' | '━' => out.push('-'),
+            '│' | '┃' => out.push('|'),
+            '\u{2500}'..='\u{257F}' => out.push('+'),
+            '…' => out.push_str("..."),
+            '›' | '❯' | '»' => out.push('>'),
+            '→' => out.push_str("->"),
+            '←' => out.push_str("<-"),
+            '↑' => out.push('^'),
+            '↓' => out.push('v'),
+            '•' | '◉' => out.push('*'),
+            '·' => out.push('-'),
+            '○' | '⬚' => out.push('o'),
+            '\u{FE0F}' | '\u{200D}' => {}
+            _ if is_pictograph(c) => {
+                skip_variation(&mut chars);
+                while chars.next_if_eq(&' ').is_some() {}
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn skip_variation(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+    while chars.next_if_eq(&'\u{FE0F}').is_some() {}
+}
+
+/// Emoji and the other symbol blocks hexstack decorates its output with
+fn is_pictograph(c: char) -> bool {
+    matches!(c,
+        '\u{2190}'..='\u{21FF}'
+        | '\u{2300}'..='\u{23FF}'
+        | '\u{25A0}'..='\u{27BF}'
+        | '\u{2B00}'..='\u{2BFF}'
+        | '\u{1F000}'..='\u{1FAFF}')
+}
+
+/// Stderr, with the text written to it turned into ASCII under `--plain`. Engine log lines go
+/// through it.
+pub struct Stderr;
+
+impl Write for Stderr {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if plain() {
+            io::stderr().write_all(ascii(&String::from_utf8_lossy(buf)).as_bytes())?;
+            Ok(buf.len())
+        } else {
+            io::stderr().write(buf)
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stderr().flush()
+    }
+}
+
+/// Prints a line on stdout, in ASCII under `--plain`
+macro_rules! say {
+    ($($arg:tt)*) => {
+        println!("{}", $crate::output::text(&format!($($arg)*)))
+    };
+}
+
+/// Prints a line on stderr, in ASCII under `--plain`
+macro_rules! say_err {
+    ($($arg:tt)*) => {
+        eprintln!("{}", $crate::output::text(&format!($($arg)*)))
+    };
+}
+
+pub(crate) use {say, say_err};
@@ -0,0 +1,133 @@
+//! Commands hexstack doesn't know are run as `hexstack-<command>` executables from the PATH,
+//! the way cargo runs its subcommands. The plugin gets the remaining arguments, the terminal,
+//! and a JSON description of the project in `HEXSTACK_PLUGIN_CONTEXT`:
+//!
+//! ```json
+//! {"protocol":1,"hexstack_version":"0.5.0","command":"lint","args":["--fix"],"cwd":"/src/my-app","project":{...}}
+//! ```
+//!
+//! `project` is the parsed `.hexstack.lock` of the current directory, or `null` outside a
+//! project. `HEXSTACK` holds the path of the hexstack binary so plugins can call back into it.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use hexstack_core::lockfile::{LOCK_FILE, LockFile};
+use hexstack_core::runner::{CommandRunner, Invocation, SystemRunner};
+
+/// Prefix of plugin executables, e.g. `hexstack-lint` for `hexstack lint`
+pub const PLUGIN_PREFIX: &str = "hexstack-";
+
+/// Environment variable the plugin context is passed in
+pub const CONTEXT_VAR: &str = "HEXSTACK_PLUGIN_CONTEXT";
+
+/// Version of the context format; bumped when fields change meaning
+const PROTOCOL_VERSION: u32 = 1;
+
+/// What a plugin is told about the invocation and the project it runs in
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginContext {
+    pub protocol: u32,
+    pub hexstack_version: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub cwd: PathBuf,
+    pub project: Option<LockFile>,
+}
+
+impl PluginContext {
+    /// Describes running `command` with `args` in `cwd`
+    pub fn new(command: &str, args: &[String], cwd: &Path) -> Result<Self> {
+        let lock_path = cwd.join(LOCK_FILE);
+        let project = if lock_path.is_file() {
+            Some(LockFile::load(&lock_path)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            protocol: PROTOCOL_VERSION,
+            hexstack_version: env!("CARGO_PKG_VERSION").to_string(),
+            command: command.to_string(),
+            args: args.to_vec(),
+            cwd: cwd.to_path_buf(),
+            project,
+        })
+    }
+}
+
+/// The plugin executable for `command` on the PATH, if one is installed
+pub fn find(command: &str) -> Option<PathBuf> {
+    find_in(command, &std::env::var_os("PATH")?)
+}
+
+/// Looks for the plugin executable for `command` in the directories of `path`
+pub fn find_in(command: &str, path: &OsStr) -> Option<PathBuf> {
+    let valid = !command.is_empty() && !command.starts_with('-') && !command.contains(['/', '\\']);
+    if !valid {
+        return None;
+    }
+    let name = format!("{}{}", PLUGIN_PREFIX, command);
+    std::env::split_paths(path).find_map(|dir| {
+        [dir.join(&name), dir.join(format!("{}.exe", name))]
+            .into_iter()
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+/// Names of the plugins installed on the PATH, for the list of available commands
+pub fn installed() -> Vec<String> {
+    let Some(path) = std::env::var_os("PATH") else {
+        return Vec::new();
+    };
+    let mut plugins: Vec<String> = std::env::split_paths(&path)
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let command = name.strip_prefix(PLUGIN_PREFIX)?;
+            Some(command.strip_suffix(".exe").unwrap_or(command).to_string())
+        })
+        .filter(|command| !command.is_empty())
+        .collect();
+    plugins.sort();
+    plugins.dedup();
+    plugins
+}
+
+/// Runs `plugin` for `command` in the current directory, failing when it exits unsuccessfully
+pub async fn run(plugin: &Path, command: &str, args: &[String]) -> Result<()> {
+    let cwd = std::env::current_dir().context("Failed to read the current directory")?;
+    run_in(plugin, command, args, &cwd).await
+}
+
+/// Runs `plugin` for `command` inside `cwd`
+pub async fn run_in(plugin: &Path, command: &str, args: &[String], cwd: &Path) -> Result<()> {
+    let context = PluginContext::new(command, args, cwd)?;
+    let context =
+        serde_json::to_string(&context).context("Failed to serialize the plugin context")?;
+
+    let mut invocation = Invocation::new(plugin.display().to_string())
+        .args(args)
+        .current_dir(cwd)
+        .env(CONTEXT_VAR, context)
+        .inherit_output();
+    if let Ok(exe) = std::env::current_exe() {
+        invocation = invocation.env("HEXSTACK", exe);
+    }
+
+    let output = SystemRunner
+        .run(&invocation)
+        .await
+        .with_context(|| format!("Failed to run {}", plugin.display()))?;
+    if !output.success() {
+        anyhow::bail!(
+            "`{}{}` failed with {}",
+            PLUGIN_PREFIX,
+            command,
+            output.status()
+        );
+    }
+    Ok(())
+}
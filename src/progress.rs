@@ -0,0 +1,59 @@
+//! Progress reporters that draw on the terminal. The reporter trait and the
+//! non-interactive reporters live in `hexstack_core::progress`.
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+pub use hexstack_core::progress::*;
+
+use crate::output::{self, say_err};
+
+/// Draws an indicatif progress bar on the terminal
+pub struct IndicatifReporter {
+    bar: ProgressBar,
+}
+
+impl IndicatifReporter {
+    pub fn new() -> Self {
+        let bar = ProgressBar::new(0);
+        if let Ok(style) = ProgressStyle::default_bar().template(
+            "{spinner:.green} [{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {msg}",
+        ) {
+            let style = style.progress_chars("#>-");
+            bar.set_style(if output::plain() {
+                style.tick_chars("-\\|/ ")
+            } else {
+                style
+            });
+        }
+        Self { bar }
+    }
+}
+
+impl Default for IndicatifReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressReporter for IndicatifReporter {
+    fn start(&self, total_steps: u64) {
+        self.bar.set_length(total_steps);
+    }
+
+    fn message(&self, message: &str) {
+        self.bar.set_message(output::text(message).into_owned());
+    }
+
+    fn step(&self) {
+        self.bar.inc(1);
+    }
+
+    fn warn(&self, message: &str) {
+        self.bar.suspend(|| say_err!("⚠️  {}", message));
+    }
+
+    fn finish(&self, message: &str) {
+        self.bar
+            .finish_with_message(output::text(message).into_owned());
+    }
+}
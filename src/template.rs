@@ -0,0 +1,856 @@
+use anyhow::{Context, Result};
+use console::style;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::output::{self, say};
+use hexstack_core::catalog::Catalog;
+use hexstack_core::config::GlobalConfig;
+use hexstack_core::extras::{Database, Extras};
+use hexstack_core::hosts;
+use hexstack_core::integrity;
+use hexstack_core::manifest::{self, MANIFEST_FILE, PLACEHOLDERS, TemplateInfo, TemplateManifest};
+use hexstack_core::registry::{self, RegistryEntry};
+use hexstack_core::retry::RetryPolicy;
+use hexstack_core::runner::{CommandRunner, Invocation, SystemRunner};
+use hexstack_core::setup::{
+    ADDON_COMPONENTS, COMPONENTS, FRONTENDS, ProjectSetup, clone_with_retry,
+};
+use hexstack_core::workspace::{Workspace, copy_dir};
+
+const USAGE: &str = "Usage:
+  hexstack template new <directory> [--name <name>] [--components <a,b>] [--frontend <frontend>]
+  hexstack template validate [directory-or-repo-url] [--skip-build]
+  hexstack template list
+  hexstack template preview <key-or-repo>
+  hexstack template add <key> <repo-url> [--name <name>] [--components <a,b>] [--frontend <frontend>]
+  hexstack template add --registry <index-url>
+  hexstack template remove <key-or-registry-url>";
+
+/// Why `key` can't name a custom template: `--template` already reads it as a component list,
+/// a repository shorthand or a built-in template, so the custom one could never be picked
+fn reserved_key_problem(key: &str) -> Option<String> {
+    if key.contains(['/', ',']) {
+        return Some(format!(
+            "'{}' would be read as a repository or a component list, template keys can't contain '/' or ','",
+            key
+        ));
+    }
+    if key == "full" || COMPONENTS.contains(&key) || ADDON_COMPONENTS.contains(&key) {
+        return Some(format!(
+            "'{}' is a built-in template value, please pick another key",
+            key
+        ));
+    }
+    if Catalog::builtin().template(key).is_some() {
+        return Some(format!(
+            "'{}' is a built-in template, please pick another key",
+            key
+        ));
+    }
+    None
+}
+
+pub async fn run(args: &[String]) -> Result<()> {
+    match args.first().map(|a| a.as_str()) {
+        Some("new") => new(&args[1..]),
+        Some("validate") => validate(&args[1..]).await,
+        Some("list") => list().await,
+        Some("preview") => preview(&args[1..]).await,
+        Some("add") => add(&args[1..]),
+        Some("remove") => remove(&args[1..]),
+        Some(other) => anyhow::bail!("Unknown template command: {}\n\n{}", other, USAGE),
+        None => anyhow::bail!("Missing template command\n\n{}", USAGE),
+    }
+}
+
+async fn list() -> Result<()> {
+    let config = GlobalConfig::load()?;
+
+    println!("Built-in templates:");
+    for template in Catalog::builtin().templates() {
+        println!("  {:<28} {}", template.key, template.name);
+    }
+
+    println!("\nRegistries:");
+    println!("  {} (official)", registry::registry_url());
+    for url in &config.registries {
+        println!("  {}", url);
+    }
+
+    if !config.templates.is_empty() {
+        println!("\nCustom templates:");
+        for template in &config.templates {
+            println!(
+                "  {:<28} {} - {}",
+                template.key, template.name, template.url
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn add(args: &[String]) -> Result<()> {
+    let mut config = GlobalConfig::load()?;
+
+    if args.first().map(|a| a.as_str()) == Some("--registry") {
+        let url = args
+            .get(1)
+            .ok_or_else(|| anyhow::anyhow!("--registry requires a value\n\n{}", USAGE))?;
+        if config.add_registry(url.clone()) {
+            config.save()?;
+            say!("✅ Added registry {}", url);
+        } else {
+            println!("Registry {} is already configured", url);
+        }
+        return Ok(());
+    }
+
+    let entry = parse_template_entry(args)?;
+    let key = entry.key.clone();
+    config.add_template(entry);
+    config.save()?;
+    say!(
+        "✅ Added template `{}`. Use it with: hexstack new my-app --template {}",
+        key,
+        key
+    );
+
+    Ok(())
+}
+
+fn remove(args: &[String]) -> Result<()> {
+    let key_or_url = args
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("Missing template key or registry URL\n\n{}", USAGE))?;
+
+    let mut config = GlobalConfig::load()?;
+    if !config.remove(key_or_url) {
+        anyhow::bail!("No custom template or registry named '{}'", key_or_url);
+    }
+    config.save()?;
+    say!("🗑️  Removed {}", key_or_url);
+
+    Ok(())
+}
+
+/// Positional arguments and shared flags of `template add` and `template new`
+struct TemplateFlags {
+    positional: Vec<String>,
+    name: Option<String>,
+    components: Vec<String>,
+    frontend: Option<String>,
+}
+
+fn parse_template_flags(args: &[String]) -> Result<TemplateFlags> {
+    let mut flags = TemplateFlags {
+        positional: Vec::new(),
+        name: None,
+        components: Vec::new(),
+        frontend: None,
+    };
+    let mut i = 0;
+
+    while i < args.len() {
+        match args[i].as_str() {
+            flag @ ("--name" | "--components" | "--frontend") => {
+                let value = args
+                    .get(i + 1)
+                    .ok_or_else(|| anyhow::anyhow!("{} requires a value", flag))?;
+                match flag {
+                    "--name" => flags.name = Some(value.clone()),
+                    "--components" => {
+                        flags.components = value
+                            .split(',')
+                            .map(|c| c.trim().to_lowercase())
+                            .filter(|c| !c.is_empty())
+                            .collect()
+                    }
+                    _ => flags.frontend = Some(value.to_lowercase()).filter(|f| f != "none"),
+                }
+                i += 2;
+            }
+            arg if !arg.starts_with('-') => {
+                flags.positional.push(arg.to_string());
+                i += 1;
+            }
+            arg => anyhow::bail!("Unknown argument: {}\n\n{}", arg, USAGE),
+        }
+    }
+
+    if let Some(unknown) = flags
+        .components
+        .iter()
+        .find(|c| !COMPONENTS.contains(&c.as_str()))
+    {
+        anyhow::bail!(
+            "Unknown component '{}'. Valid components: ripress, wynd, lume",
+            unknown
+        );
+    }
+
+    Ok(flags)
+}
+
+pub fn parse_template_entry(args: &[String]) -> Result<RegistryEntry> {
+    let flags = parse_template_flags(args)?;
+
+    let [key, url] = flags.positional.as_slice() else {
+        anyhow::bail!("Expected a template key and a repository URL\n\n{}", USAGE);
+    };
+
+    let key = key.to_lowercase();
+    if let Some(problem) = reserved_key_problem(&key) {
+        anyhow::bail!(problem);
+    }
+
+    let url = hosts::expand_shorthand(url).unwrap_or_else(|| hosts::normalize_url(url));
+    let (url, subdir) = match url.split_once('#') {
+        Some((repo, subdir)) => (repo.to_string(), Some(subdir.to_string())),
+        None => (url, None),
+    };
+
+    Ok(RegistryEntry {
+        name: flags.name.unwrap_or_else(|| key.clone()),
+        key,
+        url,
+        components: flags.components,
+        frontend: flags.frontend,
+        subdir,
+        sha256: None,
+        size: None,
+        min_version: None,
+        desktop: false,
+    })
+}
+
+fn new(args: &[String]) -> Result<()> {
+    let flags = parse_template_flags(args)?;
+
+    let [dir] = flags.positional.as_slice() else {
+        anyhow::bail!(
+            "Expected the directory to create the template in\n\n{}",
+            USAGE
+        );
+    };
+
+    let dir = Path::new(dir);
+    if dir.exists() {
+        anyhow::bail!("'{}' already exists", dir.display());
+    }
+
+    let name = flags.name.clone().unwrap_or_else(|| {
+        dir.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "my-template".to_string())
+    });
+
+    let manifest = TemplateManifest {
+        template: TemplateInfo {
+            name,
+            description: Some(
+                "Describe what projects generated from this template contain".to_string(),
+            ),
+            components: flags.components,
+            frontend: flags.frontend,
+            min_hexstack_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        },
+        toolchain: Default::default(),
+        cleanup: Default::default(),
+    };
+
+    write_template_skeleton(dir, &manifest)?;
+
+    say!("🎉 Template skeleton created in '{}'", dir.display());
+    println!("\nNext steps:");
+    println!("  cd {}", dir.display());
+    println!("  sh scripts/check-template.sh");
+    println!("\nTry it locally with:");
+    println!(
+        "  hexstack template add {} <repo-url> --components {}",
+        dir.display(),
+        manifest.template.components.join(",")
+    );
+
+    Ok(())
+}
+
+/// Writes a minimal template repository following hexstack conventions
+pub fn write_template_skeleton(dir: &Path, manifest: &TemplateManifest) -> Result<()> {
+    let placeholder_docs: String = PLACEHOLDERS
+        .iter()
+        .map(|p| format!("#   {{{{{}}}}}\n", p))
+        .collect();
+    let mut manifest_contents = format!(
+        "# hexstack template manifest\n#\n# Files in this repository may use these placeholders, which are replaced\n# when a project is generated:\n{}\n{}",
+        placeholder_docs,
+        manifest.to_toml()?
+    );
+    if manifest.toolchain.is_empty() {
+        manifest_contents.push_str(
+            "\n# Versions the template is tested with, pinned by the toolchain extra:\n# [toolchain]\n# rust = \"1.85.0\"\n# node = \"22\"\n",
+        );
+    }
+    if manifest.cleanup.is_default() {
+        manifest_contents.push_str(
+            "\n# Files removed from generated projects, LICENSE, CONTRIBUTING.md, .github/FUNDING.yml\n# and the issue templates by default. README.md loses its badges and gets the project's name.\n# [cleanup]\n# remove = [\"LICENSE\", \".github/ISSUE_TEMPLATE\"]\n# keep = [\"LICENSE\"]\n# readme = false\n",
+        );
+    }
+
+    let dependencies: String = manifest
+        .template
+        .components
+        .iter()
+        .map(|c| format!("{} = \"*\"\n", c))
+        .collect();
+
+    let files = [
+        (MANIFEST_FILE.to_string(), manifest_contents),
+        (
+            "Cargo.toml".to_string(),
+            format!(
+                "[package]\nname = \"{{{{project_name}}}}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n# Pin these before publishing the template\n[dependencies]\n{}tokio = {{ version = \"1\", features = [\"macros\", \"rt-multi-thread\"] }}\n",
+                dependencies
+            ),
+        ),
+        (
+            "src/main.rs".to_string(),
+            "#[tokio::main]\nasync fn main() {\n    println!(\"Hello from {{project_name}}!\");\n}\n"
+                .to_string(),
+        ),
+        (
+            "README.md".to_string(),
+            format!(
+                "# {{{{project_name}}}}\n\nGenerated from the {} hexstack template.\n\n```sh\ncargo run\n```\n",
+                manifest.template.name
+            ),
+        ),
+        (".gitignore".to_string(), "target/\n".to_string()),
+        ("scripts/check-template.sh".to_string(), check_script()),
+    ];
+
+    for (relative, contents) in files {
+        let path = dir.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create '{}'", parent.display()))?;
+        }
+        fs::write(&path, contents)
+            .with_context(|| format!("Failed to write '{}'", path.display()))?;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(
+            dir.join("scripts/check-template.sh"),
+            fs::Permissions::from_mode(0o755),
+        )
+        .context("Failed to make the check script executable")?;
+    }
+
+    Ok(())
+}
+
+/// A POSIX shell script that renders the template into a scratch directory and compiles it, so
+/// it runs the same locally and under any CI provider
+fn check_script() -> String {
+    // Placeholders are assembled at runtime so generating a project doesn't rewrite the script
+    let mut values: Vec<_> = manifest::placeholder_values("template-check", &sample_extras())
+        .into_iter()
+        .collect();
+    values.sort();
+    let substitutions: String = values
+        .into_iter()
+        .map(|(key, value)| {
+            format!(
+                " -e \"s/${{open}}{}${{close}}/{}/g\"",
+                key,
+                value.replace('/', "\\/")
+            )
+        })
+        .collect();
+
+    format!(
+        r#"#!/bin/sh
+# Checks that this template renders and compiles.
+set -eu
+
+cd "$(dirname "$0")/.."
+
+# Prefer the full validation when hexstack itself is installed
+if command -v hexstack >/dev/null 2>&1; then
+    exec hexstack template validate .
+fi
+
+if [ ! -f {manifest} ]; then
+    echo "error: {manifest} is missing" >&2
+    exit 1
+fi
+
+open='{{{{'
+close='}}}}'
+scratch="$(mktemp -d)"
+trap 'rm -rf "$scratch"' EXIT
+
+cp -R . "$scratch/template"
+rm -rf "$scratch/template/.git" "$scratch/template/target"
+cd "$scratch/template"
+
+find . -type f ! -name {manifest} | while read -r file; do
+    sed{substitutions} "$file" > "$file.rendered"
+    mv "$file.rendered" "$file"
+done
+
+if grep -rn "${{open}}[a-z_]*${{close}}" --exclude={manifest} .; then
+    echo "error: unknown placeholders found" >&2
+    exit 1
+fi
+
+if [ -d backend ]; then
+    cd backend
+fi
+cargo check
+echo "Template OK"
+"#,
+        manifest = MANIFEST_FILE,
+        substitutions = substitutions,
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    /// File (and line, when known) the issue was found in, relative to the template root
+    pub location: String,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn error(location: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            location: location.into(),
+            message: message.into(),
+        }
+    }
+
+    fn warning(location: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            location: location.into(),
+            message: message.into(),
+        }
+    }
+}
+
+async fn preview(args: &[String]) -> Result<()> {
+    let [target] = args else {
+        anyhow::bail!("Expected a single template key or repository\n\n{}", USAGE);
+    };
+
+    let url = resolve_preview_target(target).await?;
+    let (repo, subdir) = match url.split_once('#') {
+        Some((repo, subdir)) => (repo, Some(subdir)),
+        None => (url.as_str(), None),
+    };
+
+    let clone_dir = tempfile::tempdir().context("Failed to create a temporary directory")?;
+    let checkout = clone_dir.path().join("template");
+    shallow_clone(repo, &checkout).await?;
+
+    let dir = match subdir {
+        Some(subdir) => checkout.join(subdir),
+        None => checkout,
+    };
+    if !dir.is_dir() {
+        anyhow::bail!("'{}' has no '{}' directory", repo, subdir.unwrap_or("."));
+    }
+
+    let root = subdir.unwrap_or_else(|| {
+        repo.trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or(repo)
+    });
+    println!("\n{}", style(format!("{}/", root)).bold());
+    print!(
+        "{}",
+        output::text(&render_tree(&integrity::list_files(&dir)?))
+    );
+
+    let readme = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.file_stem()
+                .is_some_and(|stem| stem.eq_ignore_ascii_case("readme"))
+                && path.is_file()
+        });
+    match readme.and_then(|path| fs::read_to_string(path).ok()) {
+        Some(contents) => {
+            println!();
+            for line in contents.lines() {
+                if line.starts_with('#') {
+                    println!(
+                        "{}",
+                        style(line.trim_start_matches('#').trim()).bold().cyan()
+                    );
+                } else {
+                    println!("{}", line);
+                }
+            }
+        }
+        None => println!("\n(no README)"),
+    }
+
+    Ok(())
+}
+
+/// Maps a catalog key, forge shorthand or URL to a `repo[#subdir]` URL
+async fn resolve_preview_target(target: &str) -> Result<String> {
+    if target.contains("://") || target.starts_with("git@") {
+        return Ok(hosts::normalize_url(target));
+    }
+
+    let key = target.to_lowercase();
+    let config = GlobalConfig::load().unwrap_or_default();
+
+    let mut catalog = ProjectSetup::load_templates().await;
+    if let Some(remote) = registry::load_remote_templates(&config).await {
+        catalog.extend(remote);
+    }
+    catalog.extend(registry::custom_templates(&config.templates));
+
+    if let Some(template) = catalog.get(&key) {
+        return Ok(template.github_url.clone());
+    }
+    hosts::expand_shorthand(target).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unknown template '{}'. Use a key from `hexstack template list`, an owner/repo shorthand or a repository URL",
+            target
+        )
+    })
+}
+
+/// Draws sorted relative paths as an indented tree
+pub fn render_tree(paths: &[PathBuf]) -> String {
+    #[derive(Default)]
+    struct Node {
+        children: BTreeMap<String, Node>,
+    }
+
+    fn draw(node: &Node, prefix: &str, out: &mut String) {
+        let count = node.children.len();
+        for (i, (name, child)) in node.children.iter().enumerate() {
+            let last = i + 1 == count;
+            let branch = if last { "└── " } else { "├── " };
+            let suffix = if child.children.is_empty() { "" } else { "/" };
+            out.push_str(&format!("{}{}{}{}\n", prefix, branch, name, suffix));
+            let nested = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            draw(child, &nested, out);
+        }
+    }
+
+    let mut root = Node::default();
+    for path in paths {
+        let mut node = &mut root;
+        for component in path.components() {
+            node = node
+                .children
+                .entry(component.as_os_str().to_string_lossy().into_owned())
+                .or_default();
+        }
+    }
+
+    let mut out = String::new();
+    draw(&root, "", &mut out);
+    out
+}
+
+async fn validate(args: &[String]) -> Result<()> {
+    let mut target = None;
+    let mut skip_build = false;
+    for arg in args {
+        match arg.as_str() {
+            "--skip-build" => skip_build = true,
+            arg if !arg.starts_with('-') && target.is_none() => target = Some(arg.to_string()),
+            arg => anyhow::bail!("Unknown argument: {}\n\n{}", arg, USAGE),
+        }
+    }
+    let target = target.unwrap_or_else(|| ".".to_string());
+
+    // Remote templates are validated from a shallow clone that is removed afterwards
+    let clone_dir;
+    let dir: PathBuf = if target.contains("://") || target.starts_with("git@") {
+        clone_dir = tempfile::tempdir().context("Failed to create a temporary directory")?;
+        let checkout = clone_dir.path().join("template");
+        shallow_clone(&target, &checkout).await?;
+        checkout
+    } else {
+        PathBuf::from(&target)
+    };
+
+    if !dir.is_dir() {
+        anyhow::bail!("'{}' is not a directory", dir.display());
+    }
+
+    say!("🔍 Validating template in '{}'", target);
+    let mut issues = validate_template_dir(&dir)?;
+
+    let has_errors = issues.iter().any(|i| i.severity == Severity::Error);
+    if !has_errors && !skip_build {
+        say!("🔨 Checking that the rendered template compiles...");
+        if let Some(issue) = compile_check(&dir).await? {
+            issues.push(issue);
+        }
+    }
+
+    for issue in &issues {
+        let marker = match issue.severity {
+            Severity::Error => "❌",
+            Severity::Warning => "⚠️ ",
+        };
+        say!("  {} {}: {}", marker, issue.location, issue.message);
+    }
+
+    let errors = issues
+        .iter()
+        .filter(|i| i.severity == Severity::Error)
+        .count();
+    let warnings = issues.len() - errors;
+    if errors > 0 {
+        anyhow::bail!(
+            "Template validation failed with {} error(s) and {} warning(s)",
+            errors,
+            warnings
+        );
+    }
+
+    say!("✅ Template is valid ({} warning(s))", warnings);
+    println!(
+        "   sha256: {} (publish this as the registry entry's `sha256`)",
+        integrity::tree_checksum(&dir)?
+    );
+    Ok(())
+}
+
+/// Runs the static checks: manifest schema, project layout and placeholder syntax
+pub fn validate_template_dir(dir: &Path) -> Result<Vec<ValidationIssue>> {
+    let mut issues = Vec::new();
+
+    match TemplateManifest::load(dir) {
+        Ok(Some(manifest)) => validate_manifest(dir, &manifest.template, &mut issues),
+        Ok(None) => issues.push(ValidationIssue::error(
+            MANIFEST_FILE,
+            "manifest is missing; run `hexstack template new` to see the expected layout",
+        )),
+        Err(e) => issues.push(ValidationIssue::error(MANIFEST_FILE, format!("{:#}", e))),
+    }
+
+    if !dir.join("Cargo.toml").is_file() && !dir.join("backend/Cargo.toml").is_file() {
+        issues.push(ValidationIssue::error(
+            "Cargo.toml",
+            "no Cargo.toml found at the template root or in backend/",
+        ));
+    }
+
+    manifest::for_each_text_file(dir, &mut |path, contents| {
+        let relative = path.strip_prefix(dir).unwrap_or(path).display().to_string();
+        issues.extend(check_placeholders(&relative, &contents));
+        Ok(())
+    })?;
+
+    Ok(issues)
+}
+
+fn validate_manifest(dir: &Path, info: &TemplateInfo, issues: &mut Vec<ValidationIssue>) {
+    if info.name.trim().is_empty() {
+        issues.push(ValidationIssue::error(
+            MANIFEST_FILE,
+            "`template.name` must not be empty",
+        ));
+    }
+
+    if info.components.is_empty() {
+        issues.push(ValidationIssue::warning(
+            MANIFEST_FILE,
+            "`template.components` is empty, so the template can only be selected by key",
+        ));
+    }
+    for component in &info.components {
+        if !COMPONENTS.contains(&component.to_lowercase().as_str()) {
+            issues.push(ValidationIssue::error(
+                MANIFEST_FILE,
+                format!(
+                    "unknown component '{}' (valid components: {})",
+                    component,
+                    COMPONENTS.join(", ")
+                ),
+            ));
+        }
+    }
+
+    if let Some(frontend) = &info.frontend {
+        if !FRONTENDS.contains(&frontend.to_lowercase().as_str()) {
+            issues.push(ValidationIssue::warning(
+                MANIFEST_FILE,
+                format!(
+                    "frontend '{}' has no built-in support (known frontends: {})",
+                    frontend,
+                    FRONTENDS.join(", ")
+                ),
+            ));
+        }
+        if !dir.join("frontend/package.json").is_file() && !dir.join("package.json").is_file() {
+            issues.push(ValidationIssue::warning(
+                "frontend/package.json",
+                "a frontend is declared but no package.json was found",
+            ));
+        }
+    }
+
+    if let Some(min_version) = &info.min_hexstack_version {
+        match semver::Version::parse(min_version) {
+            Ok(min) => {
+                let current = semver::Version::parse(env!("CARGO_PKG_VERSION"))
+                    .expect("crate version is valid semver");
+                if min > current {
+                    issues.push(ValidationIssue::warning(
+                        MANIFEST_FILE,
+                        format!(
+                            "requires hexstack {} but this is {}, so the template will be hidden from this release",
+                            min, current
+                        ),
+                    ));
+                }
+            }
+            Err(e) => issues.push(ValidationIssue::error(
+                MANIFEST_FILE,
+                format!(
+                    "`template.min_hexstack_version` '{}' is not a valid version: {}",
+                    min_version, e
+                ),
+            )),
+        }
+    }
+}
+
+/// Flags placeholders that would silently survive rendering
+pub fn check_placeholders(location: &str, contents: &str) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    for (line_number, line) in contents.lines().enumerate() {
+        let mut rest = line;
+        while let Some(start) = rest.find("{{") {
+            let after = &rest[start + 2..];
+            let Some(end) = after.find("}}") else {
+                break;
+            };
+            let inner = &after[..end];
+            let trimmed = inner.trim();
+            let at = format!("{}:{}", location, line_number + 1);
+
+            if PLACEHOLDERS.contains(&trimmed) && inner != trimmed {
+                issues.push(ValidationIssue::error(
+                    at,
+                    format!(
+                        "placeholder `{{{{{}}}}}` must not contain spaces, write `{{{{{}}}}}`",
+                        inner, trimmed
+                    ),
+                ));
+            } else if !trimmed.is_empty()
+                && inner == trimmed
+                && !PLACEHOLDERS.contains(&trimmed)
+                && trimmed.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+            {
+                issues.push(ValidationIssue::warning(
+                    at,
+                    format!(
+                        "unknown placeholder `{{{{{}}}}}` will be left as-is (known: {})",
+                        trimmed,
+                        PLACEHOLDERS.join(", ")
+                    ),
+                ));
+            }
+
+            rest = &after[end + 2..];
+        }
+    }
+
+    issues
+}
+
+/// Extras used when checking templates, so database placeholders render to something real
+fn sample_extras() -> Extras {
+    Extras {
+        database: Some(Database::Sqlite),
+        ..Extras::default()
+    }
+}
+
+/// Renders the template into a scratch directory and runs `cargo check` on it
+async fn compile_check(dir: &Path) -> Result<Option<ValidationIssue>> {
+    let scratch = tempfile::tempdir().context("Failed to create a temporary directory")?;
+    let rendered = scratch.path().join("template-check");
+    copy_dir(dir, &rendered)?;
+    manifest::apply_placeholders(
+        &Workspace::new(&rendered),
+        "template-check",
+        &sample_extras(),
+    )?;
+
+    let backend = rendered.join("backend");
+    let crate_dir = if backend.join("Cargo.toml").is_file() {
+        backend
+    } else {
+        rendered
+    };
+
+    let output = SystemRunner
+        .run(
+            &Invocation::new("cargo")
+                .arg("check")
+                .current_dir(&crate_dir),
+        )
+        .await
+        .context("Failed to execute cargo check")?;
+
+    if output.success() {
+        return Ok(None);
+    }
+
+    Ok(Some(ValidationIssue::error(
+        "cargo check",
+        format!(
+            "the rendered template does not compile:\n{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+    )))
+}
+
+async fn shallow_clone(url: &str, dest: &Path) -> Result<()> {
+    say!("📥 Cloning {}...", url);
+    let clone = Invocation::new("git")
+        .args(["clone", "--depth", "1", url])
+        .arg(dest);
+    let output = clone_with_retry(&SystemRunner, RetryPolicy::default(), &clone, dest)
+        .await
+        .context("Failed to execute git clone command")?;
+    if !output.success() {
+        anyhow::bail!(
+            "Failed to clone '{}': {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
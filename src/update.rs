@@ -0,0 +1,412 @@
+//! Keeps hexstack up to date. When a newer release is out and hexstack runs in a terminal, it
+//! asks before updating; scripts and CI only get a notice. The release is installed from the
+//! prebuilt binary attached to its GitHub release, which takes seconds and needs no Rust
+//! toolchain; `cargo install` is the fallback for targets without a binary or when the download
+//! fails.
+//!
+//! Updates stay on the `update_channel` of the global config (`stable`, or `beta` to include
+//! prereleases) and within `pin_major_version` when it is set.
+//!
+//! `--no-update-check`, `HEXSTACK_NO_UPDATE=1` or `check_updates = false` in the global config
+//! turn the check off. Otherwise hexstack's entry in the crates.io sparse index is fetched at
+//! most once per `update_check_interval_hours` (24 by default); the published versions and the
+//! entry's ETag are cached in `<cache dir>/hexstack/update-check.toml`, so most runs make no
+//! network request and an offline machine reports the failure once per interval.
+
+use anyhow::{Context, Result};
+use dialoguer::{Confirm, theme::ColorfulTheme};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+use crate::output::say_err;
+use hexstack_core::config::GlobalConfig;
+use hexstack_core::error::HexstackError;
+use hexstack_core::proxy;
+use hexstack_core::retry::{RetryPolicy, should_retry_response};
+use hexstack_core::runner::{CommandRunner, Invocation, SystemRunner};
+
+/// Where the release binaries are published, one `hexstack-<target>` asset per target
+const RELEASES_URL: &str = "https://github.com/guru901/hexstack/releases/download";
+
+/// The crates.io sparse index, a static file per crate that CDNs cache well
+const INDEX_URL: &str = "https://index.crates.io";
+
+/// Set to anything but `0` or `false` to turn update checks off, e.g. in CI
+pub const NO_UPDATE_ENV: &str = "HEXSTACK_NO_UPDATE";
+
+/// The outcome of the last update check, persisted between runs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UpdateCheck {
+    /// Seconds since the Unix epoch when crates.io was last asked
+    pub checked_at: u64,
+    /// Every version it listed that isn't yanked, kept across failed checks so the channel
+    /// can change without a new request
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub versions: Vec<String>,
+    /// The index entry's ETag, so an unchanged entry isn't downloaded again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+}
+
+impl UpdateCheck {
+    /// `<cache dir>/hexstack/update-check.toml`, or inside `HEXSTACK_CACHE_DIR` when set
+    pub fn path() -> Result<PathBuf> {
+        Ok(GlobalConfig::cache_dir()?.join("update-check.toml"))
+    }
+
+    /// Reads the cached check; a missing or unreadable cache counts as never checked
+    pub fn load_from(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create '{}'", parent.display()))?;
+        }
+        let contents = toml::to_string(self).context("Failed to serialize the update check")?;
+        fs::write(path, contents).with_context(|| format!("Failed to write '{}'", path.display()))
+    }
+
+    /// Whether the check at `checked_at` is younger than `interval` at `now`
+    pub fn is_fresh(&self, now: u64, interval: Duration) -> bool {
+        now.saturating_sub(self.checked_at) < interval.as_secs()
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Checks crates.io for a newer release and installs it, exiting so the new binary is used
+pub async fn update_if_needed() -> Result<()> {
+    let version = env!("CARGO_PKG_VERSION");
+    let config = GlobalConfig::load().unwrap_or_default();
+    let opt_out = std::env::var(NO_UPDATE_ENV).ok();
+    if !updates_enabled(&config, opt_out.as_deref()) {
+        return Ok(());
+    }
+    let published: Vec<semver::Version> = cached_versions(config.update_check_interval())
+        .await?
+        .iter()
+        .filter_map(|version| semver::Version::parse(version).ok())
+        .collect();
+
+    let current = semver::Version::parse(version)
+        .map_err(|e| anyhow::anyhow!("Invalid current version '{}': {}", version, e))?;
+
+    // Update only to a version on the chosen channel that is strictly greater than this one.
+    if let Some(latest) = config.pick_update(&current, &published) {
+        let latest_version = latest.to_string();
+        say_err!(
+            "A new version of hexstack is available ({} → {})",
+            version,
+            latest_version
+        );
+        // Installing can take minutes, so it only happens when someone is there to agree to it
+        if !is_interactive() {
+            eprintln!("Run hexstack in a terminal to update, or run `cargo install hexstack`");
+            return Ok(());
+        }
+        let confirmed = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Update now?")
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            return Ok(());
+        }
+        println!("Updating...");
+
+        if let Err(err) = install_release(&latest_version).await {
+            say_err!("⚠️  Couldn't install the prebuilt binary: {:#}", err);
+            println!("Falling back to `cargo install hexstack`...");
+            cargo_install().await?;
+        }
+
+        println!("Updated hexstack to the latest version!");
+        println!("Please restart hexstack to use the new version.");
+
+        std::process::exit(0);
+    }
+    Ok(())
+}
+
+/// Whether update checks are on, given the global config and the value of `HEXSTACK_NO_UPDATE`
+pub fn updates_enabled(config: &GlobalConfig, no_update: Option<&str>) -> bool {
+    let opted_out = no_update.is_some_and(|value| {
+        let value = value.trim();
+        !value.is_empty() && value != "0" && !value.eq_ignore_ascii_case("false")
+    });
+    !opted_out && config.check_updates != Some(false)
+}
+
+/// Whether a person can answer the update prompt
+fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stderr().is_terminal()
+}
+
+/// The published versions, from the cache while it is fresher than `interval`. Every attempt is
+/// recorded, including failed ones, so being offline doesn't cost a request per run.
+async fn cached_versions(interval: Duration) -> Result<Vec<String>> {
+    let path = UpdateCheck::path()?;
+    let cached = UpdateCheck::load_from(&path);
+    let now = unix_now();
+    if cached.is_fresh(now, interval) {
+        debug!("Using the update check from {}", path.display());
+        return Ok(cached.versions);
+    }
+
+    let result = published_versions(cached.etag.as_deref()).await;
+    let check = match &result {
+        Ok(Some(entry)) => UpdateCheck {
+            checked_at: now,
+            versions: entry.versions.clone(),
+            etag: entry.etag.clone(),
+        },
+        // Unchanged since the last check, or unreachable: keep what we knew
+        Ok(None) | Err(_) => UpdateCheck {
+            checked_at: now,
+            ..cached
+        },
+    };
+    if let Err(err) = check.save_to(&path) {
+        debug!("Couldn't cache the update check: {:#}", err);
+    }
+    result.map(|_| check.versions)
+}
+
+/// Downloads the release binary for this target, checks it against the published sha256 and
+/// puts it in place of the running executable
+async fn install_release(version: &str) -> Result<()> {
+    let target =
+        release_target().ok_or_else(|| anyhow::anyhow!("No prebuilt binary for this platform"))?;
+    let asset = asset_name(target);
+    let client = http_client()?;
+
+    let binary = download(&client, &release_url(version, &asset)).await?;
+    let checksum = download(&client, &release_url(version, &format!("{}.sha256", asset))).await?;
+    let expected = parse_checksum(&String::from_utf8_lossy(&checksum))
+        .ok_or_else(|| anyhow::anyhow!("{}.sha256 doesn't contain a sha256", asset))?;
+    verify_checksum(&binary, &expected)?;
+
+    let exe = std::env::current_exe().context("Failed to locate the hexstack executable")?;
+    replace_executable(&exe, &binary)
+}
+
+async fn cargo_install() -> Result<()> {
+    let output = SystemRunner
+        .run(&Invocation::new("cargo").args(["install", "hexstack", "--force", "--locked"]))
+        .await?;
+
+    if !output.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!("Failed to update: {}", error));
+    }
+    Ok(())
+}
+
+/// The target triple release binaries are built for, when this platform has one
+pub fn release_target() -> Option<&'static str> {
+    match (std::env::consts::ARCH, std::env::consts::OS) {
+        ("x86_64", "linux") => Some("x86_64-unknown-linux-gnu"),
+        ("aarch64", "linux") => Some("aarch64-unknown-linux-gnu"),
+        ("x86_64", "macos") => Some("x86_64-apple-darwin"),
+        ("aarch64", "macos") => Some("aarch64-apple-darwin"),
+        ("x86_64", "windows") => Some("x86_64-pc-windows-msvc"),
+        _ => None,
+    }
+}
+
+/// File name of the release asset for `target`, e.g. `hexstack-x86_64-pc-windows-msvc.exe`
+pub fn asset_name(target: &str) -> String {
+    let extension = if target.contains("windows") {
+        ".exe"
+    } else {
+        ""
+    };
+    format!("hexstack-{}{}", target, extension)
+}
+
+fn release_url(version: &str, file: &str) -> String {
+    format!("{}/v{}/{}", RELEASES_URL, version, file)
+}
+
+/// Reads the hash out of a `sha256sum`-style line (`<hash>  <file>`)
+pub fn parse_checksum(contents: &str) -> Option<String> {
+    let hash = contents.split_whitespace().next()?;
+    let valid = hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit());
+    valid.then(|| hash.to_ascii_lowercase())
+}
+
+/// Fails unless `contents` hashes to `expected`
+pub fn verify_checksum(contents: &[u8], expected: &str) -> Result<()> {
+    let actual = format!("{:x}", Sha256::digest(contents));
+    if !actual.eq_ignore_ascii_case(expected) {
+        anyhow::bail!(
+            "Checksum mismatch: expected {}, downloaded {}",
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+/// Writes `contents` beside `exe` and renames it over the original, so an interrupted update
+/// never leaves a half-written executable. Windows can't replace a running executable, so the
+/// old one is moved aside first.
+pub fn replace_executable(exe: &Path, contents: &[u8]) -> Result<()> {
+    let staged = sibling(exe, "new");
+    fs::write(&staged, contents)
+        .with_context(|| format!("Failed to write '{}'", staged.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("Failed to make '{}' executable", staged.display()))?;
+    }
+
+    if cfg!(windows) {
+        let old = sibling(exe, "old");
+        let _ = fs::remove_file(&old);
+        fs::rename(exe, &old)
+            .with_context(|| format!("Failed to move '{}' aside", exe.display()))?;
+    }
+
+    fs::rename(&staged, exe).with_context(|| format!("Failed to replace '{}'", exe.display()))
+}
+
+fn sibling(exe: &Path, suffix: &str) -> PathBuf {
+    let mut name = exe.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", suffix));
+    exe.with_file_name(name)
+}
+
+fn http_client() -> Result<reqwest::Client> {
+    proxy::client_builder()
+        .timeout(std::time::Duration::from_secs(3))
+        .user_agent(format!(
+            "hexstack/{} (+https://github.com/guru901/hexstack)",
+            env!("CARGO_PKG_VERSION")
+        ))
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build HTTP client: {}", e))
+}
+
+async fn download(client: &reqwest::Client, url: &str) -> Result<Vec<u8>> {
+    let response = RetryPolicy::default()
+        .run(
+            "The download",
+            || {
+                client
+                    .get(url)
+                    // Release binaries are a few megabytes; the 3 second default is meant for
+                    // API calls
+                    .timeout(std::time::Duration::from_secs(120))
+                    .send()
+            },
+            should_retry_response,
+        )
+        .await
+        .map_err(|e| HexstackError::NetworkError(format!("Failed to download {}: {}", url, e)))?;
+    if !response.status().is_success() {
+        return Err(HexstackError::NetworkError(format!(
+            "Downloading {} failed with status: {}",
+            url,
+            response.status()
+        ))
+        .into());
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| HexstackError::NetworkError(format!("Failed to download {}: {}", url, e)))?;
+    Ok(bytes.to_vec())
+}
+
+/// The versions listed in the sparse index, or `None` when the entry is unchanged since `etag`
+async fn published_versions(etag: Option<&str>) -> Result<Option<IndexEntry>> {
+    let client = http_client()?;
+
+    let url = format!("{}/{}", INDEX_URL, index_path(env!("CARGO_PKG_NAME")));
+    let response = RetryPolicy::default()
+        .run(
+            "The update check",
+            || {
+                let mut request = client.get(&url);
+                if let Some(etag) = etag {
+                    request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+                }
+                request.send()
+            },
+            should_retry_response,
+        )
+        .await
+        .map_err(|e| {
+            HexstackError::NetworkError(format!("Failed to fetch the crates.io index: {}", e))
+        })?;
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(HexstackError::NetworkError(format!(
+            "Index request failed with status: {}",
+            response.status()
+        ))
+        .into());
+    }
+
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let contents = response.text().await.map_err(|e| {
+        HexstackError::NetworkError(format!("Failed to read the crates.io index: {}", e))
+    })?;
+    Ok(Some(IndexEntry {
+        versions: parse_index(&contents),
+        etag,
+    }))
+}
+
+/// A fetched sparse-index entry
+struct IndexEntry {
+    versions: Vec<String>,
+    etag: Option<String>,
+}
+
+/// Where a crate's entry lives in the sparse index, following cargo's layout: `1/a`, `2/ab`,
+/// `3/a/abc`, and `he/xs/hexstack` for longer names
+pub fn index_path(name: &str) -> String {
+    let name = name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{}", name),
+        2 => format!("2/{}", name),
+        3 => format!("3/{}/{}", &name[..1], name),
+        _ => format!("{}/{}/{}", &name[..2], &name[2..4], name),
+    }
+}
+
+/// Reads the versions that aren't yanked out of a sparse-index entry, one JSON object per line
+pub fn parse_index(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|entry| !entry["yanked"].as_bool().unwrap_or(false))
+        .filter_map(|entry| entry["vers"].as_str().map(str::to_string))
+        .collect()
+}
@@ -0,0 +1,336 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::output::say;
+use hexstack_core::error::HexstackError;
+use hexstack_core::integrity;
+use hexstack_core::lockfile::{LOCK_FILE, LockFile};
+use hexstack_core::manifest::{self, TemplateManifest};
+use hexstack_core::retry::RetryPolicy;
+use hexstack_core::runner::{CommandRunner, Invocation, SystemRunner};
+use hexstack_core::setup::{clone_with_retry, run_git};
+use hexstack_core::workspace::{Workspace, copy_dir};
+
+const USAGE: &str = "Usage: hexstack upgrade-template [--to <revision>] [--dry-run]";
+
+/// What happens to a single file when moving the project to the new template revision
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileChange {
+    /// Unchanged locally, so the upstream version is taken as-is
+    Updated,
+    Added,
+    Removed,
+    /// Changed on both sides and merged without conflicts
+    Merged,
+    /// Changed on both sides; the file now contains conflict markers
+    Conflicted,
+    /// Binary file changed on both sides; the upstream version is written next to it
+    BinaryConflict,
+    /// Changed upstream but deleted or modified locally, so it was left alone
+    Kept(&'static str),
+}
+
+pub async fn run(args: &[String]) -> Result<()> {
+    let mut revision = None;
+    let mut dry_run = false;
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--to" => {
+                revision = Some(
+                    args.get(i + 1)
+                        .ok_or_else(|| anyhow::anyhow!("--to requires a value\n\n{}", USAGE))?
+                        .clone(),
+                );
+                i += 2;
+            }
+            "--dry-run" => {
+                dry_run = true;
+                i += 1;
+            }
+            arg => anyhow::bail!("Unknown argument: {}\n\n{}", arg, USAGE),
+        }
+    }
+
+    let project_dir = PathBuf::from(".");
+    let lock_path = project_dir.join(LOCK_FILE);
+    if !lock_path.exists() {
+        anyhow::bail!(
+            "No {} found in the current directory.\nRun this command from the root of a project created by hexstack.",
+            LOCK_FILE
+        );
+    }
+    let mut lock = LockFile::load(&lock_path)?;
+
+    let scratch = tempfile::tempdir().context("Failed to create a temporary directory")?;
+    let repo = scratch.path().join("repo");
+
+    say!("📥 Fetching {}...", lock.template.url);
+    let clone = Invocation::new("git")
+        .args(["clone", "--quiet", lock.template.url.as_str()])
+        .arg(&repo);
+    let output = clone_with_retry(&SystemRunner, RetryPolicy::default(), &clone, &repo)
+        .await
+        .context("Failed to execute git clone command")?;
+    if !output.success() {
+        return Err(HexstackError::git_failed(
+            &["clone", lock.template.url.as_str()],
+            &output.stderr,
+        )
+        .into());
+    }
+
+    let target = run_git(
+        &SystemRunner,
+        &repo,
+        &[
+            "rev-parse",
+            &format!("{}^{{commit}}", revision.as_deref().unwrap_or("HEAD")),
+        ],
+    )
+    .await?;
+    if target == lock.template.commit {
+        say!("✅ Already up to date with {}", short(&target));
+        return Ok(());
+    }
+
+    say!(
+        "🔀 Upgrading template {} → {}",
+        short(&lock.template.commit),
+        short(&target)
+    );
+
+    let subdir = lock.template.subdir.as_deref();
+    let base = scratch.path().join("base");
+    let upstream = scratch.path().join("upstream");
+    let upstream_raw = scratch.path().join("upstream-raw");
+    materialize(&repo, &lock.template.commit, subdir, &base).await?;
+    materialize(&repo, &target, subdir, &upstream).await?;
+    copy_dir(&upstream, &upstream_raw)?;
+
+    // Both sides are rendered like a fresh scaffold so placeholders don't show up as changes
+    for dir in [&base, &upstream] {
+        if TemplateManifest::load(dir)?.is_some() {
+            manifest::apply_placeholders(&Workspace::new(dir), &lock.project_name, &lock.extras)?;
+        }
+    }
+
+    let changes = three_way_upgrade(&project_dir, &base, &upstream, dry_run).await?;
+
+    for (path, change) in &changes {
+        let description = match change {
+            FileChange::Updated => "updated".to_string(),
+            FileChange::Added => "added".to_string(),
+            FileChange::Removed => "removed".to_string(),
+            FileChange::Merged => "merged".to_string(),
+            FileChange::Conflicted => "CONFLICT (resolve the <<<<<<< markers)".to_string(),
+            FileChange::BinaryConflict => {
+                format!(
+                    "CONFLICT (upstream version saved as {}.upstream)",
+                    path.display()
+                )
+            }
+            FileChange::Kept(reason) => format!("kept ({})", reason),
+        };
+        println!("  {:<40} {}", path.display(), description);
+    }
+
+    if dry_run {
+        println!("\nDry run, no files were changed.");
+        return Ok(());
+    }
+
+    lock.hexstack_version = env!("CARGO_PKG_VERSION").to_string();
+    lock.template.commit = target;
+    lock.template.sha256 = Some(integrity::tree_checksum(&upstream_raw)?);
+    lock.save(&lock_path)?;
+
+    let conflicts = changes
+        .iter()
+        .filter(|(_, c)| matches!(c, FileChange::Conflicted | FileChange::BinaryConflict))
+        .count();
+    if conflicts > 0 {
+        say!(
+            "\n⚠️  {} file(s) have conflicts. Resolve them, then review the changes with `git diff`.",
+            conflicts
+        );
+    } else {
+        say!("\n🎉 Template upgraded. Review the changes with `git diff`.");
+    }
+
+    Ok(())
+}
+
+/// Copies the template tree at `commit` (optionally only `subdir`) into `dest`
+async fn materialize(repo: &Path, commit: &str, subdir: Option<&str>, dest: &Path) -> Result<()> {
+    run_git(
+        &SystemRunner,
+        repo,
+        &["checkout", "--quiet", "--force", "--detach", commit],
+    )
+    .await?;
+
+    let source = match subdir {
+        Some(subdir) => repo.join(subdir),
+        None => repo.to_path_buf(),
+    };
+    if !source.is_dir() {
+        anyhow::bail!(
+            "Template revision {} has no '{}' directory",
+            short(commit),
+            subdir.unwrap_or(".")
+        );
+    }
+
+    copy_dir(&source, dest)
+}
+
+/// Applies the difference between `base` and `upstream` to `project`, merging files that
+/// changed on both sides
+pub async fn three_way_upgrade(
+    project: &Path,
+    base: &Path,
+    upstream: &Path,
+    dry_run: bool,
+) -> Result<Vec<(PathBuf, FileChange)>> {
+    let mut paths = integrity::list_files(base)?;
+    paths.extend(integrity::list_files(upstream)?);
+    paths.sort();
+    paths.dedup();
+
+    let mut changes = Vec::new();
+    for relative in paths {
+        if relative.as_os_str() == LOCK_FILE {
+            continue;
+        }
+
+        let base_contents = read_optional(&base.join(&relative))?;
+        let upstream_contents = read_optional(&upstream.join(&relative))?;
+        let ours_path = project.join(&relative);
+        let ours_contents = read_optional(&ours_path)?;
+
+        if base_contents == upstream_contents {
+            continue;
+        }
+
+        let change = match (&base_contents, &upstream_contents, &ours_contents) {
+            // Upstream deleted the file
+            (Some(base), None, Some(ours)) if ours == base => FileChange::Removed,
+            (Some(_), None, Some(_)) => FileChange::Kept("modified locally, deleted upstream"),
+            (_, None, _) => continue,
+            // Upstream added or changed the file
+            (Some(_), Some(_), None) => FileChange::Kept("deleted locally"),
+            (_, Some(upstream), Some(ours)) if ours == upstream => continue,
+            (None, Some(_), None) => FileChange::Added,
+            (Some(base), Some(_), Some(ours)) if ours == base => FileChange::Updated,
+            (base, Some(upstream), Some(ours)) => {
+                let empty = Vec::new();
+                let base = base.as_ref().unwrap_or(&empty);
+                if [base, upstream, ours]
+                    .iter()
+                    .any(|c| std::str::from_utf8(c).is_err())
+                {
+                    FileChange::BinaryConflict
+                } else if dry_run {
+                    FileChange::Merged
+                } else {
+                    let (merged, clean) = merge_file(ours, base, upstream).await?;
+                    fs::write(&ours_path, merged)
+                        .with_context(|| format!("Failed to write '{}'", ours_path.display()))?;
+                    changes.push((
+                        relative,
+                        if clean {
+                            FileChange::Merged
+                        } else {
+                            FileChange::Conflicted
+                        },
+                    ));
+                    continue;
+                }
+            }
+        };
+
+        if !dry_run {
+            match &change {
+                FileChange::Removed => fs::remove_file(&ours_path)
+                    .with_context(|| format!("Failed to remove '{}'", ours_path.display()))?,
+                FileChange::Added | FileChange::Updated => {
+                    if let Some(parent) = ours_path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(&ours_path, upstream_contents.as_deref().unwrap_or_default())
+                        .with_context(|| format!("Failed to write '{}'", ours_path.display()))?;
+                }
+                FileChange::BinaryConflict => {
+                    let mut upstream_copy = ours_path.clone().into_os_string();
+                    upstream_copy.push(".upstream");
+                    fs::write(
+                        &upstream_copy,
+                        upstream_contents.as_deref().unwrap_or_default(),
+                    )
+                    .with_context(|| {
+                        format!(
+                            "Failed to write '{}'",
+                            PathBuf::from(&upstream_copy).display()
+                        )
+                    })?;
+                }
+                _ => {}
+            }
+        }
+
+        changes.push((relative, change));
+    }
+
+    Ok(changes)
+}
+
+/// Runs `git merge-file`, returning the merged contents and whether it merged cleanly
+async fn merge_file(ours: &[u8], base: &[u8], upstream: &[u8]) -> Result<(Vec<u8>, bool)> {
+    let scratch = tempfile::tempdir().context("Failed to create a temporary directory")?;
+    let paths = ["ours", "base", "upstream"].map(|name| scratch.path().join(name));
+    for (path, contents) in paths.iter().zip([ours, base, upstream]) {
+        fs::write(path, contents)?;
+    }
+
+    let merge = Invocation::new("git")
+        .args([
+            "merge-file",
+            "-p",
+            "-L",
+            "ours",
+            "-L",
+            "base",
+            "-L",
+            "upstream",
+        ])
+        .args(&paths);
+    let output = SystemRunner
+        .run(&merge)
+        .await
+        .context("Failed to execute git merge-file")?;
+
+    // The exit code is the number of conflicts; negative values (>127 on unix) are errors
+    match output.code {
+        Some(0) => Ok((output.stdout, true)),
+        Some(code) if (1..=127).contains(&code) => Ok((output.stdout, false)),
+        _ => anyhow::bail!(
+            "git merge-file failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+    }
+}
+
+fn read_optional(path: &Path) -> Result<Option<Vec<u8>>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    fs::read(path)
+        .map(Some)
+        .with_context(|| format!("Failed to read '{}'", path.display()))
+}
+
+fn short(commit: &str) -> &str {
+    &commit[..commit.len().min(8)]
+}
@@ -0,0 +1,579 @@
+//! The full-screen `hexstack new` wizard. Its steps are the questions the command line leaves
+//! open; the answers can be changed by moving back and forth between the steps until the last
+//! one is confirmed. It draws on stderr, so `--json` keeps stdout to itself.
+
+use anyhow::{Context, Result};
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use ratatui::layout::{Constraint, Layout, Position};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols::border;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Tabs};
+use ratatui::{Frame, backend::Backend};
+use std::io::{self, Stderr};
+
+use crate::output;
+use crate::t;
+use hexstack_core::extras::OPTIONAL_EXTRAS;
+use hexstack_core::package_manager::PACKAGE_MANAGERS;
+use hexstack_core::setup::{FRONTENDS, NODE_FRONTENDS, ProjectSetup, display_name};
+use hexstack_core::validation;
+
+/// The questions the wizard can ask, in the order it asks them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardStep {
+    Name,
+    Components,
+    Frontends,
+    /// Only asked while a Node frontend is picked
+    PackageManager,
+    Extras,
+}
+
+impl WizardStep {
+    fn title(self) -> String {
+        match self {
+            WizardStep::Name => t!("wizard-step-name"),
+            WizardStep::Components => t!("wizard-step-components"),
+            WizardStep::Frontends => t!("wizard-step-frontends"),
+            WizardStep::PackageManager => t!("wizard-step-package-manager"),
+            WizardStep::Extras => t!("wizard-step-extras"),
+        }
+    }
+
+    fn prompt(self) -> String {
+        match self {
+            WizardStep::Name => t!("wizard-prompt-name"),
+            WizardStep::Components => t!("wizard-prompt-components"),
+            WizardStep::Frontends => t!("wizard-prompt-frontends"),
+            WizardStep::PackageManager => t!("wizard-prompt-package-manager"),
+            WizardStep::Extras => t!("wizard-prompt-extras"),
+        }
+    }
+}
+
+/// What the wizard was asked for; each field is `Some` when its step was part of the wizard.
+/// Also passed in as the answers each step starts from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WizardAnswers {
+    pub name: Option<String>,
+    pub components: Option<Vec<String>>,
+    pub frontends: Option<Vec<String>>,
+    /// One of [`PACKAGE_MANAGERS`], or [`DETECT_PACKAGE_MANAGER`] to go by the template
+    pub package_manager: Option<String>,
+    pub extras: Option<Vec<String>>,
+}
+
+/// The package manager answer leaving the choice to the template's lock file
+pub const DETECT_PACKAGE_MANAGER: &str = "detect";
+
+/// What a key press did to the wizard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Continue,
+    /// The last step was confirmed
+    Done,
+    /// Ctrl-C
+    Interrupted,
+}
+
+/// A list of keys to pick any number of, or exactly one, narrowed down by typing
+#[derive(Debug)]
+struct Choices {
+    keys: Vec<String>,
+    labels: Vec<String>,
+    picked: Vec<bool>,
+    /// The highlighted item is the one picked
+    single: bool,
+    /// What has been typed to filter the list
+    filter: String,
+    /// The indices of the items matching `filter`, best match first
+    visible: Vec<usize>,
+    /// Position in `visible`
+    cursor: usize,
+}
+
+impl Choices {
+    fn new<'a>(
+        items: impl IntoIterator<Item = (&'a str, String)>,
+        picked: Option<&Vec<String>>,
+    ) -> Self {
+        let (keys, labels): (Vec<String>, Vec<String>) = items
+            .into_iter()
+            .map(|(key, label)| (key.to_string(), label))
+            .unzip();
+        let picked = keys
+            .iter()
+            .map(|key| picked.is_some_and(|picked| picked.contains(key)))
+            .collect();
+        Self {
+            visible: (0..keys.len()).collect(),
+            keys,
+            labels,
+            picked,
+            single: false,
+            filter: String::new(),
+            cursor: 0,
+        }
+    }
+
+    /// A list of which exactly one item is picked, `picked` or else the first
+    fn single<'a>(
+        items: impl IntoIterator<Item = (&'a str, String)>,
+        picked: Option<&str>,
+    ) -> Self {
+        let mut choices = Self::new(items, None);
+        choices.single = true;
+        choices.cursor = picked
+            .and_then(|picked| choices.keys.iter().position(|key| key == picked))
+            .unwrap_or(0);
+        choices.pick_highlighted();
+        choices
+    }
+
+    fn pick_highlighted(&mut self) {
+        if let Some(&i) = self.visible.get(self.cursor) {
+            self.picked = vec![false; self.keys.len()];
+            self.picked[i] = true;
+        }
+    }
+
+    /// Shows the items whose label or key fuzzily matches `filter`, all of them when it's empty
+    fn set_filter(&mut self, filter: String) {
+        let matcher = SkimMatcherV2::default();
+        let mut scored: Vec<(i64, usize)> = (0..self.keys.len())
+            .filter_map(|i| {
+                let label = matcher.fuzzy_match(&self.labels[i], &filter);
+                let key = matcher.fuzzy_match(&self.keys[i], &filter);
+                label.max(key).map(|score| (score, i))
+            })
+            .collect();
+        // Stable, so equally good matches keep the order they are offered in
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        self.visible = scored.into_iter().map(|(_, i)| i).collect();
+        self.filter = filter;
+        self.cursor = 0;
+        if self.single {
+            self.pick_highlighted();
+        }
+    }
+
+    fn picked(&self) -> Vec<String> {
+        self.keys
+            .iter()
+            .zip(&self.picked)
+            .filter(|(_, picked)| **picked)
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    fn handle(&mut self, code: KeyCode) {
+        let count = self.visible.len();
+        match code {
+            KeyCode::Up if count > 0 => {
+                self.cursor = self.cursor.checked_sub(1).unwrap_or(count - 1);
+            }
+            KeyCode::Down if count > 0 => self.cursor = (self.cursor + 1) % count,
+            KeyCode::Char(' ') if self.single => {}
+            KeyCode::Char(' ') => {
+                if let Some(&i) = self.visible.get(self.cursor) {
+                    self.picked[i] = !self.picked[i];
+                }
+            }
+            KeyCode::Char(c) => {
+                let filter = format!("{}{}", self.filter, c);
+                self.set_filter(filter);
+            }
+            KeyCode::Backspace => {
+                let mut filter = self.filter.clone();
+                filter.pop();
+                self.set_filter(filter);
+            }
+            _ => {}
+        }
+        if self.single && matches!(code, KeyCode::Up | KeyCode::Down) {
+            self.pick_highlighted();
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Wizard {
+    steps: Vec<WizardStep>,
+    current: usize,
+    name: String,
+    components: Choices,
+    frontends: Choices,
+    package_manager: Choices,
+    extras: Choices,
+    /// Why the current step can't be left yet
+    error: Option<String>,
+}
+
+impl Wizard {
+    pub fn new(steps: Vec<WizardStep>, defaults: &WizardAnswers) -> Self {
+        let components = ProjectSetup::component_choices();
+        Self {
+            steps,
+            current: 0,
+            name: defaults
+                .name
+                .clone()
+                .unwrap_or_else(|| "my-app".to_string()),
+            components: Choices::new(
+                components.iter().map(|(key, config)| {
+                    (
+                        key.as_str(),
+                        output::text(&format!("{} — {}", display_name(key), config.description))
+                            .into_owned(),
+                    )
+                }),
+                defaults.components.as_ref(),
+            ),
+            frontends: Choices::new(
+                FRONTENDS.iter().map(|key| (*key, display_name(key))),
+                defaults.frontends.as_ref(),
+            ),
+            package_manager: Choices::single(
+                std::iter::once((DETECT_PACKAGE_MANAGER, t!("wizard-detect-package-manager")))
+                    .chain(PACKAGE_MANAGERS.iter().map(|key| (*key, key.to_string()))),
+                defaults.package_manager.as_deref(),
+            ),
+            extras: Choices::new(
+                OPTIONAL_EXTRAS
+                    .iter()
+                    .map(|(key, description)| (*key, description.to_string())),
+                defaults.extras.as_ref(),
+            ),
+            error: None,
+        }
+    }
+
+    pub fn step(&self) -> WizardStep {
+        self.steps[self.current]
+    }
+
+    /// Whether `step` is asked with the answers so far. The package manager is left out when
+    /// the frontends picked in the wizard don't include a Node one.
+    fn applies(&self, step: WizardStep) -> bool {
+        match step {
+            WizardStep::PackageManager if self.steps.contains(&WizardStep::Frontends) => self
+                .frontends
+                .picked()
+                .iter()
+                .any(|frontend| NODE_FRONTENDS.contains(&frontend.as_str())),
+            _ => true,
+        }
+    }
+
+    /// The position in `steps` of the next step that is asked after the current one
+    fn following(&self) -> Option<usize> {
+        (self.current + 1..self.steps.len()).find(|&i| self.applies(self.steps[i]))
+    }
+
+    fn choices(&self) -> Option<&Choices> {
+        match self.step() {
+            WizardStep::Name => None,
+            WizardStep::Components => Some(&self.components),
+            WizardStep::Frontends => Some(&self.frontends),
+            WizardStep::PackageManager => Some(&self.package_manager),
+            WizardStep::Extras => Some(&self.extras),
+        }
+    }
+
+    fn choices_mut(&mut self) -> Option<&mut Choices> {
+        match self.step() {
+            WizardStep::Name => None,
+            WizardStep::Components => Some(&mut self.components),
+            WizardStep::Frontends => Some(&mut self.frontends),
+            WizardStep::PackageManager => Some(&mut self.package_manager),
+            WizardStep::Extras => Some(&mut self.extras),
+        }
+    }
+
+    /// The answers to the wizard's steps, as they stand
+    pub fn answers(&self) -> WizardAnswers {
+        let asked = |step| self.steps.contains(&step) && self.applies(step);
+        WizardAnswers {
+            name: asked(WizardStep::Name).then(|| self.name.clone()),
+            components: asked(WizardStep::Components).then(|| self.components.picked()),
+            frontends: asked(WizardStep::Frontends).then(|| self.frontends.picked()),
+            package_manager: asked(WizardStep::PackageManager)
+                .then(|| self.package_manager.picked().into_iter().next())
+                .flatten(),
+            extras: asked(WizardStep::Extras).then(|| self.extras.picked()),
+        }
+    }
+
+    /// Enter, Tab and Right move to the next step, or finish on the last one; Esc, Shift-Tab
+    /// and Left go back. Left and Right do nothing while typing the name. Typing in a list
+    /// filters it, and Esc clears the filter before it goes back.
+    pub fn handle(&mut self, key: KeyEvent) -> Flow {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Flow::Interrupted;
+        }
+        if key.code == KeyCode::Esc
+            && let Some(choices) = self.choices_mut()
+            && !choices.filter.is_empty()
+        {
+            choices.set_filter(String::new());
+            return Flow::Continue;
+        }
+        let step = self.step();
+        match key.code {
+            KeyCode::Enter | KeyCode::Tab => return self.next(),
+            KeyCode::Right if step != WizardStep::Name => return self.next(),
+            KeyCode::Esc | KeyCode::BackTab => self.back(),
+            KeyCode::Left if step != WizardStep::Name => self.back(),
+            code => match self.choices_mut() {
+                Some(choices) => choices.handle(code),
+                None => {
+                    match code {
+                        KeyCode::Char(c) => self.name.push(c),
+                        KeyCode::Backspace => {
+                            self.name.pop();
+                        }
+                        _ => {}
+                    }
+                    self.error = None;
+                }
+            },
+        }
+        Flow::Continue
+    }
+
+    fn next(&mut self) -> Flow {
+        if self.step() == WizardStep::Name {
+            match validation::validate_project_name(self.name.trim()) {
+                Ok(name) => self.name = name.into_string(),
+                Err(err) => {
+                    // The error suggests a fixed name, which replaces the input for Enter to take
+                    let problem = err.to_string();
+                    let problem = problem.lines().next().unwrap_or_default();
+                    self.error = Some(match validation::suggest_crate_name(&self.name) {
+                        Some(suggestion) => {
+                            let message =
+                                format!("{}. Press Enter to use `{}`", problem, suggestion);
+                            self.name = suggestion;
+                            message
+                        }
+                        None => problem.to_string(),
+                    });
+                    return Flow::Continue;
+                }
+            }
+        }
+        self.error = None;
+        match self.following() {
+            Some(next) => {
+                self.current = next;
+                Flow::Continue
+            }
+            None => Flow::Done,
+        }
+    }
+
+    fn back(&mut self) {
+        self.error = None;
+        if let Some(previous) = (0..self.current)
+            .rev()
+            .find(|&i| self.applies(self.steps[i]))
+        {
+            self.current = previous;
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let dim = Style::new().add_modifier(Modifier::DIM);
+        let [tabs, prompt, body, error, help] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(2),
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let shown: Vec<WizardStep> = self
+            .steps
+            .iter()
+            .copied()
+            .filter(|step| self.applies(*step))
+            .collect();
+        frame.render_widget(
+            Tabs::new(shown.iter().map(|step| step.title()))
+                .select(shown.iter().position(|step| *step == self.step()))
+                .style(dim)
+                .highlight_style(fg(Color::Cyan).add_modifier(Modifier::BOLD | Modifier::REVERSED))
+                .divider(output::symbol("│", "|"))
+                .block(
+                    Block::bordered()
+                        .border_set(if output::plain() {
+                            ASCII_BORDER
+                        } else {
+                            border::PLAIN
+                        })
+                        .title(" hexstack new "),
+                ),
+            tabs,
+        );
+        let mut lines = vec![Line::styled(
+            self.step().prompt(),
+            fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )];
+        if let Some(choices) = self.choices() {
+            lines.push(if choices.filter.is_empty() {
+                Line::styled(t!("wizard-type-to-filter"), dim)
+            } else {
+                Line::from(vec![
+                    Span::styled(format!("{} ", t!("wizard-filter")), dim),
+                    Span::raw(choices.filter.as_str()),
+                ])
+            });
+        }
+        frame.render_widget(Paragraph::new(lines), prompt);
+
+        match self.choices() {
+            None => {
+                let input = Line::from(vec![
+                    Span::styled(output::symbol("› ", "> "), dim),
+                    Span::styled(self.name.as_str(), fg(Color::Green)),
+                ]);
+                frame.render_widget(Paragraph::new(input), body);
+                frame.set_cursor_position(Position::new(
+                    body.x + 2 + self.name.chars().count() as u16,
+                    body.y,
+                ));
+            }
+            Some(choices) if choices.visible.is_empty() => {
+                frame.render_widget(
+                    Paragraph::new(Line::styled(t!("wizard-no-matches"), dim)),
+                    body,
+                );
+            }
+            Some(choices) => {
+                let items = choices.visible.iter().map(|&i| {
+                    let (label, picked) = (&choices.labels[i], choices.picked[i]);
+                    let (marker, style) = match (choices.single, picked) {
+                        (true, true) => (output::symbol("◉ ", "(*) "), fg(Color::Green)),
+                        (true, false) => (output::symbol("○ ", "( ) "), dim),
+                        (false, true) => (output::symbol("✔ ", "[x] "), fg(Color::Green)),
+                        (false, false) => (output::symbol("• ", "[ ] "), dim),
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(marker, style),
+                        Span::raw(label.as_str()),
+                    ]))
+                });
+                let mut state = ListState::default().with_selected(Some(choices.cursor));
+                frame.render_stateful_widget(
+                    List::new(items)
+                        .highlight_style(fg(Color::Cyan).add_modifier(Modifier::BOLD))
+                        .highlight_symbol(output::symbol("› ", "> ")),
+                    body,
+                    &mut state,
+                );
+            }
+        }
+
+        if let Some(message) = &self.error {
+            frame.render_widget(
+                Paragraph::new(Line::styled(message.as_str(), fg(Color::Red))),
+                error,
+            );
+        }
+        let mut keys = Vec::new();
+        if self.choices().is_some() {
+            keys.extend([t!("wizard-key-filter"), t!("wizard-key-move")]);
+        }
+        if self.choices().is_some_and(|choices| !choices.single) {
+            keys.push(t!("wizard-key-select"));
+        }
+        keys.push(if self.following().is_none() {
+            t!("wizard-key-finish")
+        } else {
+            t!("wizard-key-next")
+        });
+        keys.extend([t!("wizard-key-back"), t!("wizard-key-quit")]);
+        let keys = keys.join(" · ");
+        frame.render_widget(
+            Paragraph::new(Line::styled(output::text(&keys).into_owned(), dim)),
+            help,
+        );
+    }
+}
+
+/// The wizard's borders under `--plain`
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// A foreground color, left out when colors are off
+fn fg(color: Color) -> Style {
+    if output::colors() {
+        Style::new().fg(color)
+    } else {
+        Style::new()
+    }
+}
+
+/// Raw mode and the alternate screen on stderr, left again when dropped
+struct Screen(Terminal<CrosstermBackend<Stderr>>);
+
+impl Screen {
+    fn enter() -> Result<Self> {
+        enable_raw_mode().context("Failed to set up the terminal")?;
+        execute!(io::stderr(), EnterAlternateScreen).context("Failed to set up the terminal")?;
+        Ok(Self(Terminal::new(CrosstermBackend::new(io::stderr()))?))
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stderr(), LeaveAlternateScreen);
+        let _ = self.0.backend_mut().show_cursor();
+    }
+}
+
+/// Runs `wizard` until its last step is confirmed. Ctrl-C fails with an interrupted read, like
+/// a dialoguer prompt does.
+pub fn run(mut wizard: Wizard) -> Result<WizardAnswers> {
+    if !console::Term::stderr().is_term() {
+        let steps: Vec<String> = wizard
+            .steps
+            .iter()
+            .map(|step| step.title().to_lowercase())
+            .collect();
+        anyhow::bail!(t!("wizard-needs-terminal", steps = steps.join(", ")));
+    }
+
+    let mut screen = Screen::enter()?;
+    loop {
+        screen.0.draw(|frame| wizard.render(frame))?;
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            match wizard.handle(key) {
+                Flow::Continue => {}
+                Flow::Done => return Ok(wizard.answers()),
+                Flow::Interrupted => return Err(io::Error::from(io::ErrorKind::Interrupted).into()),
+            }
+        }
+    }
+}